@@ -0,0 +1,342 @@
+use ahash::AHashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, process, thread};
+use ule::client::{Client, ClientEvent};
+use ule::{SResult, SimpleError};
+
+const USAGE: &str = "Usage: ule-bench [address] [options]
+  address              server's address(default 127.0.0.1:25565)
+  --connections <n>    concurrent connections(default 100)
+  --duration <secs>    benchmark's duration(default 10)
+  --ramp <secs>        time to open all connections(default 1)
+  --mix <ops>          weights of operations(default status=50,login=30,move=20)
+                         status - status request and ping on new connection
+                         login  - login on new connection
+                         move   - movement packet from logged in player
+  --pid <pid>          server's process for memory usage(default: process named ule)";
+
+// Benchmark's operations
+const OPERATIONS: [&str; 3] = ["status", "login", "move"];
+
+// Benchmark's settings
+#[derive(Clone)]
+struct Settings {
+    address: String,
+    connections: usize,
+    duration: Duration,
+    ramp: Duration,
+    // Weights of OPERATIONS
+    mix: [u32; 3],
+}
+
+// Results of single worker
+#[derive(Default)]
+struct WorkerStats {
+    latencies: [Vec<Duration>; 3],
+    errors: AHashMap<String, usize>,
+}
+
+// Simple xorshift random(enough for choosing operations)
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+// Parsing a number's argument or exit
+fn number(value: Option<String>, name: &str) -> u64 {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(v) => v,
+        None => {
+            eprintln!("Invalid value for {}\n{}", name, USAGE);
+            process::exit(1);
+        }
+    }
+}
+
+// Parsing operations' weights like status=50,login=30,move=20
+fn parse_mix(value: &str) -> Option<[u32; 3]> {
+    let mut mix = [0; 3];
+    for part in value.split(',') {
+        let (name, weight) = part.split_once('=')?;
+        let index = OPERATIONS.iter().position(|v| *v == name.trim())?;
+        mix[index] = weight.trim().parse().ok()?;
+    }
+    if mix.iter().sum::<u32>() == 0 {
+        return None;
+    }
+    Some(mix)
+}
+
+// Status request and ping on new connection
+fn status(address: &str) -> SResult<()> {
+    let mut client = Client::connect(address)?;
+    client.handshake(1)?;
+    client.request_status()?;
+    client.ping(0)?;
+    client.disconnect();
+    Ok(())
+}
+
+// Logging in and waiting for spawn's teleport(it's confirmed by client)
+fn spawn_player(address: &str, name: &str) -> SResult<Client> {
+    let mut client = Client::login(address, name)?;
+    loop {
+        match client.poll(Duration::from_secs(5))? {
+            Some(ClientEvent::Teleport { .. }) => return Ok(client),
+            Some(_) => {}
+            None => return Err(SimpleError(String::from("No spawn's teleport"), None)),
+        }
+    }
+}
+
+// Walking in small circle from confirmed position(received teleports are answered first).
+// Movement isn't answered, so returns only sending's time
+fn walk(client: &mut Client, step: f64) -> SResult<Duration> {
+    while client.poll(Duration::from_millis(1))?.is_some() {}
+    let (x, y, z) = (client.x, client.y, client.z);
+    let start = Instant::now();
+    client.move_to(x + step.cos() * 0.2, y, z + step.sin() * 0.2, true)?;
+    Ok(start.elapsed())
+}
+
+// Single connection's worker
+fn worker(settings: &Settings, id: usize, running: &AtomicBool) -> WorkerStats {
+    let mut stats = WorkerStats::default();
+    let mut random = Random(0x9E37_79B9_7F4A_7C15 ^ (id as u64 + 1).wrapping_mul(0x2545_F491));
+    let total: u32 = settings.mix.iter().sum();
+    // Logged in player for movement
+    let mut player: Option<Client> = None;
+    let mut step = 0f64;
+    // Unique names, so new sessions don't kick still opened ones
+    let mut logins = 0u32;
+    while running.load(Ordering::Relaxed) {
+        // Choosing operation by weights
+        let mut roll = (random.next() % total as u64) as u32;
+        let mut operation = 0;
+        while roll >= settings.mix[operation] {
+            roll -= settings.mix[operation];
+            operation += 1;
+        }
+        logins = (logins + 1) % 100_000;
+        let start = Instant::now();
+        let result = match operation {
+            0 => status(&settings.address),
+            1 => {
+                Client::login(settings.address.as_str(), &format!("B{}_{}", id, logins)).map(|v| {
+                    v.disconnect();
+                })
+            }
+            _ => {
+                if player.is_none() {
+                    match spawn_player(&settings.address, &format!("M{}_{}", id, logins)) {
+                        Ok(v) => player = Some(v),
+                        Err(err) => {
+                            *stats.errors.entry(format!("move: {}", err)).or_default() += 1;
+                            continue;
+                        }
+                    }
+                }
+                let client = player.as_mut().unwrap();
+                step += 0.1;
+                let result = walk(client, step);
+                if result.is_err() {
+                    player = None;
+                }
+                match result {
+                    Ok(v) => stats.latencies[2].push(v),
+                    Err(err) => *stats.errors.entry(format!("move: {}", err)).or_default() += 1,
+                }
+                // Player sends up to 20 movements per second
+                thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+        };
+        match result {
+            Ok(_) => stats.latencies[operation].push(start.elapsed()),
+            Err(err) => {
+                *stats
+                    .errors
+                    .entry(format!("{}: {}", OPERATIONS[operation], err))
+                    .or_default() += 1
+            }
+        }
+    }
+    if let Some(client) = player {
+        client.disconnect();
+    }
+    stats
+}
+
+// Finding server's process by name
+fn find_server_pid() -> Option<u32> {
+    fs::read_dir("/proc")
+        .ok()?
+        .filter_map(|v| v.ok())
+        .filter_map(|v| v.file_name().to_str()?.parse::<u32>().ok())
+        .find(|pid| {
+            fs::read_to_string(format!("/proc/{}/comm", pid))
+                .map(|v| v.trim() == "ule")
+                .unwrap_or(false)
+        })
+}
+
+// Server's resident memory in KiB(only on Linux)
+fn server_memory(pid: u32) -> Option<u64> {
+    fs::read_to_string(format!("/proc/{}/status", pid))
+        .ok()?
+        .lines()
+        .find(|v| v.starts_with("VmRSS:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+// Value at percentile from sorted latencies
+fn percentile(sorted: &[Duration], percent: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * percent / 100.0).round() as usize;
+    sorted[index].as_secs_f64() * 1000.0
+}
+
+fn main() {
+    let mut settings = Settings {
+        address: String::from("127.0.0.1:25565"),
+        connections: 100,
+        duration: Duration::from_secs(10),
+        ramp: Duration::from_secs(1),
+        mix: [50, 30, 20],
+    };
+    let mut pid = None;
+    // Parsing arguments
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--connections" => {
+                settings.connections = number(args.next(), "--connections").max(1) as usize
+            }
+            "--duration" => {
+                settings.duration = Duration::from_secs(number(args.next(), "--duration"))
+            }
+            "--ramp" => settings.ramp = Duration::from_secs(number(args.next(), "--ramp")),
+            "--mix" => {
+                settings.mix = match args.next().as_deref().and_then(parse_mix) {
+                    Some(v) => v,
+                    None => {
+                        eprintln!("Invalid value for --mix\n{}", USAGE);
+                        process::exit(1);
+                    }
+                }
+            }
+            "--pid" => pid = Some(number(args.next(), "--pid") as u32),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if !arg.starts_with('-') => settings.address = arg,
+            _ => {
+                eprintln!("Unknown argument: {}\n{}", arg, USAGE);
+                process::exit(1);
+            }
+        }
+    }
+    // Checking server before benchmark
+    if let Err(err) = Client::status(settings.address.as_str()) {
+        eprintln!("Server {} isn't available: {}", settings.address, err);
+        process::exit(1);
+    }
+    let pid = pid.or_else(find_server_pid);
+    let memory_before = pid.and_then(server_memory);
+    println!(
+        "Benchmarking {} with {} connections for {}s...",
+        settings.address,
+        settings.connections,
+        settings.duration.as_secs()
+    );
+    // Starting workers
+    let running = Arc::new(AtomicBool::new(true));
+    let start = Instant::now();
+    let delay = settings.ramp / settings.connections as u32;
+    let mut workers = Vec::with_capacity(settings.connections);
+    for id in 0..settings.connections {
+        let settings = settings.clone();
+        let running = running.clone();
+        let worker = thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(move || worker(&settings, id, &running));
+        match worker {
+            Ok(v) => workers.push(v),
+            Err(err) => {
+                eprintln!("Failed to start worker #{}: {}", id, err);
+                break;
+            }
+        }
+        thread::sleep(delay);
+    }
+    // Sampling server's memory until the end
+    let mut memory_peak = memory_before;
+    while start.elapsed() < settings.duration {
+        thread::sleep(Duration::from_millis(250));
+        if let Some(memory) = pid.and_then(server_memory) {
+            memory_peak = Some(memory_peak.unwrap_or(0).max(memory));
+        }
+    }
+    running.store(false, Ordering::Relaxed);
+    // Collecting results
+    let mut latencies: [Vec<Duration>; 3] = Default::default();
+    let mut errors: AHashMap<String, usize> = AHashMap::new();
+    for worker in workers {
+        if let Ok(stats) = worker.join() {
+            for (all, worker) in latencies.iter_mut().zip(stats.latencies) {
+                all.extend(worker);
+            }
+            for (error, count) in stats.errors {
+                *errors.entry(error).or_default() += count;
+            }
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    // Printing report
+    println!(
+        "{:<8} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "op", "count", "ops/s", "p50 ms", "p90 ms", "p99 ms", "max ms"
+    );
+    for (name, latencies) in OPERATIONS.iter().zip(latencies.iter_mut()) {
+        latencies.sort();
+        println!(
+            "{:<8} {:>10} {:>10.1} {:>10.3} {:>10.3} {:>10.3} {:>10.3}",
+            name,
+            latencies.len(),
+            latencies.len() as f64 / elapsed,
+            percentile(latencies, 50.0),
+            percentile(latencies, 90.0),
+            percentile(latencies, 99.0),
+            percentile(latencies, 100.0)
+        );
+    }
+    let total_errors: usize = errors.values().sum();
+    println!("errors: {}", total_errors);
+    let mut errors: Vec<_> = errors.into_iter().collect();
+    errors.sort_by_key(|v| std::cmp::Reverse(v.1));
+    for (error, count) in errors {
+        println!("  {:>8} {}", count, error);
+    }
+    match (memory_before, memory_peak, pid.and_then(server_memory)) {
+        (Some(before), Some(peak), Some(after)) => println!(
+            "server memory(RSS): before {} KiB, peak {} KiB, after {} KiB",
+            before, peak, after
+        ),
+        _ => println!("server memory(RSS): unknown(use --pid on Linux)"),
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, process, thread};
+use ule::client::{Client, ClientEvent};
+
+const USAGE: &str = "Usage: ule-bot [address] [options]
+  address            server's address(default 127.0.0.1:25565)
+  --bots <count>     count of simulated players(default 1)
+  --name <prefix>    player's name prefix, bots are named <prefix><number>(default Bot)
+  --chat <message>   send the chat's message after joining
+  --move             walk around the spawn
+  --duration <secs>  time to stay online(default 10)
+  --delay <ms>       delay between bot's logins(default 50)
+  --status           only print server's status and ping";
+
+// Bot's settings
+#[derive(Clone)]
+struct Settings {
+    address: String,
+    bots: usize,
+    name: String,
+    chat: Option<String>,
+    walk: bool,
+    duration: Duration,
+    delay: Duration,
+}
+
+// Parsing a number's argument or exit
+fn number(value: Option<String>, name: &str) -> u64 {
+    match value.and_then(|v| v.parse().ok()) {
+        Some(v) => v,
+        None => {
+            eprintln!("Invalid value for {}\n{}", name, USAGE);
+            process::exit(1);
+        }
+    }
+}
+
+// Printing status and ping
+fn status(address: &str) {
+    match Client::status(address) {
+        Ok(v) => println!("Status: {}", v),
+        Err(err) => {
+            eprintln!("Failed to get status: {}", err);
+            process::exit(1);
+        }
+    }
+    let ping = Client::connect(address).and_then(|mut client| {
+        client.handshake(1)?;
+        client.ping(0)
+    });
+    match ping {
+        Ok(v) => println!("Ping: {:.3}ms", v.as_secs_f64() * 1000.0),
+        Err(err) => eprintln!("Failed to ping: {}", err),
+    }
+}
+
+// Single bot's life
+fn run_bot(settings: &Settings, name: String, online: &AtomicUsize) {
+    let mut client = match Client::login(settings.address.as_str(), &name) {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("[{}] Failed to login: {}", name, err);
+            return;
+        }
+    };
+    println!(
+        "[{}] Logged in({} online)",
+        name,
+        online.fetch_add(1, Ordering::SeqCst) + 1
+    );
+    if let Some(message) = &settings.chat {
+        if let Err(err) = client.send_chat(message) {
+            eprintln!("[{}] Failed to send chat's message: {}", name, err);
+        }
+    }
+    let start = Instant::now();
+    let mut angle: f64 = 0.0;
+    while start.elapsed() < settings.duration {
+        // Handing incoming packets(up to one tick)
+        match client.poll(Duration::from_millis(50)) {
+            Ok(Some(ClientEvent::Chat(message, _))) => println!("[{}] Chat: {}", name, message),
+            Ok(Some(ClientEvent::Disconnect(reason))) => {
+                println!("[{}] Kicked: {}", name, reason);
+                break;
+            }
+            Ok(Some(ClientEvent::Teleport { x, y, z, .. })) => {
+                println!("[{}] Teleported to {:.2} {:.2} {:.2}", name, x, y, z)
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("[{}] Connection lost: {}", name, err);
+                break;
+            }
+        }
+        // Walking around by circle
+        if settings.walk {
+            angle += 0.05;
+            let (x, z) = (client.x + angle.cos() * 0.2, client.z + angle.sin() * 0.2);
+            let yaw = (angle.to_degrees() + 90.0) as f32;
+            if let Err(err) = client.move_and_look(x, client.y, z, yaw, 0.0, true) {
+                eprintln!("[{}] Connection lost: {}", name, err);
+                break;
+            }
+        }
+    }
+    client.disconnect();
+    online.fetch_sub(1, Ordering::SeqCst);
+    println!("[{}] Disconnected", name);
+}
+
+fn main() {
+    let mut settings = Settings {
+        address: String::from("127.0.0.1:25565"),
+        bots: 1,
+        name: String::from("Bot"),
+        chat: None,
+        walk: false,
+        duration: Duration::from_secs(10),
+        delay: Duration::from_millis(50),
+    };
+    let mut only_status = false;
+    // Parsing arguments
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bots" => settings.bots = number(args.next(), "--bots") as usize,
+            "--name" => settings.name = args.next().unwrap_or(settings.name),
+            "--chat" => settings.chat = args.next(),
+            "--move" => settings.walk = true,
+            "--duration" => {
+                settings.duration = Duration::from_secs(number(args.next(), "--duration"))
+            }
+            "--delay" => settings.delay = Duration::from_millis(number(args.next(), "--delay")),
+            "--status" => only_status = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if !arg.starts_with('-') => settings.address = arg,
+            _ => {
+                eprintln!("Unknown argument: {}\n{}", arg, USAGE);
+                process::exit(1);
+            }
+        }
+    }
+    if only_status {
+        status(&settings.address);
+        return;
+    }
+    // Starting bots
+    let online = Arc::new(AtomicUsize::new(0));
+    let mut bots = Vec::with_capacity(settings.bots);
+    for i in 0..settings.bots {
+        let name = format!("{}{}", settings.name, i);
+        let bot_settings = settings.clone();
+        let online = online.clone();
+        bots.push(thread::spawn(move || run_bot(&bot_settings, name, &online)));
+        thread::sleep(settings.delay);
+    }
+    for bot in bots {
+        bot.join().ok();
+    }
+}
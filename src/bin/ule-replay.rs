@@ -0,0 +1,222 @@
+use ahash::AHashMap;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use std::{env, process, thread};
+use ule::network::capture::{CaptureEvent, CaptureReader};
+use ule::network::proto::read_frame;
+use ule::network::proto::registry::{decode_frame, state_after, Direction};
+use ule::network::ConnectionType;
+
+const USAGE: &str = "Usage: ule-replay <capture> [--send <address>] [--fast]
+  <capture>          file written by `ule --capture <capture>`
+  --send <address>   re-send serverbound packets to running ULE and print responses
+  --fast             don't wait between packets as in original capture";
+
+// Replaying connection(for --send mode)
+struct ReplayConnection {
+    stream: TcpStream,
+    state: Arc<Mutex<ConnectionType>>,
+    reader: JoinHandle<()>,
+}
+
+// Printing a decoded frame
+fn print_frame(
+    time: Duration,
+    conn: usize,
+    state: ConnectionType,
+    direction: Direction,
+    frame: &[u8],
+) {
+    let arrow = match direction {
+        Direction::Serverbound => "C->S",
+        Direction::Clientbound => "S->C",
+    };
+    match decode_frame(state, direction, frame) {
+        Ok(packet) => println!(
+            "[{:>10.3}ms] #{} {} {:?} 0x{:02X} {} {}",
+            time.as_secs_f64() * 1000.0,
+            conn,
+            arrow,
+            state,
+            packet.id,
+            packet.name.unwrap_or("Unknown"),
+            packet.fields
+        ),
+        Err(err) => println!(
+            "[{:>10.3}ms] #{} {} {:?} malformed packet({} bytes): {}",
+            time.as_secs_f64() * 1000.0,
+            conn,
+            arrow,
+            state,
+            frame.len(),
+            err
+        ),
+    }
+}
+
+// Opening a connection to server and start printing its responses
+fn open_connection(address: &str, conn: usize, start: Instant) -> Option<ReplayConnection> {
+    let stream = match TcpStream::connect(address) {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("#{} failed to connect to {}: {}", conn, address, err);
+            return None;
+        }
+    };
+    let state = Arc::new(Mutex::new(ConnectionType::HANDSHAKING));
+    let reader = thread::spawn({
+        let mut stream = stream.try_clone().unwrap();
+        let state = state.clone();
+        move || {
+            // Reading until server closes the connection
+            while let Ok(frame) = read_frame(&mut stream) {
+                let mut state = state.lock().unwrap();
+                print_frame(
+                    start.elapsed(),
+                    conn,
+                    *state,
+                    Direction::Clientbound,
+                    &frame,
+                );
+                *state = state_after(*state, Direction::Clientbound, &frame);
+            }
+        }
+    });
+    Some(ReplayConnection {
+        stream,
+        state,
+        reader,
+    })
+}
+
+fn main() {
+    // Parsing arguments
+    let mut capture = None;
+    let mut send = None;
+    let mut fast = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--send" => send = args.next(),
+            "--fast" => fast = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if capture.is_none() => capture = Some(arg),
+            _ => {
+                eprintln!("{}", USAGE);
+                process::exit(1);
+            }
+        }
+    }
+    let capture = match capture {
+        Some(v) => v,
+        None => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+    // Opening capture
+    let mut reader = match File::open(&capture).and_then(|v| CaptureReader::new(BufReader::new(v)))
+    {
+        Ok(v) => v,
+        Err(err) => {
+            eprintln!("Failed to open capture {}: {}", capture, err);
+            process::exit(1);
+        }
+    };
+    // Connection's states(for decoding) and replaying connections
+    let mut states: AHashMap<usize, ConnectionType> = AHashMap::new();
+    let mut connections: AHashMap<usize, ReplayConnection> = AHashMap::new();
+    let mut readers = Vec::new();
+    let start = Instant::now();
+    loop {
+        let record = match reader.next_record() {
+            Ok(Some(v)) => v,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Failed to read capture: {}", err);
+                break;
+            }
+        };
+        // Waiting as in original capture
+        if send.is_some() && !fast {
+            if let Some(wait) = record.time.checked_sub(start.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+        match (record.event, &send) {
+            // Printing capture
+            (CaptureEvent::Open(address), None) => {
+                states.insert(record.conn, ConnectionType::HANDSHAKING);
+                println!(
+                    "[{:>10.3}ms] #{} opened from {}",
+                    record.time.as_secs_f64() * 1000.0,
+                    record.conn,
+                    address
+                );
+            }
+            (CaptureEvent::Frame(direction, frame), None) => {
+                let state = states
+                    .entry(record.conn)
+                    .or_insert(ConnectionType::HANDSHAKING);
+                print_frame(record.time, record.conn, *state, direction, &frame);
+                *state = state_after(*state, direction, &frame);
+            }
+            (CaptureEvent::Close, None) => {
+                states.remove(&record.conn);
+                println!(
+                    "[{:>10.3}ms] #{} closed",
+                    record.time.as_secs_f64() * 1000.0,
+                    record.conn
+                );
+            }
+            // Re-sending to server
+            (CaptureEvent::Open(_), Some(address)) => {
+                if let Some(conn) = open_connection(address, record.conn, start) {
+                    connections.insert(record.conn, conn);
+                }
+            }
+            (CaptureEvent::Frame(Direction::Serverbound, frame), Some(_)) => {
+                if let Some(conn) = connections.get_mut(&record.conn) {
+                    {
+                        let mut state = conn.state.lock().unwrap();
+                        print_frame(
+                            start.elapsed(),
+                            record.conn,
+                            *state,
+                            Direction::Serverbound,
+                            &frame,
+                        );
+                        *state = state_after(*state, Direction::Serverbound, &frame);
+                    }
+                    if let Err(err) = conn.stream.write_all(&frame) {
+                        eprintln!("#{} failed to send packet: {}", record.conn, err);
+                    }
+                }
+            }
+            // Original responses aren't needed, server will send new ones
+            (CaptureEvent::Frame(Direction::Clientbound, _), Some(_)) => {}
+            (CaptureEvent::Close, Some(_)) => {
+                if let Some(conn) = connections.remove(&record.conn) {
+                    // Server closes connection after reading all packets
+                    conn.stream.shutdown(Shutdown::Write).ok();
+                    readers.push(conn.reader);
+                }
+            }
+        }
+    }
+    // Waiting for all responses
+    for (_, conn) in connections.drain() {
+        conn.stream.shutdown(Shutdown::Write).ok();
+        readers.push(conn.reader);
+    }
+    for reader in readers {
+        reader.join().ok();
+    }
+}
@@ -0,0 +1,448 @@
+use crate::config::PROTOCOL_VERSION;
+use crate::network::proto::{frame_size, PacketReader, PacketWriter};
+use crate::network::ConnectionType;
+use crate::utils::uuid::uuid_from_string;
+use crate::world::item::ItemStack;
+use crate::{io_error, SResult, SimpleError};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+// Default timeout for connecting and waiting for responses
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Event received by client on play stage
+#[derive(Debug, Clone)]
+pub enum ClientEvent {
+    // Server sent Keep Alive(already answered by client)
+    KeepAlive(i64),
+    // Chat's message as JSON and its position(0 - chat, 1 - system, 2 - hotbar)
+    Chat(String, i8),
+    // Server moved player(already confirmed by client)
+    Teleport {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+    },
+    // Player joined the world
+    JoinGame {
+        entity_id: i32,
+        gamemode: u8,
+        dimension: i32,
+    },
+    // Chunk's sections(full chunk replaces client's chunk)
+    ChunkData {
+        x: i32,
+        z: i32,
+        full_chunk: bool,
+        mask: i32,
+    },
+    // Chunk is unloaded
+    UnloadChunk {
+        x: i32,
+        z: i32,
+    },
+    // Server kicked player with the reason as JSON
+    Disconnect(String),
+    // Any another packet with its PacketID and content
+    Packet(i32, Vec<u8>),
+}
+
+/// Headless protocol's client(works like a vanilla client on the wire)
+pub struct Client {
+    stream: TcpStream,
+    // Received, but not handled bytes
+    input: Vec<u8>,
+    pub address: SocketAddr,
+    pub state: ConnectionType,
+    // Player's name and UUID after login
+    pub name: String,
+    pub uuid: u128,
+    // Player's entity's id and game's mode after Join Game
+    pub entity_id: i32,
+    pub gamemode: u8,
+    // Player's position and rotation
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+impl Client {
+    /// Connecting to server(without sending anything)
+    pub fn connect<A: ToSocketAddrs>(address: A) -> SResult<Self> {
+        let address = address
+            .to_socket_addrs()
+            .map_err(|err| io_error("Invalid address", err))?
+            .next()
+            .ok_or_else(|| SimpleError(String::from("Invalid address"), None))?;
+        let stream = TcpStream::connect_timeout(&address, DEFAULT_TIMEOUT)
+            .map_err(|err| io_error("Failed to connect", err))?;
+        stream.set_nodelay(true);
+        Ok(Self {
+            stream,
+            input: Vec::new(),
+            address,
+            state: ConnectionType::HANDSHAKING,
+            name: String::new(),
+            uuid: 0,
+            entity_id: 0,
+            gamemode: 0,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            on_ground: true,
+        })
+    }
+
+    /// Sending a frame(length prefix + packet)
+    pub fn send(&mut self, frame: &[u8]) -> SResult<()> {
+        self.stream
+            .write_all(frame)
+            .map_err(|err| io_error("Failed to send packet", err))
+    }
+
+    /// Waiting for next frame(length prefix + packet). Returns None on timeout
+    pub fn receive(&mut self, timeout: Duration) -> SResult<Option<Vec<u8>>> {
+        let deadline = Instant::now() + timeout;
+        let mut bytes = [0u8; 4096];
+        loop {
+            // Returning fully received frame
+            match frame_size(&self.input) {
+                Ok(Some(size)) => return Ok(Some(self.input.drain(..size).collect())),
+                Ok(None) => {}
+                Err(err) => return Err(io_error("Invalid frame", err)),
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Ok(None);
+            }
+            self.stream.set_read_timeout(Some(left));
+            match self.stream.read(&mut bytes) {
+                Ok(0) => return Err(SimpleError(String::from("Connection closed"), None)),
+                Ok(n) => self.input.extend_from_slice(&bytes[..n]),
+                Err(ref err)
+                    if err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut =>
+                {
+                    return Ok(None)
+                }
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(io_error("Failed to read packet", err)),
+            }
+        }
+    }
+
+    // Waiting for next packet and returning its PacketID and content
+    fn expect_packet(&mut self) -> SResult<(i32, Vec<u8>)> {
+        match self.receive(DEFAULT_TIMEOUT)? {
+            Some(mut p) => {
+                let (_, pid) = p.read_base()?;
+                Ok((pid, p))
+            }
+            None => Err(SimpleError(String::from("Server isn't responding"), None)),
+        }
+    }
+
+    /// Sending a [handshake](https://wiki.vg/index.php?title=Protocol&oldid=14204#Handshake) with next state(1 - status, 2 - login)
+    pub fn handshake(&mut self, next_state: i32) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_varint(PROTOCOL_VERSION as i32);
+        bytes.write_string(self.address.ip().to_string());
+        bytes.write_u16(self.address.port());
+        bytes.write_varint(next_state);
+        self.send(&bytes.create_packet(0x00))?;
+        self.state = if next_state == 1 {
+            ConnectionType::STATUS
+        } else {
+            ConnectionType::LOGIN
+        };
+        Ok(())
+    }
+
+    /// Requesting server's status as JSON(connection must be on status stage)
+    pub fn request_status(&mut self) -> SResult<String> {
+        self.send(&Vec::new().create_packet(0x00))?;
+        let (pid, mut p) = self.expect_packet()?;
+        if pid != 0x00 {
+            return Err(SimpleError(
+                format!("Unexpected packet 0x{:02X}", pid),
+                None,
+            ));
+        }
+        p.get_string()
+    }
+
+    /// Measuring a round-trip by Ping-Pong(connection must be on status stage)
+    pub fn ping(&mut self, payload: i64) -> SResult<Duration> {
+        let start = Instant::now();
+        let mut bytes = Vec::new();
+        bytes.write_i64(payload);
+        self.send(&bytes.create_packet(0x01))?;
+        let (pid, mut p) = self.expect_packet()?;
+        if pid != 0x01 || p.get_i64()? != payload {
+            return Err(SimpleError(String::from("Invalid pong"), None));
+        }
+        Ok(start.elapsed())
+    }
+
+    /// Getting server's status JSON in a new connection
+    pub fn status<A: ToSocketAddrs>(address: A) -> SResult<String> {
+        let mut client = Client::connect(address)?;
+        client.handshake(1)?;
+        client.request_status()
+    }
+
+    /// Connecting and logging in as player with the name
+    pub fn login<A: ToSocketAddrs>(address: A, name: &str) -> SResult<Self> {
+        let mut client = Client::connect(address)?;
+        client.handshake(2)?;
+        // Login Start
+        let mut bytes = Vec::new();
+        bytes.write_string(name.to_string());
+        client.send(&bytes.create_packet(0x00))?;
+        // Waiting for Login Success
+        let (pid, mut p) = client.expect_packet()?;
+        match pid {
+            // Disconnect
+            0x00 => Err(SimpleError(format!("Kicked: {}", p.get_string()?), None)),
+            // Encryption Request
+            0x01 => Err(SimpleError(
+                String::from("Online mode isn't supported"),
+                None,
+            )),
+            // Login Success
+            0x02 => {
+                let uuid = p.get_string()?;
+                client.uuid = uuid_from_string(&uuid)
+                    .ok_or_else(|| SimpleError(format!("Invalid UUID: {}", uuid), None))?;
+                client.name = p.get_string()?;
+                client.state = ConnectionType::PLAY;
+                Ok(client)
+            }
+            _ => Err(SimpleError(
+                format!("Unexpected packet 0x{:02X}", pid),
+                None,
+            )),
+        }
+    }
+
+    /// Waiting for next event on play stage. Returns None on timeout.
+    /// Keep Alive and teleports are answered automatically.
+    pub fn poll(&mut self, timeout: Duration) -> SResult<Option<ClientEvent>> {
+        let mut p = match self.receive(timeout)? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let (_, pid) = p.read_base()?;
+        Ok(Some(match pid {
+            // Chat Message
+            0x0F => {
+                let message = p.get_string()?;
+                ClientEvent::Chat(message, p.get_i8()?)
+            }
+            // Disconnect
+            0x1A => ClientEvent::Disconnect(p.get_string()?),
+            // Unload Chunk
+            0x1D => ClientEvent::UnloadChunk {
+                x: p.get_i32()?,
+                z: p.get_i32()?,
+            },
+            // Chunk Data(sections aren't decoded)
+            0x20 => ClientEvent::ChunkData {
+                x: p.get_i32()?,
+                z: p.get_i32()?,
+                full_chunk: p.get_bool()?,
+                mask: p.get_varint()?,
+            },
+            // Join Game
+            0x23 => {
+                self.entity_id = p.get_i32()?;
+                self.gamemode = p.get_u8()?;
+                ClientEvent::JoinGame {
+                    entity_id: self.entity_id,
+                    gamemode: self.gamemode,
+                    dimension: p.get_i32()?,
+                }
+            }
+            // Keep Alive
+            0x1F => {
+                let id = p.get_i64()?;
+                self.send_keep_alive(id)?;
+                ClientEvent::KeepAlive(id)
+            }
+            // Player Position And Look
+            0x2F => {
+                let (x, y, z) = (p.get_f64()?, p.get_f64()?, p.get_f64()?);
+                let (yaw, pitch) = (p.get_f32()?, p.get_f32()?);
+                let flags = p.get_u8()?;
+                let teleport_id = p.get_varint()?;
+                // Flags are set for relative values
+                self.x = if flags & 0x01 != 0 { self.x + x } else { x };
+                self.y = if flags & 0x02 != 0 { self.y + y } else { y };
+                self.z = if flags & 0x04 != 0 { self.z + z } else { z };
+                self.yaw = if flags & 0x08 != 0 {
+                    self.yaw + yaw
+                } else {
+                    yaw
+                };
+                self.pitch = if flags & 0x10 != 0 {
+                    self.pitch + pitch
+                } else {
+                    pitch
+                };
+                self.send_teleport_confirm(teleport_id)?;
+                ClientEvent::Teleport {
+                    x: self.x,
+                    y: self.y,
+                    z: self.z,
+                    yaw: self.yaw,
+                    pitch: self.pitch,
+                }
+            }
+            _ => ClientEvent::Packet(pid, p),
+        }))
+    }
+
+    /// Sending a Teleport Confirm
+    pub fn send_teleport_confirm(&mut self, teleport_id: i32) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_varint(teleport_id);
+        self.send(&bytes.create_packet(0x00))
+    }
+
+    /// Sending a Client Settings with view distance(another settings like vanilla's defaults)
+    pub fn send_client_settings(&mut self, view_distance: i8) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_string(String::from("en_us"));
+        bytes.write_i8(view_distance);
+        // Chat is enabled with colors
+        bytes.write_varint(0);
+        bytes.write_bool(true);
+        // All skin's parts and right hand
+        bytes.write_u8(0x7F);
+        bytes.write_varint(1);
+        self.send(&bytes.create_packet(0x04))
+    }
+
+    /// Sending a Keep Alive's answer
+    pub fn send_keep_alive(&mut self, id: i64) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_i64(id);
+        self.send(&bytes.create_packet(0x0B))
+    }
+
+    /// Sending a chat's message(or command if starts with /)
+    pub fn send_chat(&mut self, message: &str) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_string(message.to_string());
+        self.send(&bytes.create_packet(0x02))
+    }
+
+    /// Moving player to the position
+    pub fn move_to(&mut self, x: f64, y: f64, z: f64, on_ground: bool) -> SResult<()> {
+        self.x = x;
+        self.y = y;
+        self.z = z;
+        self.on_ground = on_ground;
+        let mut bytes = Vec::new();
+        bytes.write_f64(x);
+        bytes.write_f64(y);
+        bytes.write_f64(z);
+        bytes.write_bool(on_ground);
+        self.send(&bytes.create_packet(0x0D))
+    }
+
+    /// Rotating player's head
+    pub fn look(&mut self, yaw: f32, pitch: f32, on_ground: bool) -> SResult<()> {
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.on_ground = on_ground;
+        let mut bytes = Vec::new();
+        bytes.write_f32(yaw);
+        bytes.write_f32(pitch);
+        bytes.write_bool(on_ground);
+        self.send(&bytes.create_packet(0x0F))
+    }
+
+    /// Moving and rotating player
+    pub fn move_and_look(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+        on_ground: bool,
+    ) -> SResult<()> {
+        self.x = x;
+        self.y = y;
+        self.z = z;
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.on_ground = on_ground;
+        let mut bytes = Vec::new();
+        bytes.write_f64(x);
+        bytes.write_f64(y);
+        bytes.write_f64(z);
+        bytes.write_f32(yaw);
+        bytes.write_f32(pitch);
+        bytes.write_bool(on_ground);
+        self.send(&bytes.create_packet(0x0E))
+    }
+
+    /// Sending a Player packet(only on ground's state)
+    pub fn stand(&mut self, on_ground: bool) -> SResult<()> {
+        self.on_ground = on_ground;
+        let mut bytes = Vec::new();
+        bytes.write_bool(on_ground);
+        self.send(&bytes.create_packet(0x0C))
+    }
+
+    /// Clicking window's slot(clicked - item which client expects in the slot before click)
+    pub fn click_window(
+        &mut self,
+        window_id: u8,
+        slot: i16,
+        button: i8,
+        action: i16,
+        mode: i32,
+        clicked: Option<&ItemStack>,
+    ) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_u8(window_id);
+        bytes.write_i16(slot);
+        bytes.write_i8(button);
+        bytes.write_i16(action);
+        bytes.write_varint(mode);
+        bytes.write_slot(clicked);
+        self.send(&bytes.create_packet(0x07))
+    }
+
+    /// Accepting server's answer for rejected click
+    pub fn confirm_transaction(&mut self, window_id: u8, action: i16) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_i8(window_id as i8);
+        bytes.write_i16(action);
+        bytes.write_bool(false);
+        self.send(&bytes.create_packet(0x05))
+    }
+
+    /// Setting slot of player's inventory in creative mode(-1 - throwing item)
+    pub fn creative_action(&mut self, slot: i16, item: Option<&ItemStack>) -> SResult<()> {
+        let mut bytes = Vec::new();
+        bytes.write_i16(slot);
+        bytes.write_slot(item);
+        self.send(&bytes.create_packet(0x1B))
+    }
+
+    /// Closing the connection
+    pub fn disconnect(self) {
+        self.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
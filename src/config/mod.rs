@@ -4,3 +4,51 @@ pub const PROTOCOL_VERSION: u32 = 340;
 // Server's address parts(host and port)
 pub const ADDRESS_PORT: u16 = 25565;
 pub const ADDRESS: &str = "0.0.0.0";
+
+// Count of network's worker threads(0 - count of CPU's cores)
+pub const NETWORK_WORKERS: usize = 0;
+
+// Max count of players(shown in server's list and sent to client)
+pub const MAX_PLAYERS: u8 = 20;
+// Default game's mode(0 - survival, 1 - creative, 2 - adventure, 3 - spectator)
+pub const DEFAULT_GAMEMODE: u8 = 1;
+// World's difficulty(0 - peaceful, 1 - easy, 2 - normal, 3 - hard)
+pub const DIFFICULTY: u8 = 1;
+// World's type(default, flat, largeBiomes, amplified)
+pub const LEVEL_TYPE: &str = "flat";
+// Hide coordinates and etc. on client's debug screen
+pub const REDUCED_DEBUG_INFO: bool = false;
+// World's spawn point
+pub const SPAWN_POSITION: (i32, i32, i32) = (0, 64, 0);
+// Max distance in chunks around player for sending chunks(client can ask less)
+pub const VIEW_DISTANCE: u8 = 10;
+// Max distance in blocks which player can move by one packet(longer moves are rejected)
+pub const MAX_MOVE_DISTANCE: f64 = 10.0;
+// Depth in blocks which player's box can go into solid blocks(deeper moves are rejected)
+pub const MOVE_TOLERANCE: f64 = 0.0625;
+// Max count of chunks sent to single player per tick
+pub const CHUNKS_PER_TICK: usize = 8;
+// Chat's format({player} - player's name, {message} - player's message, & - formatting)
+pub const CHAT_FORMAT: &str = "<{player}> {message}";
+// Max length of player's chat message(longer messages kick the player)
+pub const MAX_CHAT_LENGTH: usize = 256;
+// Players can use formatting(&) in chat messages
+pub const CHAT_COLORS: bool = false;
+// Names of players who can run operator's commands
+pub const OPERATORS: &[&str] = &[];
+// Player list's header and footer({online}, {max_players}, {tps} and {mspt} - server's state, & - formatting, empty - none)
+pub const TAB_HEADER: &str = "&6ULE server";
+pub const TAB_FOOTER: &str = "&7Online: {online}/{max_players} | TPS: {tps}";
+// World's directory(level.dat, region's files and etc.)
+pub const WORLD_DIR: &str = "world";
+// How often(in ticks) players' data is saved(also saved on quit)
+pub const PLAYERDATA_SAVE_PERIOD: u64 = 6000;
+// World's name saved in new level.dat
+pub const LEVEL_NAME: &str = "world";
+// Seed of new world(number or any text, empty - random)
+pub const LEVEL_SEED: &str = "";
+// Spawn platform's block and radius in void world(empty block - no platform)
+pub const VOID_PLATFORM: &str = "minecraft:stone";
+pub const VOID_PLATFORM_RADIUS: u8 = 2;
+// Count of threads generating chunks(0 - count of CPU's cores)
+pub const GENERATOR_WORKERS: usize = 0;
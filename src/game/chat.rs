@@ -0,0 +1,96 @@
+use crate::config::{CHAT_FORMAT, MAX_CHAT_LENGTH};
+use crate::game::commands::{run_command, CommandSender};
+use crate::game::Game;
+use crate::network::proto::packets::play::create_chat_message;
+use crate::utils::chat::ChatMessage;
+
+/// Player's chat message before broadcasting(listeners can change or cancel it)
+#[derive(Debug, Clone)]
+pub struct ChatEvent {
+    // Sender's connection
+    pub conn: usize,
+    pub message: String,
+    // Format with {player} and {message}
+    pub format: String,
+    // Cancelled message isn't sent to anyone
+    pub cancelled: bool,
+}
+
+// Chat's listener(plugins, moderation and etc.)
+pub type ChatListener = Box<dyn FnMut(&mut Game, &mut ChatEvent) + Send>;
+
+// Characters which client can't send(formatting's sign and control characters)
+fn is_illegal(c: char) -> bool {
+    c == '§' || c < ' ' || c == '\u{7F}'
+}
+
+/// Building chat's component by format(formatting in message is replaced only with colors)
+pub fn format_chat(format: &str, player: &str, message: &str, colors: bool) -> ChatMessage {
+    let format = format.replace("{player}", player);
+    let (prefix, suffix) = format.split_once("{message}").unwrap_or((&format, ""));
+    let mut component = ChatMessage::text(prefix.to_string());
+    component.extra.push(match colors {
+        true => ChatMessage::text(message.to_string()),
+        false => ChatMessage::raw(message.to_string()),
+    });
+    if !suffix.is_empty() {
+        component.extra.push(ChatMessage::text(suffix.to_string()));
+    }
+    component
+}
+
+// Calling all chat's listeners(listeners added by listeners are called from next message)
+fn call_listeners(game: &mut Game, event: &mut ChatEvent) {
+    let mut listeners = std::mem::take(&mut game.chat_listeners);
+    for listener in listeners.iter_mut() {
+        listener(game, event);
+    }
+    listeners.append(&mut game.chat_listeners);
+    game.chat_listeners = listeners;
+}
+
+/// Handing player's chat message: running a command or broadcasting the message
+pub fn handle_chat(game: &mut Game, conn: usize, message: &str) {
+    let (name, colors) = match game.players.get(&conn) {
+        Some(v) => (v.profile.name.clone(), v.chat_colors),
+        None => return,
+    };
+    if message.chars().count() > MAX_CHAT_LENGTH {
+        return game.kick(conn, "Chat message is too long");
+    }
+    if message.chars().any(is_illegal) {
+        return game.kick(conn, "Illegal characters in chat");
+    }
+    let message = message.trim();
+    if message.is_empty() {
+        return;
+    }
+    if let Some(command) = message.strip_prefix('/') {
+        info!("{} issued server command: /{}", name, command);
+        return run_command(game, CommandSender::Player(conn), command);
+    }
+    let mut event = ChatEvent {
+        conn,
+        message: message.to_string(),
+        format: CHAT_FORMAT.to_string(),
+        cancelled: false,
+    };
+    call_listeners(game, &mut event);
+    if event.cancelled {
+        return;
+    }
+    info!(
+        "{}",
+        event
+            .format
+            .replace("{player}", &name)
+            .replace("{message}", &event.message)
+    );
+    let packet = create_chat_message(
+        &format_chat(&event.format, &name, &event.message, colors),
+        0,
+    );
+    for conn in game.players.keys() {
+        game.outbound.send(*conn, packet.clone());
+    }
+}
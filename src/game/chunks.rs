@@ -0,0 +1,108 @@
+use crate::config::CHUNKS_PER_TICK;
+use crate::game::Game;
+use crate::network::proto::packets::play::{create_chunk_data, create_unload_chunk};
+use ahash::AHashSet;
+
+// How often(in ticks) chunks out of players' view are unloaded
+pub const CHUNK_UNLOAD_PERIOD: u64 = 100;
+
+// Chunks around center in square spiral(center first, then ring by ring)
+pub fn spiral((x, z): (i32, i32), radius: i32) -> Vec<(i32, i32)> {
+    let side = (radius * 2 + 1) as usize;
+    let mut chunks = Vec::with_capacity(side * side);
+    let (mut dx, mut dz) = (0i32, 0i32);
+    chunks.push((x, z));
+    // Walking right, down, left, up with steps 1, 1, 2, 2, 3, 3 and etc.
+    let directions = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let mut step = 1;
+    let mut direction = 0;
+    while chunks.len() < side * side {
+        for _ in 0..2 {
+            let (sx, sz) = directions[direction % 4];
+            for _ in 0..step {
+                dx += sx;
+                dz += sz;
+                if dx.abs() <= radius && dz.abs() <= radius {
+                    chunks.push((x + dx, z + dz));
+                }
+            }
+            direction += 1;
+        }
+        step += 1;
+    }
+    chunks
+}
+
+// Chunk is in view distance from center(square like vanilla)
+fn in_view((x, z): (i32, i32), (cx, cz): (i32, i32), distance: u8) -> bool {
+    (x - cx).abs() <= distance as i32 && (z - cz).abs() <= distance as i32
+}
+
+// Sending chunks around players and unloading far chunks(called every tick)
+pub fn update_chunks(game: &mut Game) {
+    let Game {
+        players,
+        world,
+        entities,
+        outbound,
+        ..
+    } = game;
+    world.receive_generated();
+    for player in players.values_mut() {
+        // Player moved to another chunk or changed view distance
+        let center = match entities.get(player.entity_id) {
+            Some(v) => v.chunk_position(),
+            None => continue,
+        };
+        if player.chunk_center != Some((center, player.view_distance)) {
+            player.chunk_center = Some((center, player.view_distance));
+            let distance = player.view_distance;
+            player.loaded_chunks.retain(|chunk| {
+                let keep = in_view(*chunk, center, distance);
+                if !keep {
+                    outbound.send(player.conn, create_unload_chunk(chunk.0, chunk.1));
+                }
+                keep
+            });
+            let loaded = &player.loaded_chunks;
+            player.chunk_queue = spiral(center, distance as i32)
+                .into_iter()
+                .filter(|v| !loaded.contains(v))
+                .collect();
+        }
+        // Requesting queued chunks, so workers generate them in background
+        for chunk in player.chunk_queue.iter().take(CHUNKS_PER_TICK * 4) {
+            world.request_chunk(chunk.0, chunk.1);
+        }
+        // Sending nearest ready chunks in order, but not too many per tick
+        for _ in 0..CHUNKS_PER_TICK {
+            let (x, z) = match player.chunk_queue.front() {
+                Some(v) if world.chunks.contains_key(v) => *v,
+                _ => break,
+            };
+            player.chunk_queue.pop_front();
+            let column = world.chunk(x, z);
+            outbound.send(player.conn, create_chunk_data(column, true, u16::MAX));
+            player.loaded_chunks.insert((x, z));
+        }
+    }
+    // Chunks which left view of all players aren't generated
+    let queued: AHashSet<(i32, i32)> = players
+        .values()
+        .flat_map(|v| v.chunk_queue.iter().copied())
+        .collect();
+    world.cancel_requests(|v| queued.contains(v));
+}
+
+// Unloading chunks which aren't sent or queued for any player
+pub fn unload_chunks(game: &mut Game) {
+    let Game { players, world, .. } = game;
+    let used: AHashSet<(i32, i32)> = players
+        .values()
+        .flat_map(|v| v.loaded_chunks.iter().chain(v.chunk_queue.iter()).copied())
+        .collect();
+    let count = world.unload_chunks(|v| used.contains(v));
+    if count > 0 {
+        debug!("Unloaded {} chunks", count);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::config::OPERATORS;
+use crate::game::tick::TICK_STATS;
+use crate::game::window::{open_window, Contents};
+use crate::game::Game;
+use crate::network::proto::packets::play::create_chat_message;
+use crate::utils::chat::ChatMessage;
+use std::sync::Mutex;
+
+lazy_static! {
+    // Commands entered in console, waiting for game's thread
+    static ref CONSOLE_COMMANDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+/// Who runs a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandSender {
+    Console,
+    // Player by connection's id
+    Player(usize),
+}
+
+// Command's handler(arguments without command's name)
+type Command = fn(&mut Game, CommandSender, &[&str]);
+
+/// Who can run a command(console can run all commands)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    // All players
+    Everyone,
+    // Operators from config
+    Operator,
+    // Only console(commands which can stall the server or affect everyone)
+    Console,
+}
+
+// All commands with usage, description and permission
+const COMMANDS: &[(&str, &str, Permission, Command)] = &[
+    (
+        "help",
+        "help - list of commands",
+        Permission::Everyone,
+        help,
+    ),
+    (
+        "tps",
+        "tps - server's TPS and MSPT",
+        Permission::Everyone,
+        tps,
+    ),
+    (
+        "ping",
+        "ping [player] - player's latency",
+        Permission::Everyone,
+        ping,
+    ),
+    (
+        "save-all",
+        "save-all - saving the world",
+        Permission::Console,
+        save_all,
+    ),
+    (
+        "gamemode",
+        "gamemode <mode> [player] - changing player's game mode",
+        Permission::Operator,
+        gamemode,
+    ),
+    (
+        "enderchest",
+        "enderchest - opening own ender chest",
+        Permission::Everyone,
+        enderchest,
+    ),
+];
+
+/// Queueing a command from console(runs on game's thread)
+pub fn console_command(line: &str) {
+    CONSOLE_COMMANDS.lock().unwrap().push(String::from(line));
+}
+
+// Running all commands entered in console
+pub fn run_console_commands(game: &mut Game) {
+    let commands = std::mem::take(&mut *CONSOLE_COMMANDS.lock().unwrap());
+    for command in commands {
+        run_command(game, CommandSender::Console, &command);
+    }
+}
+
+/// Running a command's line(without leading slash)
+pub fn run_command(game: &mut Game, sender: CommandSender, line: &str) {
+    let mut args = line.split_whitespace();
+    let name = match args.next() {
+        Some(v) => v.to_lowercase(),
+        None => return,
+    };
+    let args: Vec<&str> = args.collect();
+    match COMMANDS.iter().find(|v| v.0 == name) {
+        Some((_, _, permission, command)) if can_run(game, sender, *permission) => {
+            command(game, sender, &args)
+        }
+        Some(_) => reply(
+            game,
+            sender,
+            "You don't have permission to use this command",
+        ),
+        None => reply(game, sender, "Unknown command. Type \"help\" for help."),
+    }
+}
+
+// Checking sender's permission for command
+fn can_run(game: &Game, sender: CommandSender, permission: Permission) -> bool {
+    match (sender, permission) {
+        (CommandSender::Console, _) => true,
+        (CommandSender::Player(_), Permission::Everyone) => true,
+        (CommandSender::Player(conn), Permission::Operator) => is_operator(game, conn),
+        (CommandSender::Player(_), Permission::Console) => false,
+    }
+}
+
+/// Checking if player is operator(by name, ignoring case)
+pub fn is_operator(game: &Game, conn: usize) -> bool {
+    match game.players.get(&conn) {
+        Some(player) => OPERATORS
+            .iter()
+            .any(|v| v.eq_ignore_ascii_case(&player.profile.name)),
+        None => false,
+    }
+}
+
+/// Sending command's answer to its sender
+pub fn reply(game: &Game, sender: CommandSender, text: &str) {
+    match sender {
+        CommandSender::Console => info!("{}", text),
+        CommandSender::Player(conn) => game
+            .outbound
+            .send(conn, create_chat_message(&ChatMessage::str(text), 1)),
+    }
+}
+
+// Showing list of commands which sender can run
+fn help(game: &mut Game, sender: CommandSender, _: &[&str]) {
+    for (_, usage, permission, _) in COMMANDS {
+        if can_run(game, sender, *permission) {
+            reply(game, sender, usage);
+        }
+    }
+}
+
+// Showing server's TPS and MSPT
+fn tps(game: &mut Game, sender: CommandSender, _: &[&str]) {
+    let stats = TICK_STATS.lock().unwrap().clone();
+    reply(
+        game,
+        sender,
+        &format!(
+            "TPS from last 1m, 5m, 15m: {:.2}, {:.2}, {:.2}",
+            stats.tps[0], stats.tps[1], stats.tps[2]
+        ),
+    );
+    reply(
+        game,
+        sender,
+        &format!(
+            "MSPT from last 1m, 5m, 15m: {:.2}, {:.2}, {:.2}",
+            stats.mspt[0], stats.mspt[1], stats.mspt[2]
+        ),
+    );
+}
+
+// Showing player's latency(sender's own if player isn't specified)
+fn ping(game: &mut Game, sender: CommandSender, args: &[&str]) {
+    let player = match (args.first(), sender) {
+        (Some(name), _) => game.find_player(name),
+        (None, CommandSender::Player(conn)) => game.players.get(&conn),
+        (None, CommandSender::Console) => return reply(game, sender, "Usage: ping <player>"),
+    };
+    let text = match player {
+        Some(v) => match v.ping {
+            Some(ping) => format!("{}'s ping: {}ms", v.profile.name, ping),
+            None => format!("{}'s ping isn't measured yet", v.profile.name),
+        },
+        None => format!("Player {} isn't online", args.first().unwrap_or(&"")),
+    };
+    reply(game, sender, &text);
+}
+
+// Saving the world
+fn save_all(game: &mut Game, sender: CommandSender, _: &[&str]) {
+    reply(game, sender, "Saving the world...");
+    game.save();
+    reply(game, sender, "Saved the world");
+}
+
+// Game's mode by its number or name
+fn parse_gamemode(text: &str) -> Option<u8> {
+    match text.to_lowercase().as_str() {
+        "0" | "s" | "survival" => Some(0),
+        "1" | "c" | "creative" => Some(1),
+        "2" | "a" | "adventure" => Some(2),
+        "3" | "sp" | "spectator" => Some(3),
+        _ => None,
+    }
+}
+
+// Changing player's game mode(sender's own if player isn't specified)
+fn gamemode(game: &mut Game, sender: CommandSender, args: &[&str]) {
+    let mode = match args.first().and_then(|v| parse_gamemode(v)) {
+        Some(v) => v,
+        None => return reply(game, sender, "Usage: gamemode <mode> [player]"),
+    };
+    let conn = match (args.get(1), sender) {
+        (Some(name), _) => game.find_player(name).map(|v| v.conn),
+        (None, CommandSender::Player(conn)) => Some(conn),
+        (None, CommandSender::Console) => {
+            return reply(game, sender, "Usage: gamemode <mode> <player>")
+        }
+    };
+    match conn {
+        Some(conn) => {
+            game.set_gamemode(conn, mode);
+            let name = game.players[&conn].profile.name.clone();
+            reply(
+                game,
+                sender,
+                &format!("Set {}'s game mode to {}", name, mode),
+            );
+        }
+        None => reply(
+            game,
+            sender,
+            &format!("Player {} isn't online", args.get(1).unwrap_or(&"")),
+        ),
+    }
+}
+
+// Opening sender's ender chest
+fn enderchest(game: &mut Game, sender: CommandSender, _: &[&str]) {
+    match sender {
+        CommandSender::Player(conn) => open_window(
+            game,
+            conn,
+            "minecraft:chest",
+            "Ender Chest",
+            Contents::EnderChest,
+        ),
+        CommandSender::Console => reply(game, sender, "Only players have ender chests"),
+    }
+}
@@ -0,0 +1,313 @@
+use crate::network::proto::{PacketReader, PacketWriter};
+use crate::utils::nbt::Compound;
+use crate::world::item::ItemStack;
+use crate::world::BlockState;
+use crate::{SResult, SimpleError};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Index after the last value
+const METADATA_END: u8 = 0xFF;
+
+// Base entity's values
+pub const INDEX_FLAGS: u8 = 0;
+pub const INDEX_AIR: u8 = 1;
+pub const INDEX_CUSTOM_NAME: u8 = 2;
+pub const INDEX_CUSTOM_NAME_VISIBLE: u8 = 3;
+pub const INDEX_SILENT: u8 = 4;
+pub const INDEX_NO_GRAVITY: u8 = 5;
+// Dropped item's stack
+pub const INDEX_ITEM: u8 = 6;
+
+// Bits of base entity's flags
+pub const FLAG_ON_FIRE: i8 = 0x01;
+pub const FLAG_SNEAKING: i8 = 0x02;
+pub const FLAG_SPRINTING: i8 = 0x08;
+pub const FLAG_INVISIBLE: i8 = 0x20;
+pub const FLAG_GLOWING: i8 = 0x40;
+pub const FLAG_ELYTRA_FLYING: i8 = 0x80u8 as i8;
+
+/// Value of entity's [metadata](https://wiki.vg/index.php?title=Entity_metadata&oldid=14204)
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaValue {
+    Byte(i8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    // Chat's component as JSON
+    Chat(String),
+    Slot(Option<ItemStack>),
+    Boolean(bool),
+    // Rotation by x, y and z axes in degrees
+    Rotation(f32, f32, f32),
+    Position((i32, i32, i32)),
+    OptPosition(Option<(i32, i32, i32)>),
+    // Down, up, north, south, west or east
+    Direction(i32),
+    OptUuid(Option<u128>),
+    OptBlockId(Option<BlockState>),
+    Nbt(Compound),
+}
+
+impl MetaValue {
+    /// Value's type in protocol
+    pub fn type_id(&self) -> i32 {
+        match self {
+            MetaValue::Byte(_) => 0,
+            MetaValue::VarInt(_) => 1,
+            MetaValue::Float(_) => 2,
+            MetaValue::String(_) => 3,
+            MetaValue::Chat(_) => 4,
+            MetaValue::Slot(_) => 5,
+            MetaValue::Boolean(_) => 6,
+            MetaValue::Rotation(..) => 7,
+            MetaValue::Position(_) => 8,
+            MetaValue::OptPosition(_) => 9,
+            MetaValue::Direction(_) => 10,
+            MetaValue::OptUuid(_) => 11,
+            MetaValue::OptBlockId(_) => 12,
+            MetaValue::Nbt(_) => 13,
+        }
+    }
+
+    // Writing value without type
+    fn write(&self, bytes: &mut Vec<u8>) {
+        match self {
+            MetaValue::Byte(v) => bytes.write_i8(*v),
+            MetaValue::VarInt(v) | MetaValue::Direction(v) => bytes.write_varint(*v),
+            MetaValue::Float(v) => bytes.write_f32(*v),
+            MetaValue::String(v) | MetaValue::Chat(v) => bytes.write_string(v.clone()),
+            MetaValue::Slot(v) => bytes.write_slot(v.as_ref()),
+            MetaValue::Boolean(v) => bytes.write_bool(*v),
+            MetaValue::Rotation(x, y, z) => {
+                bytes.write_f32(*x);
+                bytes.write_f32(*y);
+                bytes.write_f32(*z);
+            }
+            MetaValue::Position((x, y, z)) => bytes.write_position(*x, *y, *z),
+            MetaValue::OptPosition(v) => {
+                bytes.write_bool(v.is_some());
+                if let Some((x, y, z)) = v {
+                    bytes.write_position(*x, *y, *z);
+                }
+            }
+            MetaValue::OptUuid(v) => {
+                bytes.write_bool(v.is_some());
+                if let Some(uuid) = v {
+                    bytes.write_u128(*uuid);
+                }
+            }
+            // 0 - absent(air)
+            MetaValue::OptBlockId(v) => bytes.write_varint(v.unwrap_or(0) as i32),
+            MetaValue::Nbt(v) => bytes.write_nbt(Some(v)),
+        }
+    }
+
+    // Reading value of type
+    fn read(type_id: i32, p: &mut Vec<u8>) -> SResult<Self> {
+        Ok(match type_id {
+            0 => MetaValue::Byte(p.get_i8()?),
+            1 => MetaValue::VarInt(p.get_varint()?),
+            2 => MetaValue::Float(p.get_f32()?),
+            3 => MetaValue::String(p.get_string()?),
+            4 => MetaValue::Chat(p.get_string()?),
+            5 => MetaValue::Slot(p.get_slot()?),
+            6 => MetaValue::Boolean(p.get_bool()?),
+            7 => MetaValue::Rotation(p.get_f32()?, p.get_f32()?, p.get_f32()?),
+            8 => MetaValue::Position(p.get_position()?),
+            9 => MetaValue::OptPosition(match p.get_bool()? {
+                true => Some(p.get_position()?),
+                false => None,
+            }),
+            10 => MetaValue::Direction(p.get_varint()?),
+            11 => MetaValue::OptUuid(match p.get_bool()? {
+                true => Some(p.get_u128()?),
+                false => None,
+            }),
+            12 => MetaValue::OptBlockId(match p.get_varint()? {
+                0 => None,
+                v => Some(v as BlockState),
+            }),
+            13 => MetaValue::Nbt(p.get_nbt()?.unwrap_or_default()),
+            _ => {
+                return Err(SimpleError(
+                    format!("Unknown metadata's type {}", type_id),
+                    None,
+                ))
+            }
+        })
+    }
+}
+
+/// Entity's metadata by indexes with changed values since last sending
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metadata {
+    values: BTreeMap<u8, MetaValue>,
+    dirty: BTreeSet<u8>,
+}
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, index: u8) -> Option<&MetaValue> {
+        self.values.get(&index)
+    }
+
+    /// Setting value(it's sent to players only if it's changed)
+    pub fn set(&mut self, index: u8, value: MetaValue) {
+        if self.values.get(&index) != Some(&value) {
+            self.values.insert(index, value);
+            self.dirty.insert(index);
+        }
+    }
+
+    /// Removing value(client keeps its previous value)
+    pub fn remove(&mut self, index: u8) -> Option<MetaValue> {
+        self.dirty.remove(&index);
+        self.values.remove(&index)
+    }
+
+    // Values sorted by indexes
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &MetaValue)> {
+        self.values.iter().map(|(k, v)| (*k, v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Values were changed since last encode_dirty
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    // Writing values with indexes and types and the end's mark
+    fn encode_values<'a>(values: impl Iterator<Item = (u8, &'a MetaValue)>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (index, value) in values {
+            bytes.write_u8(index);
+            bytes.write_varint(value.type_id());
+            value.write(&mut bytes);
+        }
+        bytes.write_u8(METADATA_END);
+        bytes
+    }
+
+    /// All values in protocol's format(for spawning)
+    pub fn encode(&self) -> Vec<u8> {
+        Self::encode_values(self.iter())
+    }
+
+    /// Only changed values in protocol's format(None if nothing changed), values become not changed
+    pub fn encode_dirty(&mut self) -> Option<Vec<u8>> {
+        if self.dirty.is_empty() {
+            return None;
+        }
+        let dirty = std::mem::take(&mut self.dirty);
+        let values = self.values.iter().filter(|(k, _)| dirty.contains(k));
+        Some(Self::encode_values(values.map(|(k, v)| (*k, v))))
+    }
+
+    /// Reading values in protocol's format(read values aren't changed)
+    pub fn read(p: &mut Vec<u8>) -> SResult<Self> {
+        let mut metadata = Self::new();
+        loop {
+            let index = p.get_u8()?;
+            if index == METADATA_END {
+                return Ok(metadata);
+            }
+            let type_id = p.get_varint()?;
+            metadata.values.insert(index, MetaValue::read(type_id, p)?);
+        }
+    }
+
+    // Bit of base entity's flags
+    fn flag(&self, flag: i8) -> bool {
+        match self.get(INDEX_FLAGS) {
+            Some(MetaValue::Byte(v)) => v & flag != 0,
+            _ => false,
+        }
+    }
+
+    fn set_flag(&mut self, flag: i8, value: bool) {
+        let flags = match self.get(INDEX_FLAGS) {
+            Some(MetaValue::Byte(v)) => *v,
+            _ => 0,
+        };
+        let flags = if value { flags | flag } else { flags & !flag };
+        self.set(INDEX_FLAGS, MetaValue::Byte(flags));
+    }
+
+    pub fn is_on_fire(&self) -> bool {
+        self.flag(FLAG_ON_FIRE)
+    }
+
+    pub fn set_on_fire(&mut self, value: bool) {
+        self.set_flag(FLAG_ON_FIRE, value)
+    }
+
+    pub fn is_sneaking(&self) -> bool {
+        self.flag(FLAG_SNEAKING)
+    }
+
+    pub fn set_sneaking(&mut self, value: bool) {
+        self.set_flag(FLAG_SNEAKING, value)
+    }
+
+    pub fn is_sprinting(&self) -> bool {
+        self.flag(FLAG_SPRINTING)
+    }
+
+    pub fn set_sprinting(&mut self, value: bool) {
+        self.set_flag(FLAG_SPRINTING, value)
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.flag(FLAG_INVISIBLE)
+    }
+
+    pub fn set_invisible(&mut self, value: bool) {
+        self.set_flag(FLAG_INVISIBLE, value)
+    }
+
+    pub fn is_glowing(&self) -> bool {
+        self.flag(FLAG_GLOWING)
+    }
+
+    pub fn set_glowing(&mut self, value: bool) {
+        self.set_flag(FLAG_GLOWING, value)
+    }
+
+    /// Name over entity(None if it isn't set)
+    pub fn custom_name(&self) -> Option<&str> {
+        match self.get(INDEX_CUSTOM_NAME) {
+            Some(MetaValue::String(v)) if !v.is_empty() => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Setting name over entity(it's always visible, None - no name)
+    pub fn set_custom_name(&mut self, name: Option<&str>) {
+        self.set(
+            INDEX_CUSTOM_NAME,
+            MetaValue::String(name.unwrap_or_default().to_string()),
+        );
+        self.set(
+            INDEX_CUSTOM_NAME_VISIBLE,
+            MetaValue::Boolean(name.is_some()),
+        );
+    }
+
+    /// Dropped item's stack
+    pub fn item(&self) -> Option<&ItemStack> {
+        match self.get(INDEX_ITEM) {
+            Some(MetaValue::Slot(v)) => v.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn set_item(&mut self, item: Option<ItemStack>) {
+        self.set(INDEX_ITEM, MetaValue::Slot(item));
+    }
+}
@@ -0,0 +1,449 @@
+pub mod metadata;
+
+use crate::game::entity::metadata::Metadata;
+use crate::utils::uuid::random_uuid;
+use ahash::{AHashMap, AHashSet};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+// Last used entity's id(ids are unique for all entities including players)
+static LAST_ENTITY_ID: AtomicI32 = AtomicI32::new(0);
+
+/// Getting a new unique entity's id
+pub fn next_entity_id() -> i32 {
+    LAST_ENTITY_ID.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// How entity is spawned for clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnKind {
+    // Spawn Player
+    Player,
+    // Spawn Object with object's type
+    Object(u8),
+    // Spawn Mob with mob's type
+    Mob(u8),
+    // Spawn Experience Orb
+    ExperienceOrb,
+}
+
+/// Entity's type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityType {
+    Player,
+    // Items and experience
+    Item,
+    ExperienceOrb,
+    // Projectiles
+    Arrow,
+    Snowball,
+    Egg,
+    EnderPearl,
+    Potion,
+    ExperienceBottle,
+    Fireball,
+    // Blocks and other objects
+    Tnt,
+    FallingBlock,
+    ArmorStand,
+    Boat,
+    Minecart,
+    // Hostile mobs
+    Creeper,
+    Skeleton,
+    Spider,
+    Zombie,
+    Slime,
+    Enderman,
+    CaveSpider,
+    Witch,
+    // Passive mobs
+    Bat,
+    Pig,
+    Sheep,
+    Cow,
+    Chicken,
+    Squid,
+    Wolf,
+    Horse,
+    Villager,
+}
+
+// Types for searching by name
+const ENTITY_TYPES: [EntityType; 32] = [
+    EntityType::Player,
+    EntityType::Item,
+    EntityType::ExperienceOrb,
+    EntityType::Arrow,
+    EntityType::Snowball,
+    EntityType::Egg,
+    EntityType::EnderPearl,
+    EntityType::Potion,
+    EntityType::ExperienceBottle,
+    EntityType::Fireball,
+    EntityType::Tnt,
+    EntityType::FallingBlock,
+    EntityType::ArmorStand,
+    EntityType::Boat,
+    EntityType::Minecart,
+    EntityType::Creeper,
+    EntityType::Skeleton,
+    EntityType::Spider,
+    EntityType::Zombie,
+    EntityType::Slime,
+    EntityType::Enderman,
+    EntityType::CaveSpider,
+    EntityType::Witch,
+    EntityType::Bat,
+    EntityType::Pig,
+    EntityType::Sheep,
+    EntityType::Cow,
+    EntityType::Chicken,
+    EntityType::Squid,
+    EntityType::Wolf,
+    EntityType::Horse,
+    EntityType::Villager,
+];
+
+impl EntityType {
+    // Name, spawning and size(width and height) like in 1.12.2
+    fn info(self) -> (&'static str, SpawnKind, f64, f64) {
+        use EntityType::*;
+        use SpawnKind::*;
+        match self {
+            EntityType::Player => ("minecraft:player", SpawnKind::Player, 0.6, 1.8),
+            Item => ("minecraft:item", Object(2), 0.25, 0.25),
+            EntityType::ExperienceOrb => ("minecraft:xp_orb", SpawnKind::ExperienceOrb, 0.5, 0.5),
+            Arrow => ("minecraft:arrow", Object(60), 0.5, 0.5),
+            Snowball => ("minecraft:snowball", Object(61), 0.25, 0.25),
+            Egg => ("minecraft:egg", Object(62), 0.25, 0.25),
+            EnderPearl => ("minecraft:ender_pearl", Object(65), 0.25, 0.25),
+            Potion => ("minecraft:potion", Object(73), 0.25, 0.25),
+            ExperienceBottle => ("minecraft:xp_bottle", Object(75), 0.25, 0.25),
+            Fireball => ("minecraft:fireball", Object(63), 1.0, 1.0),
+            Tnt => ("minecraft:tnt", Object(50), 0.98, 0.98),
+            FallingBlock => ("minecraft:falling_block", Object(70), 0.98, 0.98),
+            ArmorStand => ("minecraft:armor_stand", Object(78), 0.5, 1.975),
+            Boat => ("minecraft:boat", Object(1), 1.375, 0.5625),
+            Minecart => ("minecraft:minecart", Object(10), 0.98, 0.7),
+            Creeper => ("minecraft:creeper", Mob(50), 0.6, 1.7),
+            Skeleton => ("minecraft:skeleton", Mob(51), 0.6, 1.99),
+            Spider => ("minecraft:spider", Mob(52), 1.4, 0.9),
+            Zombie => ("minecraft:zombie", Mob(54), 0.6, 1.95),
+            Slime => ("minecraft:slime", Mob(55), 0.51, 0.51),
+            Enderman => ("minecraft:enderman", Mob(58), 0.6, 2.9),
+            CaveSpider => ("minecraft:cave_spider", Mob(59), 0.7, 0.5),
+            Witch => ("minecraft:witch", Mob(66), 0.6, 1.95),
+            Bat => ("minecraft:bat", Mob(65), 0.5, 0.9),
+            Pig => ("minecraft:pig", Mob(90), 0.9, 0.9),
+            Sheep => ("minecraft:sheep", Mob(91), 0.9, 1.3),
+            Cow => ("minecraft:cow", Mob(92), 0.9, 1.4),
+            Chicken => ("minecraft:chicken", Mob(93), 0.4, 0.7),
+            Squid => ("minecraft:squid", Mob(94), 0.8, 0.8),
+            Wolf => ("minecraft:wolf", Mob(95), 0.6, 0.85),
+            Horse => ("minecraft:horse", Mob(100), 1.3964844, 1.6),
+            Villager => ("minecraft:villager", Mob(120), 0.6, 1.95),
+        }
+    }
+
+    /// Type by name(with or without "minecraft:")
+    pub fn by_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        ENTITY_TYPES
+            .iter()
+            .copied()
+            .find(|v| v.name().strip_prefix("minecraft:") == Some(name))
+    }
+
+    /// Name with "minecraft:"
+    pub fn name(self) -> &'static str {
+        self.info().0
+    }
+
+    pub fn spawn_kind(self) -> SpawnKind {
+        self.info().1
+    }
+
+    /// Width and height of bounding box
+    pub fn size(self) -> (f64, f64) {
+        let (_, _, width, height) = self.info();
+        (width, height)
+    }
+
+    /// Entity has health(players and mobs)
+    pub fn is_living(self) -> bool {
+        matches!(self.spawn_kind(), SpawnKind::Player | SpawnKind::Mob(_))
+            || self == EntityType::ArmorStand
+    }
+
+    /// Entity is thrown or shot
+    pub fn is_projectile(self) -> bool {
+        use EntityType::*;
+        matches!(
+            self,
+            Arrow | Snowball | Egg | EnderPearl | Potion | ExperienceBottle | Fireball
+        )
+    }
+}
+
+/// Axis-aligned box of entity or block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: (f64, f64, f64),
+    pub max: (f64, f64, f64),
+}
+
+impl BoundingBox {
+    pub fn new(min: (f64, f64, f64), max: (f64, f64, f64)) -> Self {
+        Self { min, max }
+    }
+
+    /// Box of entity's size with position in the middle of bottom
+    pub fn of_size(x: f64, y: f64, z: f64, width: f64, height: f64) -> Self {
+        let half = width / 2.0;
+        Self::new((x - half, y, z - half), (x + half, y + height, z + half))
+    }
+
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.0 < other.max.0
+            && self.max.0 > other.min.0
+            && self.min.1 < other.max.1
+            && self.max.1 > other.min.1
+            && self.min.2 < other.max.2
+            && self.max.2 > other.min.2
+    }
+
+    pub fn contains(&self, x: f64, y: f64, z: f64) -> bool {
+        (self.min.0..self.max.0).contains(&x)
+            && (self.min.1..self.max.1).contains(&y)
+            && (self.min.2..self.max.2).contains(&z)
+    }
+
+    /// Box moved by offset
+    pub fn offset(&self, x: f64, y: f64, z: f64) -> Self {
+        Self::new(
+            (self.min.0 + x, self.min.1 + y, self.min.2 + z),
+            (self.max.0 + x, self.max.1 + y, self.max.2 + z),
+        )
+    }
+
+    /// Box grown by value in all directions
+    pub fn grow(&self, value: f64) -> Self {
+        Self::new(
+            (self.min.0 - value, self.min.1 - value, self.min.2 - value),
+            (self.max.0 + value, self.max.1 + value, self.max.2 + value),
+        )
+    }
+}
+
+/// Entity in world
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    pub id: i32,
+    pub uuid: u128,
+    pub kind: EntityType,
+    // Position(changed only by Entities::move_entity, so it's indexed by chunk)
+    x: f64,
+    y: f64,
+    z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub head_yaw: f32,
+    // Velocity in blocks per tick
+    pub velocity: (f64, f64, f64),
+    pub on_ground: bool,
+    // Object's data for spawning(like falling block's state) or experience orb's count
+    pub data: i32,
+    pub metadata: Metadata,
+    // Ticks since spawning
+    pub age: u32,
+    // Ticks before item can be picked up
+    pub pickup_delay: u32,
+}
+
+impl Entity {
+    /// Entity with a new id and random UUID
+    pub fn new(kind: EntityType, x: f64, y: f64, z: f64) -> Self {
+        Self::with_id(next_entity_id(), random_uuid(), kind, x, y, z)
+    }
+
+    /// Entity with known id and UUID(like players)
+    pub fn with_id(id: i32, uuid: u128, kind: EntityType, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            id,
+            uuid,
+            kind,
+            x,
+            y,
+            z,
+            yaw: 0.0,
+            pitch: 0.0,
+            head_yaw: 0.0,
+            velocity: (0.0, 0.0, 0.0),
+            on_ground: false,
+            data: match kind {
+                EntityType::Item | EntityType::ExperienceOrb => 1,
+                _ => 0,
+            },
+            metadata: Metadata::new(),
+            age: 0,
+            pickup_delay: 0,
+        }
+    }
+
+    pub fn position(&self) -> (f64, f64, f64) {
+        (self.x, self.y, self.z)
+    }
+
+    /// Chunk's coordinates where entity is
+    pub fn chunk_position(&self) -> (i32, i32) {
+        ((self.x.floor() as i32) >> 4, (self.z.floor() as i32) >> 4)
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        let (width, height) = self.kind.size();
+        BoundingBox::of_size(self.x, self.y, self.z, width, height)
+    }
+}
+
+// Entities are searched in chunks around box, because they can stick out of their chunk
+const SEARCH_MARGIN: f64 = 2.0;
+
+/// Entities of world by ids and chunks
+#[derive(Default)]
+pub struct Entities {
+    entities: AHashMap<i32, Entity>,
+    chunks: AHashMap<(i32, i32), AHashSet<i32>>,
+}
+
+impl Entities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adding entity(entity with the same id is replaced), returns its id
+    pub fn spawn(&mut self, entity: Entity) -> i32 {
+        let id = entity.id;
+        self.remove(id);
+        self.chunks
+            .entry(entity.chunk_position())
+            .or_default()
+            .insert(id);
+        self.entities.insert(id, entity);
+        id
+    }
+
+    pub fn remove(&mut self, id: i32) -> Option<Entity> {
+        let entity = self.entities.remove(&id)?;
+        self.unindex(id, entity.chunk_position());
+        Some(entity)
+    }
+
+    // Removing entity from chunk's index
+    fn unindex(&mut self, id: i32, chunk: (i32, i32)) {
+        if let Some(ids) = self.chunks.get_mut(&chunk) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                self.chunks.remove(&chunk);
+            }
+        }
+    }
+
+    pub fn get(&self, id: i32) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    /// Entity for changing(position is changed by move_entity)
+    pub fn get_mut(&mut self, id: i32) -> Option<&mut Entity> {
+        self.entities.get_mut(&id)
+    }
+
+    /// Moving entity to position, returns false if entity doesn't exist
+    pub fn move_entity(&mut self, id: i32, x: f64, y: f64, z: f64) -> bool {
+        let entity = match self.entities.get_mut(&id) {
+            Some(v) => v,
+            None => return false,
+        };
+        let previous = entity.chunk_position();
+        entity.x = x;
+        entity.y = y;
+        entity.z = z;
+        let current = entity.chunk_position();
+        if previous != current {
+            self.unindex(id, previous);
+            self.chunks.entry(current).or_default().insert(id);
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.values()
+    }
+
+    /// Mutable entities(position is changed by move_entity to keep chunks' index)
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Entity> {
+        self.entities.values_mut()
+    }
+
+    /// Entities in chunk
+    pub fn in_chunk(&self, x: i32, z: i32) -> impl Iterator<Item = &Entity> {
+        self.chunks
+            .get(&(x, z))
+            .into_iter()
+            .flatten()
+            .filter_map(|v| self.entities.get(v))
+    }
+
+    /// Entities which bounding boxes intersect with the box
+    pub fn in_box(&self, area: &BoundingBox) -> Vec<&Entity> {
+        let search = area.grow(SEARCH_MARGIN);
+        let (min_x, min_z) = (
+            (search.min.0.floor() as i32) >> 4,
+            (search.min.2.floor() as i32) >> 4,
+        );
+        let (max_x, max_z) = (
+            (search.max.0.floor() as i32) >> 4,
+            (search.max.2.floor() as i32) >> 4,
+        );
+        let mut found = Vec::new();
+        for x in min_x..=max_x {
+            for z in min_z..=max_z {
+                found.extend(
+                    self.in_chunk(x, z)
+                        .filter(|v| v.bounding_box().intersects(area)),
+                );
+            }
+        }
+        found
+    }
+
+    /// Entities which positions are in radius around point
+    pub fn in_radius(&self, x: f64, y: f64, z: f64, radius: f64) -> Vec<&Entity> {
+        let area = BoundingBox::new((x, y, z), (x, y, z)).grow(radius);
+        let (min_x, min_z) = (
+            (area.min.0.floor() as i32) >> 4,
+            (area.min.2.floor() as i32) >> 4,
+        );
+        let (max_x, max_z) = (
+            (area.max.0.floor() as i32) >> 4,
+            (area.max.2.floor() as i32) >> 4,
+        );
+        let mut found = Vec::new();
+        for chunk_x in min_x..=max_x {
+            for chunk_z in min_z..=max_z {
+                found.extend(self.in_chunk(chunk_x, chunk_z).filter(|v| {
+                    let (dx, dy, dz) = (v.x - x, v.y - y, v.z - z);
+                    dx * dx + dy * dy + dz * dz <= radius * radius
+                }));
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,261 @@
+use crate::config::VIEW_DISTANCE;
+use crate::game::chat::handle_chat;
+use crate::game::keep_alive::answer_keep_alive;
+use crate::game::movement::{check_move, update_fall, MoveCheck};
+use crate::game::window::{close_window, creative_action, handle_click, send_window, Click};
+use crate::game::Game;
+use crate::network::proto::packets::play::{
+    create_held_item_change, read_chat_message, read_click_window, read_client_settings,
+    read_client_status, read_close_window, read_confirm_transaction,
+    read_creative_inventory_action, read_entity_action, read_held_item_change, read_keep_alive,
+    read_player, read_player_look, read_player_position, read_player_position_and_look,
+    read_teleport_confirm,
+};
+use crate::network::proto::PacketReader;
+use crate::SResult;
+
+// Play stage's packet handler
+type Handler = fn(&mut Game, usize, &mut Vec<u8>) -> SResult<()>;
+
+// Handler for the serverbound packet's id
+fn handler(pid: i32) -> Option<Handler> {
+    Some(match pid {
+        0x00 => teleport_confirm,
+        0x02 => chat_message,
+        0x03 => client_status,
+        0x04 => client_settings,
+        0x05 => confirm_transaction,
+        0x07 => click_window,
+        0x08 => close_window_packet,
+        0x0B => keep_alive,
+        0x0C => player,
+        0x0D => player_position,
+        0x0E => player_position_look,
+        0x0F => player_look,
+        0x15 => entity_action,
+        0x1A => held_item_change,
+        0x1B => creative_inventory_action,
+        _ => return None,
+    })
+}
+
+// Handing a frame(length prefix + packet) from player's connection
+pub fn handle_packet(game: &mut Game, conn: usize, mut p: Vec<u8>) -> SResult<()> {
+    let (_, pid) = p.read_base()?;
+    match handler(pid) {
+        Some(handler) => handler(game, conn, &mut p),
+        // Another packets are ignored
+        None => Ok(()),
+    }
+}
+
+// Teleport Confirm
+fn teleport_confirm(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let teleport_id = read_teleport_confirm(p)?;
+    if let Some(player) = game.players.get_mut(&conn) {
+        if player.teleport_id == Some(teleport_id) {
+            player.teleport_id = None;
+        }
+    }
+    Ok(())
+}
+
+// Chat Message
+fn chat_message(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let message = read_chat_message(p)?;
+    handle_chat(game, conn, &message);
+    Ok(())
+}
+
+// Client Status
+fn client_status(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let action = read_client_status(p)?;
+    // Statistics aren't supported
+    if action == 0 {
+        game.respawn(conn);
+    }
+    Ok(())
+}
+
+// Client Settings
+fn client_settings(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (_, view_distance) = read_client_settings(p)?;
+    if let Some(player) = game.players.get_mut(&conn) {
+        // Client can't ask more than server's view distance
+        player.view_distance = (view_distance.max(2) as u8).min(VIEW_DISTANCE);
+    }
+    Ok(())
+}
+
+// Confirm Transaction(client accepted rejected click and its window can be clicked again)
+fn confirm_transaction(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (window_id, action, _) = read_confirm_transaction(p)?;
+    if let Some(player) = game.players.get_mut(&conn) {
+        // Stale confirmations of earlier clicks are ignored
+        if player.windows.rejected == Some((window_id as u8, action)) {
+            player.windows.rejected = None;
+        }
+    }
+    Ok(())
+}
+
+// Click Window
+fn click_window(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (window_id, slot, button, action, mode, clicked) = read_click_window(p)?;
+    let click = Click { slot, button, mode };
+    handle_click(game, conn, window_id, action, click, clicked);
+    Ok(())
+}
+
+// Close Window
+fn close_window_packet(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    read_close_window(p)?;
+    close_window(game, conn, false);
+    Ok(())
+}
+
+// Keep Alive
+fn keep_alive(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let id = read_keep_alive(p)?;
+    answer_keep_alive(game, conn, id);
+    Ok(())
+}
+
+// Updating player's position and rotation(movement before teleport's confirmation is ignored)
+fn update_movement(
+    game: &mut Game,
+    conn: usize,
+    position: Option<(f64, f64, f64)>,
+    look: Option<(f32, f32)>,
+    on_ground: bool,
+) {
+    let Game {
+        players,
+        world,
+        entities,
+        outbound,
+        ..
+    } = game;
+    let player = match players.get_mut(&conn) {
+        Some(v) if v.teleport_id.is_none() && !v.is_dead() => v,
+        _ => return,
+    };
+    let (from, rotation) = match entities.get(player.entity_id) {
+        Some(v) => (v.position(), (v.yaw, v.pitch)),
+        None => return,
+    };
+    if let Some((x, y, z)) = position {
+        let check = check_move(world, from, (x, y, z), player.gamemode == 3);
+        if check != MoveCheck::Valid {
+            // Client must return to the last valid position(repeated moves are logged once)
+            if !player.moved_wrongly {
+                warn!(
+                    "{} moved wrongly({:?}) from {:?} to {:?}",
+                    player.profile.name,
+                    check,
+                    from,
+                    (x, y, z)
+                );
+            }
+            player.moved_wrongly = true;
+            player.teleport(outbound, entities, from, rotation);
+            return;
+        }
+        player.moved_wrongly = false;
+    }
+    let to = position.unwrap_or(from);
+    let damage = update_fall(player, world, from, to, on_ground);
+    if player.damage(outbound, damage) {
+        info!("{} fell from a high place", player.profile.name);
+    }
+    // Chunks are streamed by entity's chunk in update_chunks
+    entities.move_entity(player.entity_id, to.0, to.1, to.2);
+    if let Some(entity) = entities.get_mut(player.entity_id) {
+        if let Some((yaw, pitch)) = look {
+            entity.yaw = yaw;
+            entity.pitch = pitch;
+            entity.head_yaw = yaw;
+        }
+        entity.on_ground = on_ground;
+    }
+}
+
+// Player
+fn player(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let on_ground = read_player(p)?;
+    update_movement(game, conn, None, None, on_ground);
+    Ok(())
+}
+
+// Player Position
+fn player_position(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (x, y, z, on_ground) = read_player_position(p)?;
+    update_movement(game, conn, Some((x, y, z)), None, on_ground);
+    Ok(())
+}
+
+// Player Position And Look
+fn player_position_look(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (x, y, z, yaw, pitch, on_ground) = read_player_position_and_look(p)?;
+    update_movement(game, conn, Some((x, y, z)), Some((yaw, pitch)), on_ground);
+    Ok(())
+}
+
+// Player Look
+fn player_look(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (yaw, pitch, on_ground) = read_player_look(p)?;
+    update_movement(game, conn, None, Some((yaw, pitch)), on_ground);
+    Ok(())
+}
+
+// Entity Action
+fn entity_action(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (_, action, _) = read_entity_action(p)?;
+    let entity_id = match game.players.get(&conn) {
+        Some(v) => v.entity_id,
+        None => return Ok(()),
+    };
+    // Entity's id from packet is ignored, player can change only own entity
+    if let Some(entity) = game.entities.get_mut(entity_id) {
+        match action {
+            0 => entity.metadata.set_sneaking(true),
+            1 => entity.metadata.set_sneaking(false),
+            3 => entity.metadata.set_sprinting(true),
+            4 => entity.metadata.set_sprinting(false),
+            // Leaving bed, jumping with horse and opening horse's inventory aren't supported
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// Held Item Change
+fn held_item_change(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let slot = read_held_item_change(p)?;
+    if let Some(player) = game.players.get_mut(&conn) {
+        match slot {
+            0..=8 => player.inventory.selected = slot as u8,
+            // Client returns to the selected slot
+            _ => game
+                .outbound
+                .send(conn, create_held_item_change(player.inventory.selected)),
+        }
+    }
+    Ok(())
+}
+
+// Creative Inventory Action
+fn creative_inventory_action(game: &mut Game, conn: usize, p: &mut Vec<u8>) -> SResult<()> {
+    let (slot, item) = match read_creative_inventory_action(p) {
+        Ok(v) => v,
+        // Unknown item's data, client gets the real inventory
+        Err(_) => {
+            if let Some(player) = game.players.get(&conn) {
+                send_window(&game.outbound, player);
+            }
+            return Ok(());
+        }
+    };
+    creative_action(game, conn, slot, item);
+    Ok(())
+}
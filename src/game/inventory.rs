@@ -0,0 +1,126 @@
+use crate::utils::nbt::Tag;
+use crate::world::item::ItemStack;
+
+// Sizes of player's inventory's parts
+pub const HOTBAR_SIZE: usize = 9;
+pub const MAIN_SIZE: usize = 36;
+pub const ARMOR_SIZE: usize = 4;
+pub const ENDER_CHEST_SIZE: usize = 27;
+
+// Slots of armor and second hand in saved inventory
+const NBT_ARMOR_SLOT: i8 = 100;
+const NBT_OFFHAND_SLOT: i8 = -106;
+
+/// Items as NBT's list with their slots(empty slots are skipped)
+pub fn items_to_nbt(items: &[Option<ItemStack>], first_slot: i8) -> Vec<Tag> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, item)| {
+            let mut compound = item.as_ref()?.to_nbt();
+            compound.insert(String::from("Slot"), Tag::Byte(first_slot + slot as i8));
+            Some(Tag::Compound(compound))
+        })
+        .collect()
+}
+
+/// Items from NBT's list into slots from first slot(unknown items and slots are skipped)
+pub fn items_from_nbt(list: &[Tag], first_slot: i8, items: &mut [Option<ItemStack>]) {
+    for compound in list.iter().filter_map(|v| v.as_compound()) {
+        let slot = match compound.get("Slot").and_then(|v| v.as_i8()) {
+            Some(v) => v as i32 - first_slot as i32,
+            None => continue,
+        };
+        if let Some(item) = usize::try_from(slot).ok().and_then(|v| items.get_mut(v)) {
+            *item = ItemStack::from_nbt(compound);
+        }
+    }
+}
+
+/// Player's own items
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerInventory {
+    // Hotbar(0-8) and main inventory(9-35)
+    pub main: Vec<Option<ItemStack>>,
+    // Boots, leggings, chestplate and helmet
+    pub armor: Vec<Option<ItemStack>>,
+    pub offhand: Option<ItemStack>,
+    // Selected hotbar's slot
+    pub selected: u8,
+}
+
+impl Default for PlayerInventory {
+    fn default() -> Self {
+        Self {
+            main: vec![None; MAIN_SIZE],
+            armor: vec![None; ARMOR_SIZE],
+            offhand: None,
+            selected: 0,
+        }
+    }
+}
+
+impl PlayerInventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Item in main hand
+    pub fn held_item(&self) -> Option<&ItemStack> {
+        self.main[self.selected as usize].as_ref()
+    }
+
+    /// Adding item to main inventory(similar stacks first, then empty slots from hotbar),
+    /// returns what doesn't fit
+    pub fn add_item(&mut self, mut item: ItemStack) -> Option<ItemStack> {
+        if item.count == 0 {
+            return None;
+        }
+        let max = item.max_stack();
+        for stack in self.main.iter_mut().flatten() {
+            if stack.is_similar(&item) && stack.count < max {
+                let count = item.count.min(max - stack.count);
+                stack.count += count;
+                item.count -= count;
+                if item.count == 0 {
+                    return None;
+                }
+            }
+        }
+        for slot in self.main.iter_mut().filter(|v| v.is_none()) {
+            let count = item.count.min(max);
+            let mut stack = item.clone();
+            stack.count = count;
+            *slot = Some(stack);
+            item.count -= count;
+            if item.count == 0 {
+                return None;
+            }
+        }
+        Some(item)
+    }
+
+    /// Inventory like in playerdata's Inventory list
+    pub fn to_nbt(&self) -> Vec<Tag> {
+        let mut list = items_to_nbt(&self.main, 0);
+        list.extend(items_to_nbt(&self.armor, NBT_ARMOR_SLOT));
+        list.extend(items_to_nbt(
+            std::slice::from_ref(&self.offhand),
+            NBT_OFFHAND_SLOT,
+        ));
+        list
+    }
+
+    /// Inventory from playerdata's Inventory list(selected slot is saved separately)
+    pub fn from_nbt(list: &[Tag]) -> Self {
+        let mut inventory = Self::new();
+        items_from_nbt(list, 0, &mut inventory.main);
+        items_from_nbt(list, NBT_ARMOR_SLOT, &mut inventory.armor);
+        items_from_nbt(
+            list,
+            NBT_OFFHAND_SLOT,
+            std::slice::from_mut(&mut inventory.offhand),
+        );
+        inventory
+    }
+}
@@ -0,0 +1,150 @@
+use crate::game::entity::{BoundingBox, Entity, EntityType};
+use crate::game::movement::collides;
+use crate::game::window::send_window;
+use crate::game::Game;
+use crate::network::proto::packets::play::create_collect_item;
+use crate::world::World;
+
+// Ticks before thrown item can be picked up(2 seconds)
+pub const PICKUP_DELAY: u32 = 40;
+// Ticks before item on ground disappears(5 minutes)
+pub const DESPAWN_AGE: u32 = 6000;
+// Velocity's changes per tick
+const GRAVITY: f64 = 0.04;
+const DRAG: f64 = 0.98;
+// Horizontal velocity's multiplier on ground
+const GROUND_FRICTION: f64 = 0.6;
+// Items below it fall out of world
+const VOID_Y: f64 = -64.0;
+
+/// Moving item by its velocity with gravity and blocks' collision, returns new position
+/// (entity's velocity and on_ground are updated, position must be changed by move_entity)
+pub fn move_item(world: &World, entity: &mut Entity) -> (f64, f64, f64) {
+    let mut position = entity.position();
+    // Item stuck in blocks stays there
+    if collides(world, &entity.bounding_box()) {
+        entity.velocity = (0.0, 0.0, 0.0);
+        entity.on_ground = true;
+        return position;
+    }
+    let (width, height) = entity.kind.size();
+    let fits = |(x, y, z)| !collides(world, &BoundingBox::of_size(x, y, z, width, height));
+    let (mut dx, mut dy, mut dz) = entity.velocity;
+    dy -= GRAVITY;
+    // Axes are moved separately, so item slides along blocks
+    entity.on_ground = false;
+    if fits((position.0, position.1 + dy, position.2)) {
+        position.1 += dy;
+    } else {
+        entity.on_ground = dy < 0.0;
+        dy = 0.0;
+    }
+    if fits((position.0 + dx, position.1, position.2)) {
+        position.0 += dx;
+    } else {
+        dx = 0.0;
+    }
+    if fits((position.0, position.1, position.2 + dz)) {
+        position.2 += dz;
+    } else {
+        dz = 0.0;
+    }
+    let friction = if entity.on_ground {
+        DRAG * GROUND_FRICTION
+    } else {
+        DRAG
+    };
+    entity.velocity = (dx * friction, dy * DRAG, dz * friction);
+    position
+}
+
+// Player's box where items are picked up
+fn pickup_box(x: f64, y: f64, z: f64) -> BoundingBox {
+    let (width, height) = EntityType::Player.size();
+    let area = BoundingBox::of_size(x, y, z, width, height);
+    BoundingBox::new(
+        (area.min.0 - 1.0, area.min.1 - 0.5, area.min.2 - 1.0),
+        (area.max.0 + 1.0, area.max.1 + 0.5, area.max.2 + 1.0),
+    )
+}
+
+/// Updating items on ground: moving, picking up by players and despawning(once per tick)
+pub fn update_items(game: &mut Game) {
+    let Game {
+        players,
+        world,
+        entities,
+        tracker,
+        outbound,
+        ..
+    } = game;
+    let items: Vec<i32> = entities
+        .iter()
+        .filter(|v| v.kind == EntityType::Item)
+        .map(|v| v.id)
+        .collect();
+    for id in items {
+        let entity = match entities.get_mut(id) {
+            Some(v) => v,
+            None => continue,
+        };
+        // Items in unloaded chunks are frozen
+        if !world.chunks.contains_key(&entity.chunk_position()) {
+            continue;
+        }
+        entity.age += 1;
+        if entity.age >= DESPAWN_AGE || entity.position().1 < VOID_Y {
+            entities.remove(id);
+            continue;
+        }
+        let (x, y, z) = move_item(world, entity);
+        let ready = entity.age >= entity.pickup_delay;
+        entities.move_entity(id, x, y, z);
+        let entity = match entities.get_mut(id) {
+            Some(v) if ready => v,
+            _ => continue,
+        };
+        let item = match entity.metadata.item() {
+            Some(v) => v.clone(),
+            None => {
+                entities.remove(id);
+                continue;
+            }
+        };
+        let area = entity.bounding_box();
+        let reaches = |entity_id: i32| {
+            let (x, y, z) = match entities.get(entity_id) {
+                Some(v) => v.position(),
+                None => return false,
+            };
+            pickup_box(x, y, z).intersects(&area)
+        };
+        let player = players
+            .values_mut()
+            .find(|v| !v.is_dead() && v.gamemode != 3 && reaches(v.entity_id));
+        let player = match player {
+            Some(v) => v,
+            None => continue,
+        };
+        let count = item.count;
+        let left = player.inventory.add_item(item);
+        let picked = count - left.as_ref().map(|v| v.count).unwrap_or(0);
+        if picked == 0 {
+            continue;
+        }
+        // Item flies to collector for everyone who sees it
+        let packet = create_collect_item(id, player.entity_id, picked as i32);
+        let viewers = tracker.viewers(id).into_iter().flatten();
+        for conn in viewers.filter(|v| **v != player.conn) {
+            outbound.send(*conn, packet.clone());
+        }
+        outbound.send(player.conn, packet);
+        send_window(outbound, player);
+        match (left, entities.get_mut(id)) {
+            (Some(left), Some(entity)) => entity.metadata.set_item(Some(left)),
+            _ => {
+                entities.remove(id);
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::game::player::Player;
+use crate::game::Game;
+use crate::network::proto::packets::play::{create_keep_alive, create_player_list_latency};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Period between keep alive packets
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+// Time to answer keep alive before kick
+pub const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(30);
+// How often(in ticks) keep alive and latency are checked
+pub const KEEP_ALIVE_CHECK_PERIOD: u64 = 20;
+
+/// Result of checking player's keep alive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepAliveCheck {
+    Wait,
+    // Sending a new keep alive with id
+    Send(i64),
+    // Client didn't answer in time
+    Kick,
+}
+
+/// Checking player's keep alive at time(id - id of a new keep alive). Kicked players are skipped
+pub fn check_keep_alive(player: &mut Player, now: Instant, id: i64) -> KeepAliveCheck {
+    if player.kicked {
+        return KeepAliveCheck::Wait;
+    }
+    match player.keep_alive {
+        Some((_, sent)) if now - sent > KEEP_ALIVE_TIMEOUT => KeepAliveCheck::Kick,
+        Some(_) => KeepAliveCheck::Wait,
+        None if now - player.last_keep_alive >= KEEP_ALIVE_INTERVAL => {
+            player.keep_alive = Some((id, now));
+            player.last_keep_alive = now;
+            KeepAliveCheck::Send(id)
+        }
+        None => KeepAliveCheck::Wait,
+    }
+}
+
+// Sending keep alive packets, kicking timed out players and updating latency in tab list
+pub fn update_keep_alive(game: &mut Game) {
+    let now = Instant::now();
+    // Keep alive's id is current time(as vanilla does)
+    let id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_millis() as i64)
+        .unwrap_or(0);
+    let mut timed_out = Vec::new();
+    let mut latencies = Vec::new();
+    for player in game.players.values_mut() {
+        match check_keep_alive(player, now, id) {
+            KeepAliveCheck::Send(id) => game.outbound.send(player.conn, create_keep_alive(id)),
+            KeepAliveCheck::Kick => timed_out.push(player.conn),
+            KeepAliveCheck::Wait => {}
+        }
+        if player.ping_changed {
+            player.ping_changed = false;
+            latencies.push((player.profile.uuid, player.ping.unwrap_or(0)));
+        }
+    }
+    for conn in timed_out {
+        game.kick(conn, "Timed out");
+    }
+    // Updating latency of changed players for everyone
+    if !latencies.is_empty() {
+        let packet = create_player_list_latency(&latencies);
+        for conn in game.players.keys() {
+            game.outbound.send(*conn, packet.clone());
+        }
+    }
+}
+
+/// Client answered keep alive at time: updating player's latency.
+/// Returns false if answer doesn't match sent keep alive
+pub fn update_latency(player: &mut Player, id: i64, now: Instant) -> bool {
+    let sent = match player.keep_alive {
+        Some((pending, sent)) if pending == id => sent,
+        _ => return false,
+    };
+    player.keep_alive = None;
+    let rtt = (now - sent).as_millis().min(i32::MAX as u128) as i32;
+    // Smoothing latency like vanilla(first answer sets it as is)
+    player.ping = Some(match player.ping {
+        Some(ping) => ((ping as i64 * 3 + rtt as i64) / 4) as i32,
+        None => rtt,
+    });
+    player.ping_changed = true;
+    true
+}
+
+// Client answered keep alive(answers to unknown keep alive are ignored)
+pub fn answer_keep_alive(game: &mut Game, conn: usize, id: i64) {
+    if let Some(player) = game.players.get_mut(&conn) {
+        update_latency(player, id, Instant::now());
+    }
+}
@@ -0,0 +1,431 @@
+pub mod chat;
+pub mod chunks;
+pub mod commands;
+pub mod entity;
+mod handlers;
+pub mod inventory;
+pub mod items;
+pub mod keep_alive;
+pub mod movement;
+pub mod player;
+pub mod playerdata;
+pub mod scheduler;
+pub mod tab_list;
+pub mod tick;
+pub mod tracker;
+pub mod window;
+
+use crate::config::{
+    GENERATOR_WORKERS, LEVEL_NAME, LEVEL_SEED, MAX_PLAYERS, PLAYERDATA_SAVE_PERIOD,
+    REDUCED_DEBUG_INFO,
+};
+use crate::game::chat::ChatListener;
+use crate::game::chunks::{unload_chunks, update_chunks, CHUNK_UNLOAD_PERIOD};
+use crate::game::commands::run_console_commands;
+use crate::game::entity::{next_entity_id, Entities, Entity, EntityType};
+use crate::game::handlers::handle_packet;
+use crate::game::items::update_items;
+use crate::game::keep_alive::{update_keep_alive, KEEP_ALIVE_CHECK_PERIOD};
+use crate::game::player::{Player, MAX_FOOD, MAX_HEALTH, START_SATURATION};
+use crate::game::playerdata::{load_player_data, player_from_nbt, save_player_data};
+use crate::game::scheduler::{run_due_tasks, Scheduler, TaskFn};
+use crate::game::tab_list::{
+    add_to_tab_list, remove_from_tab_list, update_header_footer, update_list_gamemode,
+    TAB_LIST_REFRESH_PERIOD,
+};
+use crate::game::tick::{MAX_CATCH_UP, TICK, TICK_STATS};
+use crate::game::tracker::{update_tracker, EntityTracker};
+use crate::game::window::{close_window, send_inventory};
+use crate::network::proto::packets::play::{
+    create_brand, create_change_game_state, create_disconnect, create_join_game,
+    create_player_abilities, create_respawn, create_server_difficulty, create_spawn_position,
+    create_time_update,
+};
+use crate::network::{GameProfile, NetEvent, Outbound, SHUTDOWN_SERVER};
+use crate::utils::chat::ChatMessage;
+use crate::world::generator::create_generator;
+use crate::world::level::parse_seed;
+use crate::world::{World, WorldInfo};
+use ahash::AHashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+// How often(in ticks) world's time is sent to players
+const TIME_UPDATE_PERIOD: u64 = 20;
+
+lazy_static! {
+    // Game's thread works(false after saving the world on shutdown)
+    pub static ref GAME_WORKS: Mutex<bool> = Mutex::new(true);
+}
+
+// Game's state, owned by game's thread
+pub struct Game {
+    // Commands to network's workers
+    pub outbound: Outbound,
+    // Players in game by connection's id
+    pub players: AHashMap<usize, Player>,
+    // Current tick's number
+    pub tick: u64,
+    pub scheduler: Scheduler,
+    // World's metadata from level.dat
+    pub info: WorldInfo,
+    // Overworld's loaded chunks
+    pub world: World,
+    // Overworld's entities(including players)
+    pub entities: Entities,
+    // Entities' states sent to players
+    pub tracker: EntityTracker,
+    // Listeners of players' chat messages
+    pub chat_listeners: Vec<ChatListener>,
+    // World's directory(level.dat, regions and players' data)
+    pub directory: PathBuf,
+}
+
+impl Game {
+    pub fn new(outbound: Outbound, directory: PathBuf) -> Self {
+        let (info, world) = open_world(&directory);
+        let mut game = Self {
+            outbound,
+            players: AHashMap::new(),
+            tick: 0,
+            scheduler: Scheduler::new(),
+            info,
+            world,
+            entities: Entities::new(),
+            tracker: EntityTracker::new(),
+            chat_listeners: Vec::new(),
+            directory,
+        };
+        game.schedule(0, KEEP_ALIVE_CHECK_PERIOD, Box::new(update_keep_alive));
+        game.schedule(0, TIME_UPDATE_PERIOD, Box::new(update_time));
+        game.schedule(
+            CHUNK_UNLOAD_PERIOD,
+            CHUNK_UNLOAD_PERIOD,
+            Box::new(unload_chunks),
+        );
+        game.schedule(
+            PLAYERDATA_SAVE_PERIOD,
+            PLAYERDATA_SAVE_PERIOD,
+            Box::new(save_players),
+        );
+        game.schedule(
+            TAB_LIST_REFRESH_PERIOD,
+            TAB_LIST_REFRESH_PERIOD,
+            Box::new(update_header_footer),
+        );
+        game
+    }
+
+    // Player joined: sending all what client needs to load into the world
+    pub fn join(&mut self, conn: usize, profile: GameProfile) {
+        // Previous session of the same player is closed and saved before loading player's data
+        let previous = self.players.values().find(|v| {
+            v.profile.uuid == profile.uuid || v.profile.name.eq_ignore_ascii_case(&profile.name)
+        });
+        if let Some(previous) = previous.map(|v| v.conn) {
+            self.kick(previous, "You logged in from another location");
+            self.leave(previous);
+        }
+        let entity_id = next_entity_id();
+        let info = &self.info;
+        let mut player = Player::new(conn, profile, entity_id, info.gamemode as u8);
+        // Returning player continues from saved position
+        let saved = match load_player_data(&self.playerdata_dir(), player.profile.uuid) {
+            Ok(Some(nbt)) => player_from_nbt(&mut player, &nbt),
+            Ok(None) => None,
+            Err(err) => {
+                error!("Failed to load {}'s data: {}", player.profile.name, err);
+                None
+            }
+        };
+        let outbound = &self.outbound;
+        // Hardcore's flag is sent with game's mode
+        let hardcore = if info.hardcore { 0x8 } else { 0 };
+        outbound.send(
+            conn,
+            create_join_game(
+                entity_id,
+                player.gamemode | hardcore,
+                0,
+                info.difficulty,
+                MAX_PLAYERS,
+                &info.generator_name,
+                REDUCED_DEBUG_INFO || info.game_rule_bool("reducedDebugInfo"),
+            ),
+        );
+        outbound.send(conn, create_brand("ULE"));
+        outbound.send(conn, create_server_difficulty(info.difficulty));
+        let (x, y, z) = info.spawn();
+        outbound.send(conn, create_spawn_position(x, y, z));
+        outbound.send(conn, create_time_update(info.time, info.client_day_time()));
+        outbound.send(conn, create_player_abilities(player.abilities(), 0.05, 0.1));
+        player.send_health(outbound);
+        player.send_experience(outbound);
+        send_inventory(outbound, &player);
+        let entity = saved.unwrap_or_else(|| {
+            let (x, y, z) = (x as f64 + 0.5, y as f64, z as f64 + 0.5);
+            Entity::with_id(entity_id, player.profile.uuid, EntityType::Player, x, y, z)
+        });
+        let (position, rotation) = (entity.position(), (entity.yaw, entity.pitch));
+        self.entities.spawn(entity);
+        player.teleport(outbound, &mut self.entities, position, rotation);
+        info!(
+            "{} joined the game(entity #{}, connection #{})",
+            player.profile.name, entity_id, conn
+        );
+        self.players.insert(conn, player);
+        // Players must be in player list before spawning them
+        add_to_tab_list(self, conn);
+    }
+
+    // Dead player respawns at world's spawn(dimension isn't changed, so client keeps chunks)
+    pub fn respawn(&mut self, conn: usize) {
+        let player = match self.players.get_mut(&conn) {
+            Some(v) if v.is_dead() => v,
+            _ => return,
+        };
+        let info = &self.info;
+        let outbound = &self.outbound;
+        player.health = MAX_HEALTH;
+        player.food = MAX_FOOD;
+        player.saturation = START_SATURATION;
+        outbound.send(
+            conn,
+            create_respawn(0, info.difficulty, player.gamemode, &info.generator_name),
+        );
+        outbound.send(conn, create_player_abilities(player.abilities(), 0.05, 0.1));
+        player.send_health(outbound);
+        let (x, y, z) = info.spawn();
+        let spawn = (x as f64 + 0.5, y as f64, z as f64 + 0.5);
+        player.teleport(outbound, &mut self.entities, spawn, (0.0, 0.0));
+    }
+
+    // Changing player's game mode(abilities and player list are updated)
+    pub fn set_gamemode(&mut self, conn: usize, gamemode: u8) {
+        let player = match self.players.get_mut(&conn) {
+            Some(v) => v,
+            None => return,
+        };
+        player.gamemode = gamemode;
+        let outbound = &self.outbound;
+        outbound.send(conn, create_change_game_state(3, gamemode as f32));
+        outbound.send(conn, create_player_abilities(player.abilities(), 0.05, 0.1));
+        let player = &self.players[&conn];
+        update_list_gamemode(self, player);
+    }
+
+    // Finding online player by name(ignoring case)
+    pub fn find_player(&self, name: &str) -> Option<&Player> {
+        self.players
+            .values()
+            .find(|v| v.profile.name.eq_ignore_ascii_case(name))
+    }
+
+    // Kicking a player with reason(player is removed when connection closes, so kick isn't repeated)
+    pub fn kick(&mut self, conn: usize, reason: &str) {
+        match self.players.get_mut(&conn) {
+            Some(player) if player.kicked => return,
+            Some(player) => {
+                player.kicked = true;
+                info!("{} was kicked: {}", player.profile.name, reason);
+            }
+            None => {}
+        }
+        self.outbound
+            .send(conn, create_disconnect(&ChatMessage::str(reason)));
+        self.outbound.disconnect(conn);
+    }
+
+    // Player left: saving and removing the player with its entity
+    pub fn leave(&mut self, conn: usize) {
+        // Items on cursor return to inventory before saving
+        close_window(self, conn, false);
+        if let Some(player) = self.players.remove(&conn) {
+            // Position is saved from player's entity before removing it
+            self.save_player(&player);
+            self.entities.remove(player.entity_id);
+            remove_from_tab_list(self, &player);
+            info!("{} left the game", player.profile.name);
+        }
+    }
+
+    // Saving player's data(errors are logged)
+    pub fn save_player(&self, player: &Player) {
+        let entity = match self.entities.get(player.entity_id) {
+            Some(v) => v,
+            None => return,
+        };
+        if let Err(err) = save_player_data(&self.playerdata_dir(), player, entity) {
+            error!("Failed to save {}'s data: {}", player.profile.name, err);
+        }
+    }
+
+    // Directory with players' data in world's directory
+    fn playerdata_dir(&self) -> PathBuf {
+        self.directory.join("playerdata")
+    }
+
+    // Saving the world, its level.dat and players' data
+    pub fn save(&mut self) {
+        save_players(self);
+        let saved = self.world.save();
+        if let Err(err) = self.info.save(&self.directory) {
+            error!("Failed to save level.dat: {}", err);
+        }
+        info!("Saved {} chunks", saved);
+    }
+
+    // Adding a listener of players' chat messages(it can change or cancel them)
+    pub fn on_chat(&mut self, listener: ChatListener) {
+        self.chat_listeners.push(listener);
+    }
+
+    // Adding a task running after delay(in ticks) and repeating with period(0 - once)
+    pub fn schedule(&mut self, delay: u64, period: u64, task: TaskFn) {
+        self.scheduler.schedule(self.tick, delay, period, task);
+    }
+
+    // Handing an event from network
+    pub fn handle_event(&mut self, event: NetEvent) {
+        match event {
+            NetEvent::Joined { conn, profile } => self.join(conn, profile),
+            NetEvent::Packet { conn, frame } => {
+                // Broken packet disconnects only its player(handlers read packet before changing anything)
+                if let Err(err) = handle_packet(self, conn, frame) {
+                    warn!("Invalid packet from connection #{}: {}", conn, err);
+                    self.outbound.disconnect(conn);
+                }
+            }
+            NetEvent::Left { conn } => self.leave(conn),
+        }
+    }
+
+    // Single game's tick: handing network's events, updating the game and sending packets.
+    // Returns false if network stopped
+    pub fn run_tick(&mut self, events: &Receiver<NetEvent>) -> bool {
+        // Handing all events from network
+        let mut opened = true;
+        loop {
+            match events.try_recv() {
+                Ok(event) => self.handle_event(event),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    opened = false;
+                    break;
+                }
+            }
+        }
+        // Updating the game
+        self.info.tick_time();
+        run_console_commands(self);
+        run_due_tasks(self);
+        update_chunks(self);
+        update_items(self);
+        update_tracker(self);
+        // Sending all packets of this tick
+        self.outbound.flush();
+        self.tick += 1;
+        opened
+    }
+}
+
+// Loading world's metadata and overworld from directory(new world is created if there's no level.dat)
+fn open_world(directory: &Path) -> (WorldInfo, World) {
+    let loaded = match WorldInfo::load(directory) {
+        Ok(v) => v,
+        Err(err) => {
+            error!("Failed to load level.dat, creating a new world: {}", err);
+            None
+        }
+    };
+    let created = loaded.is_none();
+    let mut info = loaded.unwrap_or_else(|| WorldInfo::new(LEVEL_NAME, parse_seed(LEVEL_SEED)));
+    let mut world = World::open(&directory.join("region"), true)
+        .with_generator(create_generator(&info), info.seed);
+    world.start_workers(match GENERATOR_WORKERS {
+        0 => thread::available_parallelism()
+            .map(|v| v.get())
+            .unwrap_or(1),
+        v => v,
+    });
+    // Spawn of new world is on surface
+    if created {
+        if let Some(y) = world.surface_height(info.spawn_x, info.spawn_z) {
+            info.spawn_y = y;
+        }
+        info!(
+            "Created a new world \"{}\" with seed {}",
+            info.name, info.seed
+        );
+    }
+    (info, world)
+}
+
+// Saving data of all online players
+fn save_players(game: &mut Game) {
+    for player in game.players.values() {
+        game.save_player(player);
+    }
+}
+
+// Sending world's time to all players
+fn update_time(game: &mut Game) {
+    let packet = create_time_update(game.info.time, game.info.client_day_time());
+    for conn in game.players.keys() {
+        game.outbound.send(*conn, packet.clone());
+    }
+}
+
+// Game's loop with fixed rate(20 TPS) and catch-up on lag
+fn game_loop(mut game: Game, events: Receiver<NetEvent>) {
+    let mut next_tick = Instant::now();
+    let mut previous_start = next_tick;
+    loop {
+        // Stopping with network server
+        if *SHUTDOWN_SERVER.lock().unwrap() {
+            break;
+        }
+        let start = Instant::now();
+        if !game.run_tick(&events) {
+            break;
+        }
+        let now = Instant::now();
+        TICK_STATS
+            .lock()
+            .unwrap()
+            .record(now - start, start - previous_start);
+        previous_start = start;
+        // Waiting for next tick, or running it immediately to catch up
+        next_tick += TICK;
+        if now < next_tick {
+            thread::sleep(next_tick - now);
+        } else if now - next_tick > MAX_CATCH_UP {
+            let behind = now - next_tick;
+            warn!(
+                "Can't keep up! Is the server overloaded? Running {}ms or {} ticks behind",
+                behind.as_millis(),
+                behind.as_millis() / TICK.as_millis()
+            );
+            // Skipping late ticks
+            next_tick = now;
+        }
+    }
+    game.save();
+    *GAME_WORKS.lock().unwrap() = false;
+}
+
+// Start a game's thread which handles events from network
+pub fn spawn_game_thread(
+    events: Receiver<NetEvent>,
+    outbound: Outbound,
+    directory: PathBuf,
+) -> io::Result<()> {
+    thread::Builder::new()
+        .name(String::from("Game"))
+        .spawn(move || game_loop(Game::new(outbound, directory), events))?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use crate::config::{MAX_MOVE_DISTANCE, MOVE_TOLERANCE};
+use crate::game::entity::{BoundingBox, EntityType};
+use crate::game::player::Player;
+use crate::world::block::block;
+use crate::world::World;
+
+// Fall's distance without damage
+const SAFE_FALL_DISTANCE: f64 = 3.0;
+// Distance between checked points of long move
+const PATH_STEP: f64 = 0.5;
+// Client doesn't accept coordinates further from world's center
+const WORLD_BORDER: f64 = 30_000_000.0;
+// Blocks which stop falling
+const FALL_STOPPERS: &[&str] = &[
+    "minecraft:water",
+    "minecraft:flowing_water",
+    "minecraft:lava",
+    "minecraft:flowing_lava",
+    "minecraft:ladder",
+    "minecraft:vine",
+    "minecraft:web",
+];
+
+/// Result of checking player's move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveCheck {
+    Valid,
+    // Move is too long for one packet or its coordinates are invalid
+    TooFast,
+    // Player's box goes through solid blocks
+    ThroughBlocks,
+}
+
+// Player's box at position
+fn player_box((x, y, z): (f64, f64, f64)) -> BoundingBox {
+    let (width, height) = EntityType::Player.size();
+    BoundingBox::of_size(x, y, z, width, height)
+}
+
+/// Box intersects solid blocks of loaded chunks(box is shrunk by tolerance)
+pub fn collides(world: &World, area: &BoundingBox) -> bool {
+    let area = area.grow(-MOVE_TOLERANCE);
+    // Fences and walls are higher than a block, so blocks below are checked too
+    for x in area.min.0.floor() as i32..=area.max.0.floor() as i32 {
+        for y in area.min.1.floor() as i32 - 1..=area.max.1.floor() as i32 {
+            for z in area.min.2.floor() as i32..=area.max.2.floor() as i32 {
+                let shape = match world.collision_box(x, y, z) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let (x, y, z) = (x as f64, y as f64, z as f64);
+                let shape = BoundingBox::new(
+                    (x + shape[0], y + shape[1], z + shape[2]),
+                    (x + shape[3], y + shape[4], z + shape[5]),
+                );
+                if shape.intersects(&area) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Checking player's move from position to another(noclip - player goes through blocks)
+pub fn check_move(
+    world: &World,
+    from: (f64, f64, f64),
+    to: (f64, f64, f64),
+    noclip: bool,
+) -> MoveCheck {
+    let valid = |v: f64| v.is_finite() && v.abs() < WORLD_BORDER;
+    if !valid(to.0) || !valid(to.1) || !valid(to.2) {
+        return MoveCheck::TooFast;
+    }
+    let delta = (to.0 - from.0, to.1 - from.1, to.2 - from.2);
+    let distance = (delta.0 * delta.0 + delta.1 * delta.1 + delta.2 * delta.2).sqrt();
+    if distance > MAX_MOVE_DISTANCE {
+        return MoveCheck::TooFast;
+    }
+    // Player stuck in blocks can get out of them
+    if noclip || collides(world, &player_box(from)) {
+        return MoveCheck::Valid;
+    }
+    // Short moves are checked only at the end, because client steps up and slides along blocks
+    let steps = if distance > 1.0 {
+        (distance / PATH_STEP).ceil() as i32
+    } else {
+        1
+    };
+    for step in 1..=steps {
+        let part = step as f64 / steps as f64;
+        let point = (
+            from.0 + delta.0 * part,
+            from.1 + delta.1 * part,
+            from.2 + delta.2 * part,
+        );
+        if collides(world, &player_box(point)) {
+            return MoveCheck::ThroughBlocks;
+        }
+    }
+    MoveCheck::Valid
+}
+
+// Player is in liquid or climbs, so doesn't fall
+fn fall_stopped(world: &World, (x, y, z): (f64, f64, f64)) -> bool {
+    let state = world.get_block(x.floor() as i32, y.floor() as i32, z.floor() as i32);
+    match block(state >> 4) {
+        Some(v) => FALL_STOPPERS.contains(&v.name.as_str()),
+        None => false,
+    }
+}
+
+/// Updating player's fall by accepted move from position to another,
+/// returns fall's damage after landing
+pub fn update_fall(
+    player: &mut Player,
+    world: &World,
+    from: (f64, f64, f64),
+    to: (f64, f64, f64),
+    on_ground: bool,
+) -> f32 {
+    if player.is_invulnerable() || fall_stopped(world, to) {
+        player.fall_distance = 0.0;
+        return 0.0;
+    }
+    if to.1 < from.1 {
+        player.fall_distance += from.1 - to.1;
+    }
+    if !on_ground {
+        return 0.0;
+    }
+    let damage = (player.fall_distance - SAFE_FALL_DISTANCE).ceil().max(0.0);
+    player.fall_distance = 0.0;
+    damage as f32
+}
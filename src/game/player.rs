@@ -0,0 +1,182 @@
+use crate::config::{CHAT_COLORS, VIEW_DISTANCE};
+use crate::game::entity::Entities;
+use crate::game::inventory::{PlayerInventory, ENDER_CHEST_SIZE};
+use crate::game::window::WindowState;
+use crate::network::proto::packets::play::{
+    create_player_position_look, create_set_experience, create_update_health,
+};
+use crate::network::{GameProfile, Outbound};
+use crate::utils::chat::ChatMessage;
+use crate::utils::nbt::Compound;
+use crate::world::item::ItemStack;
+use ahash::AHashSet;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+// Player's abilities flags
+pub const ABILITY_INVULNERABLE: u8 = 0x01;
+pub const ABILITY_FLYING: u8 = 0x02;
+pub const ABILITY_ALLOW_FLYING: u8 = 0x04;
+pub const ABILITY_CREATIVE: u8 = 0x08;
+
+// Health and food of new or respawned player
+pub const MAX_HEALTH: f32 = 20.0;
+pub const MAX_FOOD: i32 = 20;
+pub const START_SATURATION: f32 = 5.0;
+
+// Player in game
+pub struct Player {
+    // Player's connection
+    pub conn: usize,
+    pub profile: GameProfile,
+    // Name in player list(None - player's name)
+    pub display_name: Option<ChatMessage>,
+    // Player's entity(it has player's position and rotation)
+    pub entity_id: i32,
+    // Game's mode(0 - survival, 1 - creative, 2 - adventure, 3 - spectator)
+    pub gamemode: u8,
+    // Distance of current fall in blocks(for fall damage)
+    pub fall_distance: f64,
+    // Last move was rejected(rejected moves are logged once until valid move)
+    pub moved_wrongly: bool,
+    // Health(0 - dead), food and saturation
+    pub health: f32,
+    pub food: i32,
+    pub saturation: f32,
+    // Experience's level, progress to next level(0.0 - 1.0) and total points
+    pub xp_level: i32,
+    pub xp_progress: f32,
+    pub xp_total: i32,
+    pub inventory: PlayerInventory,
+    pub ender_chest: Vec<Option<ItemStack>>,
+    // Open window, cursor's item and crafting's grid
+    pub windows: WindowState,
+    // Saved data which server doesn't use(kept on saving)
+    pub saved_data: Compound,
+    // Player can use formatting(&) in chat
+    pub chat_colors: bool,
+    // Teleport waiting for client's confirmation(movement is ignored until it)
+    pub teleport_id: Option<i32>,
+    last_teleport_id: i32,
+    // Latency in milliseconds(smoothed round trip time of keep alive, None before first answer)
+    pub ping: Option<i32>,
+    // Ping changed since last update of tab list
+    pub ping_changed: bool,
+    // Keep alive waiting for client's answer(id and sending time)
+    pub keep_alive: Option<(i64, Instant)>,
+    // Time of last sent keep alive
+    pub last_keep_alive: Instant,
+    // Player was kicked and waits for closing of connection
+    pub kicked: bool,
+    // Distance in chunks for sending chunks
+    pub view_distance: u8,
+    // Chunks which are sent to client
+    pub loaded_chunks: AHashSet<(i32, i32)>,
+    // Chunks waiting for sending(nearest first)
+    pub chunk_queue: VecDeque<(i32, i32)>,
+    // Chunk and view distance of last chunks' update(None - needs update)
+    pub chunk_center: Option<((i32, i32), u8)>,
+}
+
+impl Player {
+    pub fn new(conn: usize, profile: GameProfile, entity_id: i32, gamemode: u8) -> Self {
+        Self {
+            conn,
+            profile,
+            display_name: None,
+            entity_id,
+            gamemode,
+            fall_distance: 0.0,
+            moved_wrongly: false,
+            health: MAX_HEALTH,
+            food: MAX_FOOD,
+            saturation: START_SATURATION,
+            xp_level: 0,
+            xp_progress: 0.0,
+            xp_total: 0,
+            inventory: PlayerInventory::new(),
+            ender_chest: vec![None; ENDER_CHEST_SIZE],
+            windows: WindowState::new(),
+            saved_data: Compound::new(),
+            chat_colors: CHAT_COLORS,
+            teleport_id: None,
+            last_teleport_id: 0,
+            ping: None,
+            ping_changed: false,
+            keep_alive: None,
+            last_keep_alive: Instant::now(),
+            kicked: false,
+            view_distance: VIEW_DISTANCE,
+            loaded_chunks: AHashSet::new(),
+            chunk_queue: VecDeque::new(),
+            chunk_center: None,
+        }
+    }
+
+    // Player's abilities by game's mode
+    pub fn abilities(&self) -> u8 {
+        match self.gamemode {
+            1 => ABILITY_INVULNERABLE | ABILITY_ALLOW_FLYING | ABILITY_CREATIVE,
+            3 => ABILITY_INVULNERABLE | ABILITY_ALLOW_FLYING | ABILITY_FLYING,
+            _ => 0,
+        }
+    }
+
+    // Player can't be damaged(creative and spectator)
+    pub fn is_invulnerable(&self) -> bool {
+        self.abilities() & ABILITY_INVULNERABLE != 0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    // Sending health, food and saturation to client
+    pub fn send_health(&self, outbound: &Outbound) {
+        outbound.send(
+            self.conn,
+            create_update_health(self.health, self.food, self.saturation),
+        );
+    }
+
+    // Sending experience's level and progress to client
+    pub fn send_experience(&self, outbound: &Outbound) {
+        outbound.send(
+            self.conn,
+            create_set_experience(self.xp_progress, self.xp_level, self.xp_total),
+        );
+    }
+
+    // Taking damage(ignored if player is invulnerable or dead), returns true if player died
+    pub fn damage(&mut self, outbound: &Outbound, amount: f32) -> bool {
+        if amount <= 0.0 || self.is_invulnerable() || self.is_dead() {
+            return false;
+        }
+        self.health = (self.health - amount).max(0.0);
+        self.send_health(outbound);
+        self.is_dead()
+    }
+
+    // Moving player and its entity to position(client must confirm the teleport)
+    pub fn teleport(
+        &mut self,
+        outbound: &Outbound,
+        entities: &mut Entities,
+        (x, y, z): (f64, f64, f64),
+        (yaw, pitch): (f32, f32),
+    ) {
+        entities.move_entity(self.entity_id, x, y, z);
+        if let Some(entity) = entities.get_mut(self.entity_id) {
+            entity.yaw = yaw;
+            entity.pitch = pitch;
+            entity.head_yaw = yaw;
+        }
+        self.fall_distance = 0.0;
+        self.last_teleport_id = self.last_teleport_id.wrapping_add(1);
+        self.teleport_id = Some(self.last_teleport_id);
+        outbound.send(
+            self.conn,
+            create_player_position_look((x, y, z), yaw, pitch, 0, self.last_teleport_id),
+        );
+    }
+}
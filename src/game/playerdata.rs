@@ -0,0 +1,145 @@
+use crate::game::entity::{Entity, EntityType};
+use crate::game::inventory::{items_from_nbt, items_to_nbt, PlayerInventory};
+use crate::game::player::Player;
+use crate::utils::nbt::{read_gzip, write_gzip, Compound, Tag};
+use crate::utils::uuid::uuid_to_string;
+use crate::world::anvil::DATA_VERSION;
+use crate::{io_error, SResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Player's file in playerdata's directory
+fn player_path(directory: &Path, uuid: u128) -> PathBuf {
+    directory.join(format!("{}.dat", uuid_to_string(uuid)))
+}
+
+fn get_list<'a>(nbt: &'a Compound, key: &str) -> Option<&'a Vec<Tag>> {
+    nbt.get(key).and_then(|v| v.as_list())
+}
+
+/// Player's data with its entity's position like vanilla's playerdata(unknown saved values are kept)
+pub fn player_to_nbt(player: &Player, entity: &Entity) -> Compound {
+    let (x, y, z) = entity.position();
+    let mut nbt = player.saved_data.clone();
+    let mut insert = |key: &str, tag: Tag| nbt.insert(String::from(key), tag);
+    insert("DataVersion", Tag::Int(DATA_VERSION));
+    insert("UUIDMost", Tag::Long((player.profile.uuid >> 64) as i64));
+    insert("UUIDLeast", Tag::Long(player.profile.uuid as i64));
+    insert(
+        "Pos",
+        Tag::List(vec![Tag::Double(x), Tag::Double(y), Tag::Double(z)]),
+    );
+    insert(
+        "Rotation",
+        Tag::List(vec![Tag::Float(entity.yaw), Tag::Float(entity.pitch)]),
+    );
+    // Only overworld exists
+    insert("Dimension", Tag::Int(0));
+    insert("OnGround", Tag::from(entity.on_ground));
+    insert("FallDistance", Tag::Float(player.fall_distance as f32));
+    insert("Health", Tag::Float(player.health));
+    insert("foodLevel", Tag::Int(player.food));
+    insert("foodSaturationLevel", Tag::Float(player.saturation));
+    insert("XpLevel", Tag::Int(player.xp_level));
+    insert("XpP", Tag::Float(player.xp_progress));
+    insert("XpTotal", Tag::Int(player.xp_total));
+    insert("playerGameType", Tag::Int(player.gamemode as i32));
+    insert(
+        "SelectedItemSlot",
+        Tag::Int(player.inventory.selected as i32),
+    );
+    insert("Inventory", Tag::List(player.inventory.to_nbt()));
+    insert(
+        "EnderItems",
+        Tag::List(items_to_nbt(&player.ender_chest, 0)),
+    );
+    nbt
+}
+
+/// Applying saved data to player(missing values aren't changed).
+/// Returns player's entity at saved position(None if it can't be used: other dimension or invalid position)
+pub fn player_from_nbt(player: &mut Player, nbt: &Compound) -> Option<Entity> {
+    let int = |key: &str| nbt.get(key).and_then(|v| v.as_i32());
+    let float = |key: &str| nbt.get(key).and_then(|v| v.as_f32());
+    if let Some(v) = float("Health") {
+        player.health = v.max(0.0);
+    }
+    if let Some(v) = int("foodLevel") {
+        player.food = v;
+    }
+    if let Some(v) = float("foodSaturationLevel") {
+        player.saturation = v;
+    }
+    if let Some(v) = int("XpLevel") {
+        player.xp_level = v;
+    }
+    if let Some(v) = float("XpP") {
+        player.xp_progress = v;
+    }
+    if let Some(v) = int("XpTotal") {
+        player.xp_total = v;
+    }
+    if let Some(v) = int("playerGameType").filter(|v| (0..4).contains(v)) {
+        player.gamemode = v as u8;
+    }
+    if let Some(list) = get_list(nbt, "Inventory") {
+        player.inventory = PlayerInventory::from_nbt(list);
+    }
+    if let Some(v) = int("SelectedItemSlot").filter(|v| (0..9).contains(v)) {
+        player.inventory.selected = v as u8;
+    }
+    if let Some(list) = get_list(nbt, "EnderItems") {
+        items_from_nbt(list, 0, &mut player.ender_chest);
+    }
+    player.saved_data = nbt.clone();
+    let position: Vec<f64> = get_list(nbt, "Pos")
+        .map(|v| v.iter().filter_map(|v| v.as_f64()).collect())
+        .unwrap_or_default();
+    let valid = |v: &f64| v.is_finite() && v.abs() < 30_000_000.0;
+    if int("Dimension").unwrap_or(0) != 0 || position.len() != 3 || !position.iter().all(valid) {
+        return None;
+    }
+    let mut entity = Entity::with_id(
+        player.entity_id,
+        player.profile.uuid,
+        EntityType::Player,
+        position[0],
+        position[1],
+        position[2],
+    );
+    if let Some(rotation) = get_list(nbt, "Rotation") {
+        if let [yaw, pitch] = rotation.as_slice() {
+            entity.yaw = yaw.as_f32().unwrap_or(0.0);
+            entity.pitch = pitch.as_f32().unwrap_or(0.0);
+            entity.head_yaw = entity.yaw;
+        }
+    }
+    entity.on_ground = nbt
+        .get("OnGround")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    player.fall_distance = float("FallDistance").unwrap_or(0.0) as f64;
+    Some(entity)
+}
+
+/// Loading player's data from playerdata's directory(None if player didn't play before)
+pub fn load_player_data(directory: &Path, uuid: u128) -> SResult<Option<Compound>> {
+    let path = player_path(directory, uuid);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read(&path).map_err(|err| io_error("Failed to read player's data", err))?;
+    read_gzip(&data).map(Some)
+}
+
+/// Saving player's data into playerdata's directory like vanilla:
+/// writing a temporary file and replacing previous file by it
+pub fn save_player_data(directory: &Path, player: &Player, entity: &Entity) -> SResult<()> {
+    let data = write_gzip(&player_to_nbt(player, entity))?;
+    fs::create_dir_all(directory)
+        .map_err(|err| io_error("Failed to create playerdata's directory", err))?;
+    let path = player_path(directory, player.profile.uuid);
+    let temp = path.with_extension("dat.tmp");
+    fs::write(&temp, data).map_err(|err| io_error("Failed to write player's data", err))?;
+    fs::rename(&temp, &path).map_err(|err| io_error("Failed to replace player's data", err))
+}
@@ -0,0 +1,69 @@
+use crate::game::Game;
+
+// Task's function
+pub type TaskFn = Box<dyn FnMut(&mut Game) + Send>;
+
+/// Scheduled task
+pub struct Task {
+    // Tick when task runs
+    tick: u64,
+    // Repeating period in ticks(0 - runs once)
+    period: u64,
+    run: TaskFn,
+}
+
+/// Tasks which run on game's thread at specified ticks
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: Vec<Task>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    // Adding a task running after delay(in ticks) and repeating with period(0 - once)
+    pub fn schedule(&mut self, current_tick: u64, delay: u64, period: u64, run: TaskFn) {
+        self.tasks.push(Task {
+            tick: current_tick + delay,
+            period,
+            run,
+        });
+    }
+
+    // Count of waiting tasks
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Taking tasks which must run at tick(others keep waiting)
+    pub fn take_due(&mut self, tick: u64) -> Vec<Task> {
+        let (due, waiting) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition(|v| v.tick <= tick);
+        self.tasks = waiting;
+        due
+    }
+
+    /// Returning task which ran at tick(repeating tasks wait for next period, others are dropped)
+    pub fn reschedule(&mut self, mut task: Task, tick: u64) {
+        if task.period > 0 {
+            task.tick = tick + task.period;
+            self.tasks.push(task);
+        }
+    }
+}
+
+// Running all tasks for current tick(tasks can schedule new tasks)
+pub fn run_due_tasks(game: &mut Game) {
+    let tick = game.tick;
+    for mut task in game.scheduler.take_due(tick) {
+        (task.run)(game);
+        game.scheduler.reschedule(task, tick);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::config::{MAX_PLAYERS, TAB_FOOTER, TAB_HEADER};
+use crate::game::player::Player;
+use crate::game::tick::TICK_STATS;
+use crate::game::Game;
+use crate::network::proto::packets::play::{
+    create_player_list_add, create_player_list_display_name, create_player_list_gamemode,
+    create_player_list_header_footer, create_player_list_remove,
+};
+use crate::utils::chat::ChatMessage;
+
+// How often(in ticks) header and footer are refreshed
+pub const TAB_LIST_REFRESH_PERIOD: u64 = 40;
+
+// Player's entry for adding to player list
+fn list_entry(player: &Player) -> (u128, &str, u8, i32, Option<&ChatMessage>) {
+    (
+        player.profile.uuid,
+        player.profile.name.as_str(),
+        player.gamemode,
+        player.ping.unwrap_or(0),
+        player.display_name.as_ref(),
+    )
+}
+
+// Sending packet to all players
+fn broadcast(game: &Game, packet: Vec<u8>) {
+    for conn in game.players.keys() {
+        game.outbound.send(*conn, packet.clone());
+    }
+}
+
+/// Adding joined player to others' player list and all players to joined player's list
+pub fn add_to_tab_list(game: &Game, conn: usize) {
+    let player = match game.players.get(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    let entries: Vec<_> = game.players.values().map(list_entry).collect();
+    game.outbound.send(conn, create_player_list_add(&entries));
+    let packet = create_player_list_add(&[list_entry(player)]);
+    for other in game.players.keys().filter(|v| **v != conn) {
+        game.outbound.send(*other, packet.clone());
+    }
+    if let Some(packet) = create_header_footer(game) {
+        game.outbound.send(conn, packet);
+    }
+}
+
+/// Removing left player from everyone's player list
+pub fn remove_from_tab_list(game: &Game, player: &Player) {
+    broadcast(game, create_player_list_remove(&[player.profile.uuid]));
+}
+
+/// Sending player's game mode to everyone's player list
+pub fn update_list_gamemode(game: &Game, player: &Player) {
+    broadcast(
+        game,
+        create_player_list_gamemode(&[(player.profile.uuid, player.gamemode)]),
+    );
+}
+
+/// Changing player's name in player list(None - player's name)
+pub fn set_display_name(game: &mut Game, conn: usize, display_name: Option<ChatMessage>) {
+    let player = match game.players.get_mut(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    player.display_name = display_name;
+    let packet =
+        create_player_list_display_name(&[(player.profile.uuid, player.display_name.as_ref())]);
+    broadcast(game, packet);
+}
+
+/// Replacing placeholders in header's or footer's text
+pub fn format_tab_text(text: &str, online: usize, tps: f64, mspt: f64) -> String {
+    text.replace("{online}", &online.to_string())
+        .replace("{max_players}", &MAX_PLAYERS.to_string())
+        .replace("{tps}", &format!("{:.1}", tps))
+        .replace("{mspt}", &format!("{:.1}", mspt))
+}
+
+// Header and footer with current server's state(None if they aren't set)
+fn create_header_footer(game: &Game) -> Option<Vec<u8>> {
+    if TAB_HEADER.is_empty() && TAB_FOOTER.is_empty() {
+        return None;
+    }
+    let stats = TICK_STATS.lock().unwrap().clone();
+    let (online, tps, mspt) = (game.players.len(), stats.tps[0], stats.mspt[0]);
+    Some(create_player_list_header_footer(
+        &ChatMessage::text(format_tab_text(TAB_HEADER, online, tps, mspt)),
+        &ChatMessage::text(format_tab_text(TAB_FOOTER, online, tps, mspt)),
+    ))
+}
+
+// Refreshing header and footer of all players
+pub fn update_header_footer(game: &mut Game) {
+    if let Some(packet) = create_header_footer(game) {
+        broadcast(game, packet);
+    }
+}
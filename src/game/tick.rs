@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+// Ticks per second
+pub const TPS: u32 = 20;
+// Tick's duration(50ms)
+pub const TICK: Duration = Duration::from_millis(1000 / TPS as u64);
+// Max lag for catch-up. If server is late more, the late ticks are skipped
+pub const MAX_CATCH_UP: Duration = Duration::from_secs(2);
+
+lazy_static! {
+    // Tick's statistics, updated by game's thread
+    pub static ref TICK_STATS: std::sync::Mutex<TickStats> = std::sync::Mutex::new(TickStats::new());
+}
+
+// Smoothing factors of per-second samples for 1, 5 and 15 minutes(like Unix's load average)
+fn smoothing(minutes: f64) -> f64 {
+    1.0 - (-1.0 / (minutes * 60.0)).exp()
+}
+
+/// Average TPS and MSPT(milliseconds per tick) for 1m/5m/15m
+#[derive(Debug, Clone)]
+pub struct TickStats {
+    pub tps: [f64; 3],
+    pub mspt: [f64; 3],
+    // Total ticks from start
+    pub ticks: u64,
+    // Current second's samples
+    second_ticks: u32,
+    second_time: Duration,
+    second_elapsed: Duration,
+}
+
+impl Default for TickStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickStats {
+    pub fn new() -> Self {
+        Self {
+            tps: [TPS as f64; 3],
+            mspt: [0.0; 3],
+            ticks: 0,
+            second_ticks: 0,
+            second_time: Duration::ZERO,
+            second_elapsed: Duration::ZERO,
+        }
+    }
+
+    // Recording a tick: its work's time and time since previous tick's start
+    pub fn record(&mut self, work: Duration, since_previous: Duration) {
+        self.ticks += 1;
+        self.second_ticks += 1;
+        self.second_time += work;
+        self.second_elapsed += since_previous;
+        // Updating averages every second
+        if self.second_elapsed >= Duration::from_secs(1) {
+            let tps =
+                (self.second_ticks as f64 / self.second_elapsed.as_secs_f64()).min(TPS as f64);
+            let mspt = self.second_time.as_secs_f64() * 1000.0 / self.second_ticks as f64;
+            for (i, minutes) in [1.0, 5.0, 15.0].iter().enumerate() {
+                let factor = smoothing(*minutes);
+                self.tps[i] += (tps - self.tps[i]) * factor;
+                // First sample replaces initial zero
+                self.mspt[i] = if self.ticks <= self.second_ticks as u64 {
+                    mspt
+                } else {
+                    self.mspt[i] + (mspt - self.mspt[i]) * factor
+                };
+            }
+            self.second_ticks = 0;
+            self.second_time = Duration::ZERO;
+            self.second_elapsed = Duration::ZERO;
+        }
+    }
+}
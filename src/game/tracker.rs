@@ -0,0 +1,240 @@
+use crate::game::entity::{Entity, EntityType, SpawnKind};
+use crate::game::Game;
+use crate::network::proto::packets::play::{
+    create_destroy_entities, create_entity_head_look, create_entity_look,
+    create_entity_look_relative_move, create_entity_metadata, create_entity_relative_move,
+    create_entity_teleport, create_entity_velocity, create_spawn_experience_orb, create_spawn_mob,
+    create_spawn_object, create_spawn_player, to_angle, to_velocity,
+};
+use ahash::{AHashMap, AHashSet};
+
+// Distance in blocks where players see entity(also limited by loaded chunks)
+fn tracking_range(kind: EntityType) -> f64 {
+    match kind.spawn_kind() {
+        SpawnKind::Player => 512.0,
+        SpawnKind::Mob(_) => 80.0,
+        SpawnKind::ExperienceOrb => 160.0,
+        SpawnKind::Object(_) => 64.0,
+    }
+}
+
+// Position in units of 1/4096 block like client stores it
+fn encode_position(entity: &Entity) -> (i64, i64, i64) {
+    let encode = |v: f64| (v * 4096.0).floor() as i64;
+    let (x, y, z) = entity.position();
+    (encode(x), encode(y), encode(z))
+}
+
+fn encode_velocity(entity: &Entity) -> (i16, i16, i16) {
+    let (x, y, z) = entity.velocity;
+    (to_velocity(x), to_velocity(y), to_velocity(z))
+}
+
+// Packets spawning entity for a player
+fn spawn_packets(entity: &Entity) -> Vec<Vec<u8>> {
+    let position = entity.position();
+    let rotation = (to_angle(entity.yaw), to_angle(entity.pitch));
+    let velocity = encode_velocity(entity);
+    let spawn = match entity.kind.spawn_kind() {
+        SpawnKind::Player => create_spawn_player(
+            entity.id,
+            entity.uuid,
+            position,
+            rotation,
+            entity.metadata.encode(),
+        ),
+        SpawnKind::Object(kind) => create_spawn_object(
+            entity.id,
+            entity.uuid,
+            kind,
+            position,
+            rotation,
+            entity.data,
+            velocity,
+        ),
+        SpawnKind::Mob(kind) => create_spawn_mob(
+            entity.id,
+            entity.uuid,
+            kind as i32,
+            position,
+            (rotation.0, rotation.1, to_angle(entity.head_yaw)),
+            velocity,
+            entity.metadata.encode(),
+        ),
+        SpawnKind::ExperienceOrb => {
+            create_spawn_experience_orb(entity.id, position, entity.data as i16)
+        }
+    };
+    let mut packets = vec![spawn];
+    // Objects and orbs are spawned without metadata
+    let with_metadata = matches!(
+        entity.kind.spawn_kind(),
+        SpawnKind::Player | SpawnKind::Mob(_)
+    );
+    if !with_metadata && !entity.metadata.is_empty() {
+        packets.push(create_entity_metadata(entity.id, entity.metadata.encode()));
+    }
+    // Spawn Player doesn't have head's rotation
+    if entity.kind == EntityType::Player {
+        packets.push(create_entity_head_look(
+            entity.id,
+            to_angle(entity.head_yaw),
+        ));
+    }
+    packets
+}
+
+// Entity's state which players know
+struct TrackedEntity {
+    // Connections of players who see the entity
+    viewers: AHashSet<usize>,
+    // Last sent position, rotation, head's rotation and velocity
+    position: (i64, i64, i64),
+    rotation: (u8, u8),
+    head_yaw: u8,
+    velocity: (i16, i16, i16),
+}
+
+impl TrackedEntity {
+    fn new(entity: &Entity) -> Self {
+        Self {
+            viewers: AHashSet::new(),
+            position: encode_position(entity),
+            rotation: (to_angle(entity.yaw), to_angle(entity.pitch)),
+            head_yaw: to_angle(entity.head_yaw),
+            velocity: encode_velocity(entity),
+        }
+    }
+
+    // Packets with entity's changes since last update(one packet of each kind per tick)
+    fn updates(&mut self, entity: &mut Entity) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        let position = encode_position(entity);
+        let rotation = (to_angle(entity.yaw), to_angle(entity.pitch));
+        let moved = position != self.position;
+        let rotated = rotation != self.rotation;
+        if moved || rotated {
+            let delta = (
+                i16::try_from(position.0 - self.position.0),
+                i16::try_from(position.1 - self.position.1),
+                i16::try_from(position.2 - self.position.2),
+            );
+            packets.push(match delta {
+                _ if !moved => create_entity_look(entity.id, rotation, entity.on_ground),
+                (Ok(dx), Ok(dy), Ok(dz)) if rotated => create_entity_look_relative_move(
+                    entity.id,
+                    (dx, dy, dz),
+                    rotation,
+                    entity.on_ground,
+                ),
+                (Ok(dx), Ok(dy), Ok(dz)) => {
+                    create_entity_relative_move(entity.id, (dx, dy, dz), entity.on_ground)
+                }
+                // Relative move can't be longer than 8 blocks
+                _ => {
+                    create_entity_teleport(entity.id, entity.position(), rotation, entity.on_ground)
+                }
+            });
+            self.position = position;
+            self.rotation = rotation;
+        }
+        let head_yaw = to_angle(entity.head_yaw);
+        if head_yaw != self.head_yaw {
+            packets.push(create_entity_head_look(entity.id, head_yaw));
+            self.head_yaw = head_yaw;
+        }
+        let velocity = encode_velocity(entity);
+        if velocity != self.velocity {
+            packets.push(create_entity_velocity(entity.id, velocity));
+            self.velocity = velocity;
+        }
+        // Only changed metadata's values
+        if let Some(metadata) = entity.metadata.encode_dirty() {
+            packets.push(create_entity_metadata(entity.id, metadata));
+        }
+        packets
+    }
+}
+
+/// Entities' states sent to players
+#[derive(Default)]
+pub struct EntityTracker {
+    entities: AHashMap<i32, TrackedEntity>,
+}
+
+impl EntityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connections of players who see the entity
+    pub fn viewers(&self, entity_id: i32) -> Option<&AHashSet<usize>> {
+        self.entities.get(&entity_id).map(|v| &v.viewers)
+    }
+}
+
+// Spawning, moving and destroying entities for players in range(once per tick)
+pub fn update_tracker(game: &mut Game) {
+    let Game {
+        players,
+        entities,
+        tracker,
+        outbound,
+        ..
+    } = game;
+    // Destroyed entities are sent in one packet for each player
+    let mut destroyed: AHashMap<usize, Vec<i32>> = AHashMap::new();
+    tracker.entities.retain(|id, tracked| {
+        if entities.get(*id).is_some() {
+            return true;
+        }
+        for conn in &tracked.viewers {
+            destroyed.entry(*conn).or_default().push(*id);
+        }
+        false
+    });
+    // Players' positions are taken before changing entities
+    let positions: AHashMap<i32, (f64, f64, f64)> = players
+        .values()
+        .filter_map(|v| Some((v.entity_id, entities.get(v.entity_id)?.position())))
+        .collect();
+    for entity in entities.iter_mut() {
+        let tracked = tracker
+            .entities
+            .entry(entity.id)
+            .or_insert_with(|| TrackedEntity::new(entity));
+        let range = tracking_range(entity.kind);
+        let chunk = entity.chunk_position();
+        let (x, _, z) = entity.position();
+        let in_range =
+            |(vx, _, vz): (f64, f64, f64)| (vx - x).abs() <= range && (vz - z).abs() <= range;
+        let viewers: AHashSet<usize> = players
+            .values()
+            .filter(|v| v.entity_id != entity.id && v.loaded_chunks.contains(&chunk))
+            .filter(|v| positions.get(&v.entity_id).is_some_and(|v| in_range(*v)))
+            .map(|v| v.conn)
+            .collect();
+        let updates = tracked.updates(entity);
+        let entity = &*entity;
+        for conn in &tracked.viewers {
+            if viewers.contains(conn) {
+                for packet in &updates {
+                    outbound.send(*conn, packet.clone());
+                }
+            } else if players.contains_key(conn) {
+                destroyed.entry(*conn).or_default().push(entity.id);
+            }
+        }
+        for conn in viewers.difference(&tracked.viewers) {
+            for packet in spawn_packets(entity) {
+                outbound.send(*conn, packet);
+            }
+        }
+        tracked.viewers = viewers;
+    }
+    for (conn, ids) in destroyed {
+        if players.contains_key(&conn) {
+            outbound.send(conn, create_destroy_entities(&ids));
+        }
+    }
+}
@@ -0,0 +1,757 @@
+use crate::game::entity::{Entity, EntityType};
+use crate::game::inventory::{PlayerInventory, ENDER_CHEST_SIZE, HOTBAR_SIZE, MAIN_SIZE};
+use crate::game::items::PICKUP_DELAY;
+use crate::game::player::Player;
+use crate::game::Game;
+use crate::network::proto::packets::play::{
+    create_close_window, create_confirm_transaction, create_held_item_change, create_open_window,
+    create_set_slot, create_window_items,
+};
+use crate::network::Outbound;
+use crate::utils::chat::ChatMessage;
+use crate::world::item::ItemStack;
+
+// Player's inventory's window(it's always open)
+pub const PLAYER_WINDOW: u8 = 0;
+// Slots of player's inventory's window: crafting's result and grid, armor, main inventory, hotbar and second hand
+pub const PLAYER_WINDOW_SIZE: usize = 46;
+// Slot of clicks outside of window
+pub const OUTSIDE_SLOT: i16 = -999;
+// Crafting's grid in player's inventory
+const CRAFTING_SIZE: usize = 4;
+// Container's windows get ids from 1 to this
+const MAX_WINDOW_ID: u8 = 100;
+
+/// Items of container's window
+#[derive(Debug, Clone, PartialEq)]
+pub enum Contents {
+    // Window's own items(they're lost after closing)
+    Items(Vec<Option<ItemStack>>),
+    // Player's ender chest
+    EnderChest,
+}
+
+/// Opened container's window(player's main inventory and hotbar follow its slots)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    pub id: u8,
+    // Window's type like minecraft:chest
+    pub kind: String,
+    pub title: String,
+    pub contents: Contents,
+}
+
+impl Window {
+    /// Count of container's slots
+    pub fn size(&self) -> usize {
+        match &self.contents {
+            Contents::Items(v) => v.len(),
+            Contents::EnderChest => ENDER_CHEST_SIZE,
+        }
+    }
+}
+
+// Slots taken by dragging with button(0 - left, 1 - right, 2 - middle)
+#[derive(Debug, Clone, PartialEq)]
+struct Drag {
+    button: i8,
+    slots: Vec<usize>,
+}
+
+/// Player's windows: open container, item on cursor and crafting's grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowState {
+    pub open: Option<Window>,
+    pub cursor: Option<ItemStack>,
+    // Items in inventory's crafting grid(returned to inventory on closing)
+    pub crafting: Vec<Option<ItemStack>>,
+    // Crafting isn't supported, so result is always empty
+    craft_result: Option<ItemStack>,
+    drag: Option<Drag>,
+    // Window's id and action of rejected click waiting for client's confirmation(other clicks are ignored until it)
+    pub rejected: Option<(u8, i16)>,
+    last_window_id: u8,
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            open: None,
+            cursor: None,
+            crafting: vec![None; CRAFTING_SIZE],
+            craft_result: None,
+            drag: None,
+            rejected: None,
+            last_window_id: 0,
+        }
+    }
+}
+
+impl WindowState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of window which player sees
+    pub fn window_id(&self) -> u8 {
+        self.open.as_ref().map(|v| v.id).unwrap_or(PLAYER_WINDOW)
+    }
+
+    // Id for a new container's window
+    fn next_window_id(&mut self) -> u8 {
+        self.last_window_id = self.last_window_id % MAX_WINDOW_ID + 1;
+        self.last_window_id
+    }
+}
+
+/// Armor's slot for item(0 - boots, 3 - helmet), None if item can't be worn
+pub fn armor_slot(item: &ItemStack) -> Option<usize> {
+    let name = item.item()?.name.as_str();
+    match name {
+        _ if name.ends_with("_boots") => Some(0),
+        _ if name.ends_with("_leggings") => Some(1),
+        _ if name.ends_with("_chestplate") || name == "minecraft:elytra" => Some(2),
+        _ if name.ends_with("_helmet")
+            || name == "minecraft:skull"
+            || name == "minecraft:pumpkin" =>
+        {
+            Some(3)
+        }
+        _ => None,
+    }
+}
+
+// What can be placed into slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotKind {
+    Normal,
+    CraftResult,
+    Armor(usize),
+}
+
+fn can_place(kind: SlotKind, item: &ItemStack) -> bool {
+    match kind {
+        SlotKind::Normal => true,
+        SlotKind::CraftResult => false,
+        SlotKind::Armor(slot) => armor_slot(item) == Some(slot),
+    }
+}
+
+// Max count of item in slot
+fn max_count(kind: SlotKind, item: &ItemStack) -> u8 {
+    match kind {
+        SlotKind::Armor(_) => 1,
+        _ => item.max_stack(),
+    }
+}
+
+// Taking count of items from stack
+fn split(stack: &mut ItemStack, count: u8) -> ItemStack {
+    let mut taken = stack.clone();
+    taken.count = count;
+    stack.count -= count;
+    taken
+}
+
+// Stack or nothing if it's empty
+fn non_empty(stack: ItemStack) -> Option<ItemStack> {
+    (stack.count > 0).then_some(stack)
+}
+
+// Window's slots over player's inventory and container
+struct Slots<'a> {
+    inventory: &'a mut PlayerInventory,
+    crafting: &'a mut [Option<ItemStack>],
+    craft_result: &'a mut Option<ItemStack>,
+    container: Option<&'a mut [Option<ItemStack>]>,
+}
+
+impl Slots<'_> {
+    // Count of container's slots(0 in player's inventory)
+    fn container_size(&self) -> usize {
+        self.container.as_ref().map(|v| v.len()).unwrap_or(0)
+    }
+
+    fn len(&self) -> usize {
+        match &self.container {
+            Some(v) => v.len() + MAIN_SIZE,
+            None => PLAYER_WINDOW_SIZE,
+        }
+    }
+
+    // Window's slot of hotbar's slot
+    fn hotbar_slot(&self, hotbar: usize) -> usize {
+        match &self.container {
+            Some(v) => v.len() + MAIN_SIZE - HOTBAR_SIZE + hotbar,
+            None => 36 + hotbar,
+        }
+    }
+
+    fn kind(&self, slot: usize) -> SlotKind {
+        match (&self.container, slot) {
+            (None, 0) => SlotKind::CraftResult,
+            (None, 5..=8) => SlotKind::Armor(8 - slot),
+            _ => SlotKind::Normal,
+        }
+    }
+
+    fn get_mut(&mut self, slot: usize) -> &mut Option<ItemStack> {
+        let size = self.container_size();
+        let inventory = &mut *self.inventory;
+        match &mut self.container {
+            Some(container) if slot < size => &mut container[slot],
+            // Main inventory(9-35) goes before hotbar(0-8)
+            Some(_) if slot < size + MAIN_SIZE - HOTBAR_SIZE => {
+                &mut inventory.main[slot - size + HOTBAR_SIZE]
+            }
+            Some(_) => &mut inventory.main[slot - size - (MAIN_SIZE - HOTBAR_SIZE)],
+            None => match slot {
+                0 => self.craft_result,
+                1..=4 => &mut self.crafting[slot - 1],
+                5..=8 => &mut inventory.armor[8 - slot],
+                9..=35 => &mut inventory.main[slot],
+                36..=44 => &mut inventory.main[slot - 36],
+                _ => &mut inventory.offhand,
+            },
+        }
+    }
+
+    fn get(&mut self, slot: usize) -> Option<&ItemStack> {
+        self.get_mut(slot).as_ref()
+    }
+
+    // Slots where shift-click moves item from slot(in order of filling)
+    fn quick_move_targets(&mut self, slot: usize, item: &ItemStack) -> Vec<usize> {
+        let size = self.container_size();
+        if size > 0 {
+            return match slot < size {
+                true => (size..size + MAIN_SIZE).rev().collect(),
+                false => (0..size).collect(),
+            };
+        }
+        // Armor goes to its empty slot first
+        let armor: Option<usize> = armor_slot(item)
+            .map(|v| 8 - v)
+            .filter(|v| self.get(*v).is_none());
+        match slot {
+            9..=35 => armor.into_iter().chain(36..45).collect(),
+            36..=44 => armor.into_iter().chain(9..36).collect(),
+            _ => (9..45).collect(),
+        }
+    }
+
+    // Putting item into slots(similar stacks first, then empty slots), returns what doesn't fit
+    fn merge(&mut self, mut item: ItemStack, targets: &[usize]) -> Option<ItemStack> {
+        for pass in 0..2 {
+            for target in targets {
+                let kind = self.kind(*target);
+                if item.count == 0 {
+                    break;
+                }
+                if !can_place(kind, &item) {
+                    continue;
+                }
+                let max = max_count(kind, &item);
+                let slot = self.get_mut(*target);
+                match slot {
+                    Some(stack) if pass == 0 && stack.is_similar(&item) => {
+                        let count = item.count.min(max.saturating_sub(stack.count));
+                        stack.count += count;
+                        item.count -= count;
+                    }
+                    None if pass == 1 => {
+                        let count = item.count.min(max);
+                        *slot = Some(split(&mut item, count));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        non_empty(item)
+    }
+}
+
+/// Click from Click Window packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Click {
+    // Window's slot(-999 - outside of window)
+    pub slot: i16,
+    pub button: i8,
+    pub mode: i32,
+}
+
+/// Result of applied click
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClickResult {
+    // Items thrown out of window
+    pub dropped: Vec<ItemStack>,
+    // Item which client sends as clicked if it has the same state as server
+    pub clicked: Option<ItemStack>,
+}
+
+// Left(take or put all) or right(take half or put one) click on slot
+fn pickup(slots: &mut Slots, cursor: &mut Option<ItemStack>, slot: usize, right: bool) {
+    let kind = slots.kind(slot);
+    let target = slots.get_mut(slot);
+    match (target.take(), cursor.take()) {
+        (None, None) => {}
+        (Some(mut stack), None) => {
+            let count = if right {
+                stack.count.div_ceil(2)
+            } else {
+                stack.count
+            };
+            *cursor = Some(split(&mut stack, count));
+            *target = non_empty(stack);
+        }
+        (None, Some(mut held)) if can_place(kind, &held) => {
+            let count = if right { 1 } else { held.count }.min(max_count(kind, &held));
+            *target = Some(split(&mut held, count));
+            *cursor = non_empty(held);
+        }
+        (Some(mut stack), Some(mut held)) if stack.is_similar(&held) && can_place(kind, &held) => {
+            let space = max_count(kind, &stack).saturating_sub(stack.count);
+            let count = if right { 1 } else { held.count }.min(space);
+            stack.count += count;
+            held.count -= count;
+            *target = Some(stack);
+            *cursor = non_empty(held);
+        }
+        // Different items are swapped if item from cursor fits
+        (Some(stack), Some(held))
+            if can_place(kind, &held) && held.count <= max_count(kind, &held) =>
+        {
+            *target = Some(held);
+            *cursor = Some(stack);
+        }
+        (stack, held) => {
+            *target = stack;
+            *cursor = held;
+        }
+    }
+}
+
+// Shift-click: moving slot's item to other part of window, returns true if something moved
+fn quick_move(slots: &mut Slots, slot: usize) -> bool {
+    let item = match slots.get_mut(slot).take() {
+        Some(v) => v,
+        None => return false,
+    };
+    let count = item.count;
+    let targets = slots.quick_move_targets(slot, &item);
+    let rest = slots.merge(item, &targets);
+    let moved = rest.as_ref().map(|v| v.count) != Some(count);
+    *slots.get_mut(slot) = rest;
+    moved
+}
+
+// Number key: swapping slot with hotbar's slot, None if hotbar's item can't be placed
+fn swap_hotbar(slots: &mut Slots, slot: usize, hotbar: usize) -> Option<()> {
+    let target = slots.hotbar_slot(hotbar);
+    let kind = slots.kind(slot);
+    if let Some(held) = slots.get(target) {
+        if !can_place(kind, held) || held.count > max_count(kind, held) {
+            return None;
+        }
+    }
+    let item = slots.get_mut(slot).take();
+    let held = std::mem::replace(slots.get_mut(target), item);
+    *slots.get_mut(slot) = held;
+    Some(())
+}
+
+// Dragging(painting) item from cursor over slots, None if drag's sequence is broken
+fn drag(
+    slots: &mut Slots,
+    cursor: &mut Option<ItemStack>,
+    drag: &mut Option<Drag>,
+    button: i8,
+    slot: Option<usize>,
+    creative: bool,
+) -> Option<()> {
+    // Buttons: 0, 4 and 8 - start, 1, 5 and 9 - adding slot, 2, 6 and 10 - end of left, right and middle drag
+    let (kind, stage) = (button / 4, button % 4);
+    match (stage, slot) {
+        (0, None) if (0..2).contains(&kind) || (kind == 2 && creative) => {
+            *drag = cursor.as_ref().map(|_| Drag {
+                button: kind,
+                slots: Vec::new(),
+            });
+        }
+        (1, Some(slot)) => {
+            let (current, item) = match (drag.as_mut(), cursor.as_ref()) {
+                (Some(v), Some(item)) if v.button == kind => (v, item),
+                _ => return None,
+            };
+            let slot_kind = slots.kind(slot);
+            let fits = match slots.get(slot) {
+                Some(stack) => stack.is_similar(item) && stack.count < max_count(slot_kind, stack),
+                None => true,
+            };
+            if can_place(slot_kind, item) && fits && !current.slots.contains(&slot) {
+                current.slots.push(slot);
+            }
+        }
+        (2, None) => {
+            let (current, mut item) = match (drag.take(), cursor.take()) {
+                (Some(v), Some(item)) if v.button == kind => (v, item),
+                (_, item) => {
+                    *cursor = item;
+                    return None;
+                }
+            };
+            let per_slot = match kind {
+                0 => item.count / current.slots.len().max(1) as u8,
+                1 => 1,
+                _ => item.max_stack(),
+            };
+            for slot in current.slots {
+                let max = max_count(slots.kind(slot), &item);
+                let target = slots.get_mut(slot);
+                let existing = target.as_ref().map(|v| v.count).unwrap_or(0);
+                let mut count = per_slot.min(max.saturating_sub(existing));
+                // Middle drag copies items in creative
+                if kind != 2 {
+                    count = count.min(item.count);
+                }
+                if count == 0 {
+                    continue;
+                }
+                match target {
+                    Some(stack) => stack.count += count,
+                    None => {
+                        let mut stack = item.clone();
+                        stack.count = count;
+                        *target = Some(stack);
+                    }
+                }
+                if kind != 2 {
+                    item.count -= count;
+                }
+            }
+            *cursor = non_empty(item);
+        }
+        _ => {
+            *drag = None;
+            return None;
+        }
+    }
+    Some(())
+}
+
+// Double click: collecting similar items into cursor(not full stacks first)
+fn collect(slots: &mut Slots, cursor: &mut Option<ItemStack>) {
+    let item = match cursor.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+    let max = item.max_stack();
+    for pass in 0..2 {
+        for slot in 0..slots.len() {
+            if item.count >= max {
+                return;
+            }
+            if slots.kind(slot) == SlotKind::CraftResult {
+                continue;
+            }
+            let target = slots.get_mut(slot);
+            if let Some(stack) = target {
+                if stack.is_similar(item) && (pass == 1 || stack.count < stack.max_stack()) {
+                    let count = stack.count.min(max - item.count);
+                    stack.count -= count;
+                    item.count += count;
+                    if stack.count == 0 {
+                        *target = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Applying click to player's open window. None if click isn't valid
+pub fn click(player: &mut Player, click: Click) -> Option<ClickResult> {
+    let creative = player.gamemode == 1;
+    let Player {
+        inventory,
+        windows,
+        ender_chest,
+        ..
+    } = player;
+    let WindowState {
+        open,
+        cursor,
+        crafting,
+        craft_result,
+        drag: current_drag,
+        ..
+    } = windows;
+    let container = open.as_mut().map(|v| match &mut v.contents {
+        Contents::Items(items) => items.as_mut_slice(),
+        Contents::EnderChest => ender_chest.as_mut_slice(),
+    });
+    let mut slots = Slots {
+        inventory,
+        crafting,
+        craft_result,
+        container,
+    };
+    let slot = match click.slot {
+        OUTSIDE_SLOT => None,
+        v if (0..slots.len() as i16).contains(&v) => Some(v as usize),
+        _ => return None,
+    };
+    // Dragging is broken by any other click
+    if click.mode != 5 {
+        *current_drag = None;
+    }
+    let mut result = ClickResult::default();
+    match (click.mode, click.button, slot) {
+        // Throwing cursor's item(all or one)
+        (0, 0 | 1, None) => {
+            if let Some(mut held) = cursor.take() {
+                let count = if click.button == 0 { held.count } else { 1 };
+                result.dropped.push(split(&mut held, count));
+                *cursor = non_empty(held);
+            }
+        }
+        (0, 0 | 1, Some(slot)) => {
+            result.clicked = slots.get(slot).cloned();
+            pickup(&mut slots, cursor, slot, click.button == 1);
+        }
+        (1, 0 | 1, Some(slot)) => {
+            let item = slots.get(slot).cloned();
+            if quick_move(&mut slots, slot) {
+                result.clicked = item;
+            }
+        }
+        (2, 0..=8, Some(slot)) => swap_hotbar(&mut slots, slot, click.button as usize)?,
+        // Middle click takes full stack in creative
+        (3, 2, Some(slot)) => {
+            if creative && cursor.is_none() {
+                *cursor = slots.get(slot).map(|v| {
+                    let mut stack = v.clone();
+                    stack.count = v.max_stack();
+                    stack
+                });
+            }
+        }
+        (4, 0 | 1, None) => {}
+        // Throwing slot's item(one or all)
+        (4, 0 | 1, Some(slot)) => {
+            let target = slots.get_mut(slot);
+            if let Some(mut stack) = target.take() {
+                let count = if click.button == 0 { 1 } else { stack.count };
+                result.dropped.push(split(&mut stack, count));
+                *target = non_empty(stack);
+            }
+        }
+        (5, button, slot) => drag(&mut slots, cursor, current_drag, button, slot, creative)?,
+        (6, 0, Some(_)) => collect(&mut slots, cursor),
+        _ => return None,
+    }
+    Some(result)
+}
+
+/// Items of all window's slots
+pub fn window_items(player: &Player) -> Vec<Option<ItemStack>> {
+    let inventory = &player.inventory;
+    let windows = &player.windows;
+    // Main inventory goes before hotbar
+    let main = inventory.main[HOTBAR_SIZE..]
+        .iter()
+        .chain(&inventory.main[..HOTBAR_SIZE])
+        .cloned();
+    match &windows.open {
+        Some(window) => {
+            let container = match &window.contents {
+                Contents::Items(v) => v,
+                Contents::EnderChest => &player.ender_chest,
+            };
+            container.iter().cloned().chain(main).collect()
+        }
+        None => std::iter::once(windows.craft_result.clone())
+            .chain(windows.crafting.iter().cloned())
+            .chain(inventory.armor.iter().rev().cloned())
+            .chain(main)
+            .chain(std::iter::once(inventory.offhand.clone()))
+            .collect(),
+    }
+}
+
+/// Sending all slots of player's window and cursor's item(for correcting client's state)
+pub fn send_window(outbound: &Outbound, player: &Player) {
+    let window_id = player.windows.window_id();
+    outbound.send(
+        player.conn,
+        create_window_items(window_id, &window_items(player)),
+    );
+    outbound.send(
+        player.conn,
+        create_set_slot(-1, -1, player.windows.cursor.as_ref()),
+    );
+}
+
+/// Sending player's inventory and selected hotbar's slot(on join)
+pub fn send_inventory(outbound: &Outbound, player: &Player) {
+    send_window(outbound, player);
+    outbound.send(
+        player.conn,
+        create_held_item_change(player.inventory.selected),
+    );
+}
+
+/// Throwing items from player's eyes in looking direction
+pub fn drop_items(game: &mut Game, conn: usize, items: Vec<ItemStack>) {
+    let player = match game
+        .players
+        .get(&conn)
+        .and_then(|v| game.entities.get(v.entity_id))
+    {
+        Some(v) => v,
+        None => return,
+    };
+    let (yaw, pitch) = (
+        (player.yaw as f64).to_radians(),
+        (player.pitch as f64).to_radians(),
+    );
+    let velocity = (
+        -yaw.sin() * pitch.cos() * 0.3,
+        -pitch.sin() * 0.3 + 0.1,
+        yaw.cos() * pitch.cos() * 0.3,
+    );
+    let (x, y, z) = player.position();
+    let y = y + 1.32;
+    for item in items {
+        let mut entity = Entity::new(EntityType::Item, x, y, z);
+        entity.velocity = velocity;
+        entity.pickup_delay = PICKUP_DELAY;
+        entity.metadata.set_item(Some(item));
+        game.entities.spawn(entity);
+    }
+}
+
+// Returning items from cursor and crafting's grid into inventory, returns what doesn't fit
+fn return_items(player: &mut Player) -> Vec<ItemStack> {
+    let windows = &mut player.windows;
+    let items: Vec<ItemStack> = windows
+        .cursor
+        .take()
+        .into_iter()
+        .chain(windows.crafting.iter_mut().filter_map(|v| v.take()))
+        .collect();
+    items
+        .into_iter()
+        .filter_map(|v| player.inventory.add_item(v))
+        .collect()
+}
+
+/// Opening container's window for player(previous window is closed)
+pub fn open_window(game: &mut Game, conn: usize, kind: &str, title: &str, contents: Contents) {
+    close_window(game, conn, true);
+    let player = match game.players.get_mut(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    let window = Window {
+        id: player.windows.next_window_id(),
+        kind: kind.to_string(),
+        title: title.to_string(),
+        contents,
+    };
+    game.outbound.send(
+        conn,
+        create_open_window(
+            window.id,
+            &window.kind,
+            &ChatMessage::str(&window.title),
+            window.size() as u8,
+        ),
+    );
+    player.windows.open = Some(window);
+    send_window(&game.outbound, player);
+}
+
+/// Closing player's window(notify - sending Close Window to client).
+/// Items on cursor and in crafting's grid return to inventory or are dropped
+pub fn close_window(game: &mut Game, conn: usize, notify: bool) {
+    let player = match game.players.get_mut(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    if let Some(window) = player.windows.open.take() {
+        if notify {
+            game.outbound.send(conn, create_close_window(window.id));
+        }
+    }
+    player.windows.drag = None;
+    let dropped = return_items(player);
+    drop_items(game, conn, dropped);
+}
+
+/// Handing player's click: state is changed by server's rules and client is corrected if it predicted other result
+pub fn handle_click(
+    game: &mut Game,
+    conn: usize,
+    window_id: u8,
+    action: i16,
+    click_info: Click,
+    clicked: Option<ItemStack>,
+) {
+    let player = match game.players.get_mut(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    // Clicks in closed windows and before confirmation of rejected click are ignored
+    if window_id != player.windows.window_id() || player.windows.rejected.is_some() {
+        return;
+    }
+    let result = click(player, click_info);
+    let accepted = matches!(&result, Some(v) if v.clicked == clicked);
+    game.outbound.send(
+        conn,
+        create_confirm_transaction(window_id as i8, action, accepted),
+    );
+    if !accepted {
+        player.windows.rejected = Some((window_id, action));
+        send_window(&game.outbound, player);
+    }
+    if let Some(result) = result {
+        drop_items(game, conn, result.dropped);
+    }
+}
+
+/// Handing creative inventory's action: setting slot of player's inventory(-1 - throwing item)
+pub fn creative_action(game: &mut Game, conn: usize, slot: i16, item: Option<ItemStack>) {
+    let player = match game.players.get_mut(&conn) {
+        Some(v) => v,
+        None => return,
+    };
+    let item = item.filter(|v| v.count > 0);
+    let valid = item
+        .as_ref()
+        .map(|v| v.item().is_some() && v.count <= 64)
+        .unwrap_or(true);
+    if player.gamemode != 1 || !valid {
+        return send_window(&game.outbound, player);
+    }
+    match slot {
+        -1 => drop_items(game, conn, item.into_iter().collect()),
+        // Slots of player's inventory's window(crafting's result can't be set)
+        1..=45 => {
+            let WindowState {
+                crafting,
+                craft_result,
+                ..
+            } = &mut player.windows;
+            let mut slots = Slots {
+                inventory: &mut player.inventory,
+                crafting,
+                craft_result,
+                container: None,
+            };
+            *slots.get_mut(slot as usize) = item;
+        }
+        _ => send_window(&game.outbound, player),
+    }
+}
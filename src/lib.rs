@@ -0,0 +1,54 @@
+#![allow(unused_must_use)]
+use crate::game::spawn_game_thread;
+use crate::network::spawn_network_server;
+use std::error::Error;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::Path;
+
+// Use a macros from serde(Serialize and Deserialize), log(Logging) and lazy_static(Global variables)
+#[macro_use]
+extern crate serde;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate lazy_static;
+
+pub mod client;
+pub mod config;
+pub mod game;
+pub mod network;
+pub mod utils;
+pub mod world;
+
+// Custom error(yes, not std::io:Error)
+#[derive(Debug)]
+pub struct SimpleError(pub String, pub Option<std::io::Error>);
+
+impl Error for SimpleError {}
+
+impl fmt::Display for SimpleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Check is error provided
+        if self.1.is_some() {
+            write!(f, "{}: {:?}", self.0, self.1)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+// Custom Result with custom Error
+pub type SResult<T> = Result<T, SimpleError>;
+
+/// Error with text which is caused by IO's error
+pub fn io_error(text: &str, err: std::io::Error) -> SimpleError {
+    SimpleError(String::from(text), Some(err))
+}
+
+// Start network's and game's threads with world from directory. Returns the bound address
+pub fn start_server(address: String, directory: &Path) -> std::io::Result<SocketAddr> {
+    let network = spawn_network_server(address)?;
+    spawn_game_thread(network.events, network.outbound, directory.to_path_buf())?;
+    Ok(network.address)
+}
@@ -1,27 +1,17 @@
 #![allow(unused_must_use)]
-use crate::config::{ADDRESS, ADDRESS_PORT};
-use crate::logger::start_input_handler;
-use crate::network::network_server_start;
-use fern::colors::Color;
-use std::error::Error;
+use std::env;
+use std::path::Path;
 use std::process;
-use std::sync::mpsc::channel;
-use std::sync::Arc;
 use std::time::SystemTime;
-use std::{fmt, thread};
-use utils::logger;
+use ule::config::{ADDRESS, ADDRESS_PORT, WORLD_DIR};
+use ule::network::capture;
+use ule::start_server;
+use ule::utils::logger;
+use ule::utils::logger::start_input_handler;
 
-// Use a macros from serde(Serialize and Deserialize), log(Logging) and lazy_static(Global variables)
-#[macro_use]
-extern crate serde;
+// Use a macros from log(Logging)
 #[macro_use]
 extern crate log;
-#[macro_use]
-extern crate lazy_static;
-
-pub mod config;
-pub mod network;
-pub mod utils;
 
 // Main function of application
 fn main() {
@@ -32,28 +22,31 @@ fn main() {
         eprintln!("Failed to initialize logger: {}", err);
         process::exit(1);
     }
-    // Creating channel for multithreading communication with main's thread and network's thread
-    let (tx, rx) = channel::<bool>();
-    // Generate server's address and make it accessible with thread safe
-    let address = Arc::new(String::from(format!(
-        "{}:{}",
-        ADDRESS,
-        ADDRESS_PORT.to_string()
-    )));
-    // Start network in another thread
-    thread::spawn({
-        let address = address.to_string();
-        move || {
-            // Start network
-            // If failed to start when return error
-            if let Err(err) = network_server_start(address, &tx) {
-                error!("{}", err);
-                tx.send(false);
+    // Reading launch's arguments(--capture <file> - recording all connections into the file)
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--capture" => {
+                let path = match args.next() {
+                    Some(v) => v,
+                    None => {
+                        error!("Missing capture's file after --capture");
+                        process::exit(1);
+                    }
+                };
+                if let Err(err) = capture::start_capture(&path) {
+                    error!("Failed to start packet capture into {}: {}", path, err);
+                    process::exit(1);
+                }
+                info!("Capturing packets into {}", path);
             }
+            _ => warn!("Unknown argument: {}", arg),
         }
-    });
-    // Wait for status from server's network
-    if rx.recv().unwrap_or(false) {
+    }
+    // Generate server's address
+    let address = format!("{}:{}", ADDRESS, ADDRESS_PORT);
+    // Start network in another thread and wait for status from server's network
+    if start_server(address.clone(), Path::new(WORLD_DIR)).is_ok() {
         // If Server successful started
         info!("Server started at {}", address);
         // Showing about the full launch and showing the time to start
@@ -69,36 +62,12 @@ fn main() {
                     format!("{}ns", elapsed.as_nanos())
                 }
             );
-            drop(elapsed);
-            drop(start);
         };
     } else {
         // If Failed to start Server
         error!("Failed to start server on {}.", address);
         process::exit(1);
     }
-    // Remove channel
-    std::mem::drop(rx);
     // Start console input handler(input commands)
     start_input_handler();
 }
-
-// Custom error(yes, not std::io:Error)
-#[derive(Debug)]
-pub struct SimpleError(String, Option<std::io::Error>);
-
-impl Error for SimpleError {}
-
-impl fmt::Display for SimpleError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Check is error provided
-        if self.1.is_some() {
-            write!(f, "{}: {:?}", self.0, self.1)
-        } else {
-            write!(f, "{}", self.0)
-        }
-    }
-}
-
-// Custom Result with custom Error
-pub type SResult<T> = Result<T, SimpleError>;
@@ -0,0 +1,224 @@
+use crate::network::proto::registry::Direction;
+use crate::network::proto::{read_frame, read_varint, PacketWriter};
+use std::fs::File;
+use std::io::{self, BufWriter, ErrorKind, Read, Write};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/*
+Capture's file format(all numbers are VarInt/VarLong if not specified):
+  Header: "ULECAP", version(u8), capture's start as unix milliseconds(i64, big-endian)
+  Records: kind(u8), connection's id, time since capture's start in microseconds, data
+    0 - connection opened, data is peer's address as String
+    1 - serverbound frame, data is frame as on wire(length prefix + packet)
+    2 - clientbound frame, data is frame as on wire(length prefix + packet)
+    3 - connection closed, without data
+*/
+
+// Capture file's magic and version
+const MAGIC: &[u8; 6] = b"ULECAP";
+const VERSION: u8 = 1;
+// Record's kinds
+const RECORD_OPEN: u8 = 0;
+const RECORD_SERVERBOUND: u8 = 1;
+const RECORD_CLIENTBOUND: u8 = 2;
+const RECORD_CLOSE: u8 = 3;
+
+// Running capture's writer
+struct CaptureWriter {
+    file: BufWriter<File>,
+    start: Instant,
+    last_flush: Instant,
+}
+
+lazy_static! {
+    // Active capture(None if capturing disabled)
+    static ref CAPTURE: Mutex<Option<CaptureWriter>> = Mutex::new(None);
+}
+
+// Fast check without locking a mutex
+static CAPTURE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Start capturing all connections into file(file will be overwritten)
+pub fn start_capture(path: &str) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    // Writing header
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    file.write_all(MAGIC)?;
+    file.write_all(&[VERSION])?;
+    file.write_all(&started.to_be_bytes())?;
+    file.flush()?;
+    let now = Instant::now();
+    *CAPTURE.lock().unwrap() = Some(CaptureWriter {
+        file,
+        start: now,
+        last_flush: now,
+    });
+    CAPTURE_ENABLED.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Stop capturing and write all buffered records
+pub fn finish_capture() {
+    CAPTURE_ENABLED.store(false, Ordering::Release);
+    if let Some(mut capture) = CAPTURE.lock().unwrap().take() {
+        if let Err(err) = capture.file.flush() {
+            error!("Failed to write packet capture: {}", err);
+        }
+    }
+}
+
+// Writing a record into capture if it's enabled
+fn record(kind: u8, conn: usize, data: &[u8]) {
+    if !CAPTURE_ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+    let mut guard = CAPTURE.lock().unwrap();
+    let capture = match guard.as_mut() {
+        Some(v) => v,
+        None => return,
+    };
+    // Building a record
+    let mut bytes = Vec::with_capacity(data.len() + 12);
+    bytes.write_u8(kind);
+    bytes.write_varlong(conn as i64);
+    bytes.write_varlong(capture.start.elapsed().as_micros() as i64);
+    bytes.extend_from_slice(data);
+    // Flushing on close and at least every second, so capture survives a crash
+    let result = capture.file.write_all(&bytes).and_then(|_| {
+        if kind == RECORD_CLOSE || capture.last_flush.elapsed() >= Duration::from_secs(1) {
+            capture.last_flush = Instant::now();
+            capture.file.flush()
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(err) = result {
+        error!("Failed to write packet capture, capturing stopped: {}", err);
+        CAPTURE_ENABLED.store(false, Ordering::Release);
+        guard.take();
+    }
+}
+
+/// Record a new connection
+pub fn record_open(conn: usize, address: SocketAddr) {
+    let mut data = Vec::new();
+    data.write_string(address.to_string());
+    record(RECORD_OPEN, conn, &data);
+}
+
+/// Record a frame(length prefix + packet)
+pub fn record_frame(conn: usize, direction: Direction, frame: &[u8]) {
+    let kind = match direction {
+        Direction::Serverbound => RECORD_SERVERBOUND,
+        Direction::Clientbound => RECORD_CLIENTBOUND,
+    };
+    record(kind, conn, frame);
+}
+
+/// Record a connection's closing
+pub fn record_close(conn: usize) {
+    record(RECORD_CLOSE, conn, &[]);
+}
+
+/// Captured event
+#[derive(Debug, Clone)]
+pub enum CaptureEvent {
+    Open(String),
+    Frame(Direction, Vec<u8>),
+    Close,
+}
+
+/// Single record from capture's file
+#[derive(Debug, Clone)]
+pub struct CaptureRecord {
+    pub conn: usize,
+    pub time: Duration,
+    pub event: CaptureEvent,
+}
+
+/// Reader of capture's files
+pub struct CaptureReader<R: Read> {
+    reader: R,
+    // Capture's start as unix milliseconds
+    pub started: i64,
+}
+
+// Read a VarLong from a blocking reader
+fn read_varlong<R: Read>(reader: &mut R) -> io::Result<i64> {
+    let mut ans = 0;
+    for i in 0..10 {
+        let mut buf = [0u8; 1];
+        reader.read_exact(&mut buf)?;
+        ans |= ((buf[0] & 0b0111_1111) as i64) << (7 * i);
+        if buf[0] & 0b1000_0000 == 0 {
+            return Ok(ans);
+        }
+    }
+    Err(io::Error::new(ErrorKind::InvalidData, "VarLong is too big"))
+}
+
+impl<R: Read> CaptureReader<R> {
+    // Opening a capture and checking header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; 15];
+        reader.read_exact(&mut header)?;
+        if &header[..6] != MAGIC {
+            return Err(io::Error::new(ErrorKind::InvalidData, "Not a ULE capture"));
+        }
+        if header[6] != VERSION {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported capture's version {}", header[6]),
+            ));
+        }
+        let mut started = [0u8; 8];
+        started.copy_from_slice(&header[7..]);
+        Ok(Self {
+            reader,
+            started: i64::from_be_bytes(started),
+        })
+    }
+
+    // Reading a next record. Returns None at end of capture
+    pub fn next_record(&mut self) -> io::Result<Option<CaptureRecord>> {
+        let mut kind = [0u8; 1];
+        match self.reader.read_exact(&mut kind) {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err),
+        }
+        let conn = read_varlong(&mut self.reader)? as usize;
+        let time = Duration::from_micros(read_varlong(&mut self.reader)? as u64);
+        let event = match kind[0] {
+            RECORD_OPEN => {
+                let len = read_varint(&mut self.reader)?;
+                if len < 0 {
+                    return Err(io::Error::new(ErrorKind::InvalidData, "Invalid address"));
+                }
+                let mut bytes = vec![0; len as usize];
+                self.reader.read_exact(&mut bytes)?;
+                CaptureEvent::Open(String::from_utf8_lossy(&bytes).to_string())
+            }
+            RECORD_SERVERBOUND => {
+                CaptureEvent::Frame(Direction::Serverbound, read_frame(&mut self.reader)?)
+            }
+            RECORD_CLIENTBOUND => {
+                CaptureEvent::Frame(Direction::Clientbound, read_frame(&mut self.reader)?)
+            }
+            RECORD_CLOSE => CaptureEvent::Close,
+            v => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Unknown record's kind {}", v),
+                ))
+            }
+        };
+        Ok(Some(CaptureRecord { conn, time, event }))
+    }
+}
@@ -1,51 +1,81 @@
-use crate::network::network_client::ConnectionType::STATUS;
-use crate::network::network_client::NetworkClient;
+use crate::config::PROTOCOL_VERSION;
+use crate::network::messages::NetEvent;
+use crate::network::network_client::ConnectionType::{HANDSHAKING, LOGIN, PLAY, STATUS};
+use crate::network::network_client::{GameProfile, NetworkClient};
 use crate::network::proto::packets::handshaking::read_handshake_packet;
+use crate::network::proto::packets::login::{
+    create_login_disconnect, create_login_success, read_login_start,
+};
 use crate::network::proto::packets::status::create_server_list_ping_response;
 use crate::network::proto::PacketReader;
+use crate::utils::chat::ChatMessage;
+use crate::utils::uuid::offline_uuid;
 use crate::SResult;
 use mio::event::Event;
-use std::io::Write;
+use std::sync::mpsc::Sender;
 
-// Handshaking connection's stage
-pub fn handshaking(conn: &mut NetworkClient, event: &Event) -> SResult<bool> {
-    // Checking if we can read the package
-    if !event.is_readable() {
-        return Ok(false);
+// Handing connection's event. Returns true if connection needs to be closed
+pub fn handle_event(
+    conn: &mut NetworkClient,
+    event: &Event,
+    events: &Sender<NetEvent>,
+) -> SResult<bool> {
+    if event.is_readable() && !conn.closing {
+        // Reading all input bytes
+        let opened = conn.read()?;
+        // Handing all received packets
+        while let Some(frame) = conn.next_frame()? {
+            let m = match &conn.conn_type {
+                HANDSHAKING => handshaking,
+                STATUS => status_handler,
+                LOGIN => login_handler,
+                PLAY => play_handler,
+            };
+            if m(conn, frame, events)? {
+                return Ok(true);
+            }
+        }
+        if !opened {
+            return Ok(true);
+        }
+    }
+    if event.is_writable() {
+        // Sending a waiting bytes
+        conn.flush()?;
     }
+    // Connection closes after sending all bytes
+    Ok(conn.closing && conn.is_flushed())
+}
+
+// Handshaking connection's stage
+pub fn handshaking(
+    conn: &mut NetworkClient,
+    mut p: Vec<u8>,
+    _: &Sender<NetEvent>,
+) -> SResult<bool> {
     // Reading packet
-    let handshake = read_handshake_packet(conn);
+    let handshake = read_handshake_packet(&mut p);
     // Checking if is error
     if handshake.is_err() {
         return Ok(true);
     }
     // Getting results
-    let (_, _, _, next_state) = handshake.unwrap();
+    let (version, _, _, next_state) = handshake.unwrap();
+    conn.protocol = version;
     // Change types
     conn.conn_type = match next_state {
         1 => STATUS,
-        _ => STATUS,
+        _ => LOGIN,
     };
     Ok(false)
 }
 
 // Status connection's stage
-pub fn status_handler(conn: &mut NetworkClient, event: &Event) -> SResult<bool> {
-    // Checking if we can read and write
-    if !event.is_readable() || !event.is_writable() {
-        return Ok(false);
-    }
-    // Getting a input's bytes
-    let (ok, p, err) = match conn.read() {
-        Ok((ok, p)) => (ok, Some(p), None),
-        Err(err) => (false, None, Some(err)),
-    };
-    // Checking if a read or not
-    if !ok {
-        return Ok(err.is_some());
-    }
-    // Packet's bytes
-    let mut p: Vec<u8> = p.unwrap();
+pub fn status_handler(
+    conn: &mut NetworkClient,
+    mut p: Vec<u8>,
+    _: &Sender<NetEvent>,
+) -> SResult<bool> {
     // Cloning bytes(for ping-pong)
     let bytes = p.clone();
     // Reading a packet's length(and remove...) and PacketID
@@ -54,11 +84,11 @@ pub fn status_handler(conn: &mut NetworkClient, event: &Event) -> SResult<bool>
         // Is Ping List
         0x00 => {
             drop(bytes);
-            conn.stream.write_all(&*create_server_list_ping_response());
+            conn.send(&create_server_list_ping_response())?;
         }
         // Is Ping-Pong
         0x01 => {
-            conn.stream.write_all(bytes.as_slice());
+            conn.send(bytes.as_slice())?;
             match conn.stream.peer_addr() {
                 Ok(v) => info!("Server pinged from {}", v),
                 Err(_) => {
@@ -70,3 +100,62 @@ pub fn status_handler(conn: &mut NetworkClient, event: &Event) -> SResult<bool>
     }
     Ok(false)
 }
+
+// Login connection's stage(offline mode, without encryption and compression)
+pub fn login_handler(
+    conn: &mut NetworkClient,
+    mut p: Vec<u8>,
+    events: &Sender<NetEvent>,
+) -> SResult<bool> {
+    let (_, pid) = p.read_base()?;
+    // Only Login Start is expected
+    if pid != 0x00 {
+        return Ok(true);
+    }
+    // Checking client's version
+    if conn.protocol != PROTOCOL_VERSION {
+        conn.send(&create_login_disconnect(&ChatMessage::str(
+            if conn.protocol < PROTOCOL_VERSION {
+                "Outdated client! Please use 1.12.2"
+            } else {
+                "Outdated server! I'm still on 1.12.2"
+            },
+        )))?;
+        return Ok(true);
+    }
+    let name = match read_login_start(&mut p) {
+        Ok(v) => v,
+        Err(err) => {
+            conn.send(&create_login_disconnect(&ChatMessage::str(&err.0)))?;
+            return Ok(true);
+        }
+    };
+    let profile = GameProfile {
+        uuid: offline_uuid(&name),
+        name,
+    };
+    conn.send(&create_login_success(profile.uuid, &profile.name))?;
+    conn.profile = Some(profile.clone());
+    conn.conn_type = PLAY;
+    // Game's thread continues with the player
+    events.send(NetEvent::Joined {
+        conn: conn.id,
+        profile,
+    });
+    Ok(false)
+}
+
+// Play connection's stage
+pub fn play_handler(
+    conn: &mut NetworkClient,
+    p: Vec<u8>,
+    events: &Sender<NetEvent>,
+) -> SResult<bool> {
+    // Packets on play stage are handled by game's thread
+    Ok(events
+        .send(NetEvent::Packet {
+            conn: conn.id,
+            frame: p,
+        })
+        .is_err())
+}
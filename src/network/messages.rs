@@ -0,0 +1,102 @@
+use crate::network::network_client::GameProfile;
+use mio::Waker;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Event from network's workers to game's thread
+#[derive(Debug)]
+pub enum NetEvent {
+    // Player logged in and now on play stage
+    Joined { conn: usize, profile: GameProfile },
+    // Frame(length prefix + packet) received on play stage
+    Packet { conn: usize, frame: Vec<u8> },
+    // Player's connection closed
+    Left { conn: usize },
+}
+
+/// Command from game's thread to network's worker
+#[derive(Debug)]
+pub enum NetCommand {
+    // Sending a frame(length prefix + packet)
+    Send(usize, Vec<u8>),
+    // Sending all waiting frames and closing the connection
+    Disconnect(usize),
+}
+
+// Single worker's command channel
+#[derive(Clone)]
+struct WorkerHandle {
+    commands: Sender<NetCommand>,
+    waker: Arc<Waker>,
+    // Worker has commands which aren't flushed
+    pending: Arc<AtomicBool>,
+}
+
+/// Sender of commands to connections(routes to worker which owns the connection).
+/// Commands are queued until [Outbound::flush]
+#[derive(Clone)]
+pub struct Outbound {
+    workers: Vec<WorkerHandle>,
+}
+
+impl Outbound {
+    pub(crate) fn new() -> Self {
+        Self {
+            workers: Vec::new(),
+        }
+    }
+
+    // Adding a worker(connection goes to worker by its id)
+    pub(crate) fn add_worker(&mut self, commands: Sender<NetCommand>, waker: Arc<Waker>) {
+        self.workers.push(WorkerHandle {
+            commands,
+            waker,
+            pending: Arc::new(AtomicBool::new(false)),
+        });
+    }
+
+    // Worker's index for connection
+    pub(crate) fn worker_of(&self, conn: usize) -> usize {
+        conn % self.workers.len()
+    }
+
+    // Queueing a command to connection's worker
+    fn command(&self, conn: usize, command: NetCommand) {
+        let worker = &self.workers[self.worker_of(conn)];
+        if worker.commands.send(command).is_ok() {
+            worker.pending.store(true, Ordering::Release);
+        }
+    }
+
+    /// Waking workers which have queued commands(once per tick)
+    pub fn flush(&self) {
+        for worker in &self.workers {
+            if worker.pending.swap(false, Ordering::AcqRel) {
+                worker.waker.wake();
+            }
+        }
+    }
+
+    /// Sending a frame(length prefix + packet) to connection
+    pub fn send(&self, conn: usize, frame: Vec<u8>) {
+        self.command(conn, NetCommand::Send(conn, frame));
+    }
+
+    /// Closing a connection after sending all waiting frames
+    pub fn disconnect(&self, conn: usize) {
+        self.command(conn, NetCommand::Disconnect(conn));
+    }
+
+    // Waking connection's worker
+    pub(crate) fn wake(&self, conn: usize) {
+        self.workers[self.worker_of(conn)].waker.wake();
+    }
+
+    // Waking all workers(for shutdown)
+    pub(crate) fn wake_all(&self) {
+        for worker in &self.workers {
+            worker.waker.wake();
+        }
+    }
+}
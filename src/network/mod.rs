@@ -1,7 +1,12 @@
+pub mod capture;
 mod handler;
+pub mod messages;
 mod network_client;
 pub mod proto;
 mod server;
+mod worker;
 
-pub use server::network_server_start;
+pub use messages::{NetEvent, Outbound};
+pub use network_client::{ConnectionType, GameProfile};
+pub use server::{network_server_start, spawn_network_server, NetworkServer};
 pub use server::{NET_SERVER_WORKS, SHUTDOWN_SERVER};
@@ -1,44 +1,131 @@
+use crate::network::capture;
+use crate::network::proto::frame_size;
+use crate::network::proto::registry::Direction;
 use crate::{SResult, SimpleError};
 use mio::net::TcpStream;
-use std::io::{ErrorKind, Read};
+use std::io::{ErrorKind, Read, Write};
 
 // Connection's types
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConnectionType {
     HANDSHAKING,
     STATUS,
+    LOGIN,
+    PLAY,
+}
+
+// Logged in player's profile
+#[derive(Debug, Clone)]
+pub struct GameProfile {
+    pub name: String,
+    pub uuid: u128,
 }
 
 // Network-base client
 pub struct NetworkClient {
+    // Connection's unique id(same as token)
+    pub id: usize,
     pub stream: TcpStream,
     pub conn_type: ConnectionType,
+    // Client's protocol version from handshake
+    pub protocol: u32,
+    // Player's profile(after login)
+    pub profile: Option<GameProfile>,
+    // Connection will be closed after sending all waiting bytes
+    pub closing: bool,
+    // Received, but not handled bytes
+    input: Vec<u8>,
+    // Bytes waiting for socket to be writable
+    output: Vec<u8>,
 }
 
 // Declare functions
 impl NetworkClient {
-    // Function for reading input bytes
-    pub fn read(&mut self) -> SResult<(bool, Vec<u8>)> {
+    // Creating a client on handshaking stage
+    pub fn new(id: usize, stream: TcpStream) -> Self {
+        Self {
+            id,
+            stream,
+            conn_type: ConnectionType::HANDSHAKING,
+            protocol: 0,
+            profile: None,
+            closing: false,
+            input: Vec::new(),
+            output: Vec::new(),
+        }
+    }
+
+    // Function for reading all input bytes into buffer. Returns false if connection closed
+    pub fn read(&mut self) -> SResult<bool> {
         // Creating a buffer up to 4KB information
-        let mut bytes = vec![0; 4096];
-        // Reading a bytes
-        let (ok, err) = match self.stream.read(&mut bytes) {
-            // Connection closed. Why exists?
-            Ok(0) => (false, None),
-            // Getting byte's length and resize buffer
-            Ok(n) => {
-                bytes.resize(n, 0);
-                (true, None)
+        let mut bytes = [0; 4096];
+        loop {
+            // Reading a bytes
+            match self.stream.read(&mut bytes) {
+                // Connection closed
+                Ok(0) => return Ok(false),
+                // Pushing read bytes into buffer
+                Ok(n) => self.input.extend_from_slice(&bytes[..n]),
+                // Connection don't has a input bytes now
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => return Ok(true),
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                // Failed to read bytes
+                Err(err) => {
+                    return Err(SimpleError(
+                        String::from("Failed to read packet"),
+                        Some(err),
+                    ))
+                }
             }
-            // Connection don't has a input bytes
-            Err(ref err) if err.kind() == ErrorKind::WouldBlock => (false, None),
-            // Failed to read bytes
-            Err(err) => (false, Some(err)),
+        }
+    }
+
+    // Taking a next fully received frame(length prefix + packet) from buffer
+    pub fn next_frame(&mut self) -> SResult<Option<Vec<u8>>> {
+        let size = match frame_size(&self.input) {
+            Ok(Some(v)) => v,
+            Ok(None) => return Ok(None),
+            Err(err) => return Err(SimpleError(String::from("Invalid frame"), Some(err))),
         };
-        // If Error exists when returning a error
-        if err.is_some() {
-            return Err(SimpleError(String::from("Failed to read packet"), err));
+        let frame: Vec<u8> = self.input.drain(..size).collect();
+        capture::record_frame(self.id, Direction::Serverbound, &frame);
+        Ok(Some(frame))
+    }
+
+    // Sending a frame to client(buffered if socket isn't writable now)
+    pub fn send(&mut self, frame: &[u8]) -> SResult<()> {
+        capture::record_frame(self.id, Direction::Clientbound, frame);
+        self.output.extend_from_slice(frame);
+        self.flush()
+    }
+
+    // All bytes are sent
+    pub fn is_flushed(&self) -> bool {
+        self.output.is_empty()
+    }
+
+    // Writing a buffered bytes to socket
+    pub fn flush(&mut self) -> SResult<()> {
+        while !self.output.is_empty() {
+            match self.stream.write(&self.output) {
+                Ok(0) => {
+                    return Err(SimpleError(String::from("Connection closed"), None));
+                }
+                Ok(n) => {
+                    self.output.drain(..n);
+                }
+                // Waiting for writable event
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    return Err(SimpleError(
+                        String::from("Failed to write packet"),
+                        Some(err),
+                    ))
+                }
+            }
         }
-        // Returning a result of reading
-        Ok((ok, bytes))
+        Ok(())
     }
 }
@@ -0,0 +1,77 @@
+use crate::network::proto::PacketWriter;
+use std::io::{self, ErrorKind, Read};
+
+// Max packet's length allowed by the protocol(2^21 - 1)
+pub const MAX_FRAME_LENGTH: usize = 2_097_151;
+
+/// Peek a VarInt from the start of slice. Returns value and count of used bytes,
+/// or [None] if the slice ends before the VarInt.
+pub fn peek_varint(bytes: &[u8]) -> io::Result<Option<(i32, usize)>> {
+    let mut ans = 0;
+    for i in 0..5 {
+        // Not enough bytes yet
+        let buf = match bytes.get(i) {
+            Some(v) => *v,
+            None => return Ok(None),
+        };
+        ans |= ((buf & 0b0111_1111) as i32) << (7 * i);
+        if buf & 0b1000_0000 == 0 {
+            return Ok(Some((ans, i + 1)));
+        }
+    }
+    Err(io::Error::new(ErrorKind::InvalidData, "VarInt is too big"))
+}
+
+/// Getting a full frame's size(length prefix + packet) from start of buffer.
+/// Returns [None] if frame isn't fully received.
+pub fn frame_size(bytes: &[u8]) -> io::Result<Option<usize>> {
+    let (len, used) = match peek_varint(bytes)? {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    // Checking length
+    if len < 0 || len as usize > MAX_FRAME_LENGTH {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "Invalid packet's length",
+        ));
+    }
+    let size = used + len as usize;
+    Ok(if bytes.len() >= size {
+        Some(size)
+    } else {
+        None
+    })
+}
+
+/// Read a VarInt from a blocking reader
+pub fn read_varint<R: Read>(reader: &mut R) -> io::Result<i32> {
+    let mut ans = 0;
+    for i in 0..5 {
+        let mut buf = [0u8; 1];
+        reader.read_exact(&mut buf)?;
+        ans |= ((buf[0] & 0b0111_1111) as i32) << (7 * i);
+        if buf[0] & 0b1000_0000 == 0 {
+            return Ok(ans);
+        }
+    }
+    Err(io::Error::new(ErrorKind::InvalidData, "VarInt is too big"))
+}
+
+/// Read a full frame(length prefix + packet) from a blocking reader
+pub fn read_frame<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let len = read_varint(reader)?;
+    if len < 0 || len as usize > MAX_FRAME_LENGTH {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "Invalid packet's length",
+        ));
+    }
+    // Restoring the length prefix before the packet
+    let mut frame = Vec::with_capacity(len as usize + 5);
+    frame.write_varint(len);
+    let start = frame.len();
+    frame.resize(start + len as usize, 0);
+    reader.read_exact(&mut frame[start..])?;
+    Ok(frame)
+}
@@ -1,6 +1,9 @@
+mod frame;
 mod packet_read;
 mod packet_write;
 pub mod packets;
+pub mod registry;
 
+pub use frame::{frame_size, peek_varint, read_frame, read_varint, MAX_FRAME_LENGTH};
 pub use packet_read::PacketReader;
 pub use packet_write::PacketWriter;
@@ -1,94 +1,197 @@
+use crate::utils::nbt::{read_nbt, Compound};
+use crate::world::item::ItemStack;
 use crate::{SResult, SimpleError};
 
 /// Reader [Vec] of bytes
 pub trait PacketReader {
     // 1-Byte
-    fn get_u8(&mut self) -> u8;
-    fn get_i8(&mut self) -> i8;
+    fn get_u8(&mut self) -> SResult<u8>;
+    fn get_i8(&mut self) -> SResult<i8>;
+    fn get_bool(&mut self) -> SResult<bool>;
     // 2-Byte
-    fn get_u16(&mut self) -> u16;
-    fn get_i16(&mut self) -> i16;
+    fn get_u16(&mut self) -> SResult<u16>;
+    fn get_i16(&mut self) -> SResult<i16>;
     // 4-Byte
     fn get_varint(&mut self) -> SResult<i32>;
+    fn get_i32(&mut self) -> SResult<i32>;
+    fn get_f32(&mut self) -> SResult<f32>;
     // 8-Byte
-    fn get_i64(&mut self) -> i64;
+    fn get_varlong(&mut self) -> SResult<i64>;
+    fn get_i64(&mut self) -> SResult<i64>;
+    fn get_f64(&mut self) -> SResult<f64>;
+    // 16-Byte
+    fn get_u128(&mut self) -> SResult<u128>;
+    fn get_position(&mut self) -> SResult<(i32, i32, i32)>;
     // Another
     fn get_string(&mut self) -> SResult<String>;
+    fn get_nbt(&mut self) -> SResult<Option<Compound>>;
+    fn get_slot(&mut self) -> SResult<Option<ItemStack>>;
     fn read_base(&mut self) -> SResult<(i32, i32)>;
 }
 
+// Taking N first bytes(error if packet is shorter)
+fn take_bytes<const N: usize>(p: &mut Vec<u8>) -> SResult<[u8; N]> {
+    if p.len() < N {
+        return Err(SimpleError(String::from("Packet ended unexpectedly"), None));
+    }
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(&p[..N]);
+    p.drain(..N);
+    Ok(bytes)
+}
+
 // Apply reader to Vec
 impl PacketReader for Vec<u8> {
     // Read a single byte as u8 ( 8-Bit Unsigned Integer )
-    fn get_u8(&mut self) -> u8 {
-        self.remove(0)
+    fn get_u8(&mut self) -> SResult<u8> {
+        Ok(take_bytes::<1>(self)?[0])
     }
 
     // Read a single byte as i8 ( 8-Bit Integer )
-    fn get_i8(&mut self) -> i8 {
-        self.remove(1) as i8
+    fn get_i8(&mut self) -> SResult<i8> {
+        Ok(self.get_u8()? as i8)
+    }
+
+    // Read a single byte as bool ( 0x00 - false, another - true )
+    fn get_bool(&mut self) -> SResult<bool> {
+        Ok(self.get_u8()? != 0)
     }
 
     // Read a two bytes as u16 ( 16-Bit Unsigned Integer )
-    fn get_u16(&mut self) -> u16 {
-        u16::from_be_bytes([self.get_u8(), self.get_u8()])
+    fn get_u16(&mut self) -> SResult<u16> {
+        Ok(u16::from_be_bytes(take_bytes(self)?))
     }
 
     // Read a two bytes as i16 ( 16-Bit Integer )
-    fn get_i16(&mut self) -> i16 {
-        i16::from_be_bytes([self.get_u8(), self.get_u8()])
+    fn get_i16(&mut self) -> SResult<i16> {
+        Ok(i16::from_be_bytes(take_bytes(self)?))
     }
 
     // Read a VarInt ( Dynamic-length 32-Bit Integer )
     fn get_varint(&mut self) -> SResult<i32> {
         // Result variable
         let mut ans = 0;
-        // Read up to 4 bytes
-        for i in 0..4 {
+        // Read up to 5 bytes
+        for i in 0..5 {
+            // Packet ended before the VarInt
+            if self.is_empty() {
+                return Err(SimpleError(String::from("VarInt is not completed"), None));
+            }
+            // Read one byte
+            let buf = self.get_u8()?;
+            // Calculate res with bit moving and another
+            ans |= ((buf & 0b0111_1111) as i32) << (7 * i);
+            // If it's limit when stop reading
+            if buf & 0b1000_0000 == 0 {
+                return Ok(ans);
+            }
+        }
+        // VarInt can't be longer than 5 bytes
+        Err(SimpleError(String::from("VarInt is too big"), None))
+    }
+
+    // Read a four bytes as i32 ( 32-Bit Integer )
+    fn get_i32(&mut self) -> SResult<i32> {
+        Ok(i32::from_be_bytes(take_bytes(self)?))
+    }
+
+    // Read a four bytes as f32 ( 32-Bit Float )
+    fn get_f32(&mut self) -> SResult<f32> {
+        Ok(f32::from_be_bytes(take_bytes(self)?))
+    }
+
+    // Read a VarLong ( Dynamic-length 64-Bit Integer )
+    fn get_varlong(&mut self) -> SResult<i64> {
+        // Result variable
+        let mut ans = 0;
+        // Read up to 10 bytes
+        for i in 0..10 {
+            // Packet ended before the VarLong
+            if self.is_empty() {
+                return Err(SimpleError(String::from("VarLong is not completed"), None));
+            }
             // Read one byte
-            let buf = self.get_u8();
+            let buf = self.get_u8()?;
             // Calculate res with bit moving and another
-            ans |= ((buf & 0b0111_1111) as i32) << 7 * i;
+            ans |= ((buf & 0b0111_1111) as i64) << (7 * i);
             // If it's limit when stop reading
             if buf & 0b1000_0000 == 0 {
-                break;
+                return Ok(ans);
             }
         }
-        // Return result as successful
-        Ok(ans)
+        // VarLong can't be longer than 10 bytes
+        Err(SimpleError(String::from("VarLong is too big"), None))
     }
 
     // Read a Long ( 64-Bit Integer )
-    fn get_i64(&mut self) -> i64 {
-        // Yes, read 8 bytes
-        i64::from_be_bytes([
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-            self.get_u8(),
-        ])
+    fn get_i64(&mut self) -> SResult<i64> {
+        Ok(i64::from_be_bytes(take_bytes(self)?))
+    }
+
+    // Read a Double ( 64-Bit Float )
+    fn get_f64(&mut self) -> SResult<f64> {
+        Ok(f64::from_be_bytes(take_bytes(self)?))
+    }
+
+    // Read a UUID ( 128-Bit Unsigned Integer )
+    fn get_u128(&mut self) -> SResult<u128> {
+        Ok(u128::from_be_bytes(take_bytes(self)?))
+    }
+
+    // Read a Position ( x as 26 bits, y as 12 bits, z as 26 bits in Long )
+    fn get_position(&mut self) -> SResult<(i32, i32, i32)> {
+        let value = self.get_i64()?;
+        // Moving bits to the left and back for sign
+        Ok((
+            (value >> 38) as i32,
+            ((value << 26) >> 52) as i32,
+            ((value << 38) >> 38) as i32,
+        ))
     }
 
     // Read a String ( VarInt as len; bytes[::len] )
     fn get_string(&mut self) -> SResult<String> {
         // Getting string-length
         let len = self.get_varint()?;
-        // Create String's bytes buffer
-        let mut buf = Vec::new();
-        // Reading Bytes
-        for _ in 0..len {
-            buf.push(self.get_u8())
+        // Checking string-length before reading
+        if len < 0 || len as usize > self.len() {
+            return Err(SimpleError(String::from("Invalid string's length"), None));
         }
+        // Reading String's bytes
+        let buf: Vec<u8> = self.drain(..len as usize).collect();
         // Convert Bytes to UTF8 String
         match String::from_utf8(buf) {
             Ok(v) => Ok(v),
             Err(_) => Err(SimpleError(String::from("Failed to parse chars"), None)),
         }
     }
+
+    // Read a NBT ( root compound or TAG_End if it's empty )
+    fn get_nbt(&mut self) -> SResult<Option<Compound>> {
+        Ok(read_nbt(self)?.map(|v| v.1))
+    }
+
+    // Read a Slot ( Short as item's id(-1 - empty), Byte as count, Short as damage and NBT )
+    fn get_slot(&mut self) -> SResult<Option<ItemStack>> {
+        let id = self.get_i16()?;
+        if id < 0 {
+            return Ok(None);
+        }
+        let count = self.get_i8()?;
+        let damage = self.get_i16()?;
+        let nbt = self.get_nbt()?;
+        // Stack without items is an empty slot
+        if count <= 0 {
+            return Ok(None);
+        }
+        Ok(Some(ItemStack {
+            id: id as u16,
+            count: count as u8,
+            damage,
+            nbt,
+        }))
+    }
+
     // Read first two VarInt(Packet's length and id)
     fn read_base(&mut self) -> SResult<(i32, i32)> {
         let len = self.get_varint()?;
@@ -1,18 +1,30 @@
+use crate::utils::nbt::{write_nbt, Compound, TAG_END};
+use crate::world::item::ItemStack;
 /// Writer [Vec] of bytes
 pub trait PacketWriter {
     // 1-Byte
     fn write_u8(&mut self, value: u8);
     fn write_i8(&mut self, value: i8);
+    fn write_bool(&mut self, value: bool);
     // 2-Byte
     fn write_u16(&mut self, value: u16);
     fn write_i16(&mut self, value: i16);
     // 4-Byte
     fn write_varint(&mut self, value: i32);
+    fn write_i32(&mut self, value: i32);
+    fn write_f32(&mut self, value: f32);
     // 8-Byte
+    fn write_varlong(&mut self, value: i64);
     fn write_i64(&mut self, value: i64);
+    fn write_f64(&mut self, value: f64);
+    // 16-Byte
+    fn write_u128(&mut self, value: u128);
+    fn write_position(&mut self, x: i32, y: i32, z: i32);
     // Another
     fn write_vec_bytes(&mut self, bytes: Vec<u8>);
     fn write_string(&mut self, value: String);
+    fn write_nbt(&mut self, value: Option<&Compound>);
+    fn write_slot(&mut self, value: Option<&ItemStack>);
     fn create_packet(&mut self, pid: i32) -> Vec<u8>;
 }
 
@@ -27,6 +39,11 @@ impl PacketWriter for Vec<u8> {
         self.push(value as u8)
     }
 
+    // Writing bool as byte
+    fn write_bool(&mut self, value: bool) {
+        self.push(value as u8)
+    }
+
     // Writing 2-byte unsigned integer
     fn write_u16(&mut self, value: u16) {
         self.extend_from_slice(&value.to_be_bytes());
@@ -38,29 +55,49 @@ impl PacketWriter for Vec<u8> {
     }
 
     // Writing bytes as VarInt
-    fn write_varint(&mut self, mut value: i32) {
-        // Bytes buffer
-        let mut buf = vec![0u8; 1];
-        // Byte's length
-        let mut n = 0;
-        // Converts value to bytes
+    fn write_varint(&mut self, value: i32) {
+        // Negative numbers are written as unsigned(always 5 bytes)
+        let mut value = value as u32;
+        // Converts value to bytes by 7 bits
         loop {
-            // Break if it's limit
-            if value <= 127 || n >= 8 {
+            // Last byte hasn't continuation bit
+            if value & !0x7F == 0 {
+                self.push(value as u8);
                 break;
             }
-            // Pushing a byte to buffer
-            buf.insert(n, (0x80 | (value & 0x7F)) as u8);
+            // Pushing a byte with continuation bit
+            self.push((value & 0x7F | 0x80) as u8);
+            // Moving value's bits on 7
+            value >>= 7;
+        }
+    }
+
+    // Writing 4-byte integer
+    fn write_i32(&mut self, value: i32) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+
+    // Writing 4-byte float
+    fn write_f32(&mut self, value: f32) {
+        self.extend_from_slice(&value.to_be_bytes());
+    }
+
+    // Writing bytes as VarLong
+    fn write_varlong(&mut self, value: i64) {
+        // Negative numbers are written as unsigned(always 10 bytes)
+        let mut value = value as u64;
+        // Converts value to bytes by 7 bits
+        loop {
+            // Last byte hasn't continuation bit
+            if value & !0x7F == 0 {
+                self.push(value as u8);
+                break;
+            }
+            // Pushing a byte with continuation bit
+            self.push((value & 0x7F | 0x80) as u8);
             // Moving value's bits on 7
             value >>= 7;
-            value -= 1;
-            n += 1;
         }
-        // Pushing byte, because it lower that 256(<256)
-        buf.insert(n, value as u8);
-        n += 1;
-        // Pushing converted bytes into byte's buffer
-        self.extend_from_slice(&buf.as_slice()[..n])
     }
 
     // Writing Long ( 64-Bit Integer )
@@ -68,6 +105,23 @@ impl PacketWriter for Vec<u8> {
         self.extend_from_slice(value.to_be_bytes().as_slice())
     }
 
+    // Writing Double ( 64-Bit Float )
+    fn write_f64(&mut self, value: f64) {
+        self.extend_from_slice(value.to_be_bytes().as_slice())
+    }
+
+    // Writing UUID ( 128-Bit Unsigned Integer )
+    fn write_u128(&mut self, value: u128) {
+        self.extend_from_slice(&value.to_be_bytes())
+    }
+
+    // Writing Position ( x as 26 bits, y as 12 bits, z as 26 bits in Long )
+    fn write_position(&mut self, x: i32, y: i32, z: i32) {
+        self.write_i64(
+            ((x as i64 & 0x3FF_FFFF) << 38) | ((y as i64 & 0xFFF) << 26) | (z as i64 & 0x3FF_FFFF),
+        )
+    }
+
     // Alias of extend_from_slice, but works with Vec, not Slice
     fn write_vec_bytes(&mut self, mut bytes: Vec<u8>) {
         self.append(&mut bytes);
@@ -83,6 +137,27 @@ impl PacketWriter for Vec<u8> {
         self.extend_from_slice(bytes);
     }
 
+    // Write NBT ( root compound without name or TAG_End if it's empty )
+    fn write_nbt(&mut self, value: Option<&Compound>) {
+        match value {
+            Some(v) => write_nbt(self, "", v),
+            None => self.write_u8(TAG_END),
+        }
+    }
+
+    // Writing Slot ( Short as item's id(-1 - empty), Byte as count, Short as damage and NBT )
+    fn write_slot(&mut self, value: Option<&ItemStack>) {
+        match value {
+            Some(v) => {
+                self.write_i16(v.id as i16);
+                self.write_i8(v.count as i8);
+                self.write_i16(v.damage);
+                self.write_nbt(v.nbt.as_ref());
+            }
+            None => self.write_i16(-1),
+        }
+    }
+
     // Packet's base builder
     fn create_packet(&mut self, pid: i32) -> Vec<u8> {
         // Creating empty packet's buffer
@@ -0,0 +1,35 @@
+use crate::network::proto::{PacketReader, PacketWriter};
+use crate::utils::chat::ChatMessage;
+use crate::utils::uuid::uuid_to_string;
+use crate::{SResult, SimpleError};
+
+/// Trying to read [login start](https://wiki.vg/index.php?title=Protocol&oldid=14204#Login_Start) packet's content
+pub fn read_login_start(p: &mut Vec<u8>) -> SResult<String> {
+    let name = p.get_string()?;
+    // Player's name can be only 1-16 chars(latin letters, digits and underscore)
+    if name.is_empty()
+        || name.len() > 16
+        || !name.chars().all(|v| v.is_ascii_alphanumeric() || v == '_')
+    {
+        return Err(SimpleError(
+            format!("Invalid player's name: {:?}", name),
+            None,
+        ));
+    }
+    Ok(name)
+}
+
+/// Build [login success](https://wiki.vg/index.php?title=Protocol&oldid=14204#Login_Success) packet
+pub fn create_login_success(uuid: u128, name: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(uuid_to_string(uuid));
+    bytes.write_string(name.to_string());
+    bytes.create_packet(0x02)
+}
+
+/// Build [disconnect](https://wiki.vg/index.php?title=Protocol&oldid=14204#Disconnect_.28login.29) packet on login stage
+pub fn create_login_disconnect(reason: &ChatMessage) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(serde_json::to_string(reason).unwrap());
+    bytes.create_packet(0x00)
+}
@@ -1,3 +1,5 @@
 // Publish packets
 pub mod handshaking;
+pub mod login;
+pub mod play;
 pub mod status;
@@ -0,0 +1,644 @@
+use crate::network::proto::{PacketReader, PacketWriter};
+use crate::utils::chat::ChatMessage;
+use crate::world::item::ItemStack;
+use crate::world::{ChunkColumn, ChunkSection};
+use crate::SResult;
+
+/// Build [join game](https://wiki.vg/index.php?title=Protocol&oldid=14204#Join_Game) packet
+pub fn create_join_game(
+    entity_id: i32,
+    gamemode: u8,
+    dimension: i32,
+    difficulty: u8,
+    max_players: u8,
+    level_type: &str,
+    reduced_debug_info: bool,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i32(entity_id);
+    bytes.write_u8(gamemode);
+    bytes.write_i32(dimension);
+    bytes.write_u8(difficulty);
+    bytes.write_u8(max_players);
+    bytes.write_string(level_type.to_string());
+    bytes.write_bool(reduced_debug_info);
+    bytes.create_packet(0x23)
+}
+
+/// Build [plugin message](https://wiki.vg/index.php?title=Protocol&oldid=14204#Plugin_Message_.28clientbound.29) packet
+pub fn create_plugin_message(channel: &str, data: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(channel.to_string());
+    bytes.write_vec_bytes(data);
+    bytes.create_packet(0x18)
+}
+
+/// Build server's brand plugin message(shown on client's debug screen)
+pub fn create_brand(brand: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.write_string(brand.to_string());
+    create_plugin_message("MC|Brand", data)
+}
+
+/// Build [server difficulty](https://wiki.vg/index.php?title=Protocol&oldid=14204#Server_Difficulty) packet
+pub fn create_server_difficulty(difficulty: u8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(difficulty);
+    bytes.create_packet(0x0D)
+}
+
+/// Build [spawn position](https://wiki.vg/index.php?title=Protocol&oldid=14204#Spawn_Position) packet
+pub fn create_spawn_position(x: i32, y: i32, z: i32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_position(x, y, z);
+    bytes.create_packet(0x46)
+}
+
+/// Build [time update](https://wiki.vg/index.php?title=Protocol&oldid=14204#Time_Update) packet.
+/// Negative time of day stops day's cycle on client
+pub fn create_time_update(world_age: i64, time_of_day: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i64(world_age);
+    bytes.write_i64(time_of_day);
+    bytes.create_packet(0x47)
+}
+
+/// Build [player abilities](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_Abilities_.28clientbound.29) packet
+pub fn create_player_abilities(flags: u8, flying_speed: f32, fov_modifier: f32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(flags);
+    bytes.write_f32(flying_speed);
+    bytes.write_f32(fov_modifier);
+    bytes.create_packet(0x2C)
+}
+
+/// Build [player position and look](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_Position_And_Look_.28clientbound.29) packet
+pub fn create_player_position_look(
+    (x, y, z): (f64, f64, f64),
+    yaw: f32,
+    pitch: f32,
+    flags: u8,
+    teleport_id: i32,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_f64(x);
+    bytes.write_f64(y);
+    bytes.write_f64(z);
+    bytes.write_f32(yaw);
+    bytes.write_f32(pitch);
+    bytes.write_u8(flags);
+    bytes.write_varint(teleport_id);
+    bytes.create_packet(0x2F)
+}
+
+/// Build [update health](https://wiki.vg/index.php?title=Protocol&oldid=14204#Update_Health) packet
+pub fn create_update_health(health: f32, food: i32, saturation: f32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_f32(health);
+    bytes.write_varint(food);
+    bytes.write_f32(saturation);
+    bytes.create_packet(0x41)
+}
+
+/// Build [set experience](https://wiki.vg/index.php?title=Protocol&oldid=14204#Set_Experience) packet
+pub fn create_set_experience(progress: f32, level: i32, total: i32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_f32(progress);
+    bytes.write_varint(level);
+    bytes.write_varint(total);
+    bytes.create_packet(0x40)
+}
+
+/// Build [respawn](https://wiki.vg/index.php?title=Protocol&oldid=14204#Respawn) packet
+pub fn create_respawn(dimension: i32, difficulty: u8, gamemode: u8, level_type: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i32(dimension);
+    bytes.write_u8(difficulty);
+    bytes.write_u8(gamemode);
+    bytes.write_string(level_type.to_string());
+    bytes.create_packet(0x35)
+}
+
+/// Trying to read [client status](https://wiki.vg/index.php?title=Protocol&oldid=14204#Client_Status) packet's content(0 - respawn, 1 - statistics)
+pub fn read_client_status(p: &mut Vec<u8>) -> SResult<i32> {
+    p.get_varint()
+}
+
+/// Trying to read [teleport confirm](https://wiki.vg/index.php?title=Protocol&oldid=14204#Teleport_Confirm) packet's content
+pub fn read_teleport_confirm(p: &mut Vec<u8>) -> SResult<i32> {
+    p.get_varint()
+}
+
+/// Build [keep alive](https://wiki.vg/index.php?title=Protocol&oldid=14204#Keep_Alive_.28clientbound.29) packet
+pub fn create_keep_alive(id: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i64(id);
+    bytes.create_packet(0x1F)
+}
+
+/// Trying to read [keep alive](https://wiki.vg/index.php?title=Protocol&oldid=14204#Keep_Alive_.28serverbound.29) packet's content
+pub fn read_keep_alive(p: &mut Vec<u8>) -> SResult<i64> {
+    p.get_i64()
+}
+
+/// Trying to read [player](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player) packet's content(on ground)
+pub fn read_player(p: &mut Vec<u8>) -> SResult<bool> {
+    p.get_bool()
+}
+
+/// Trying to read [player position](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_Position) packet's content.
+/// Returns x, y, z and on ground
+pub fn read_player_position(p: &mut Vec<u8>) -> SResult<(f64, f64, f64, bool)> {
+    Ok((p.get_f64()?, p.get_f64()?, p.get_f64()?, p.get_bool()?))
+}
+
+/// Trying to read [player position and look](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_Position_And_Look_.28serverbound.29) packet's content.
+/// Returns x, y, z, yaw, pitch and on ground
+pub fn read_player_position_and_look(p: &mut Vec<u8>) -> SResult<(f64, f64, f64, f32, f32, bool)> {
+    Ok((
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f32()?,
+        p.get_f32()?,
+        p.get_bool()?,
+    ))
+}
+
+/// Trying to read [player look](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_Look) packet's content.
+/// Returns yaw, pitch and on ground
+pub fn read_player_look(p: &mut Vec<u8>) -> SResult<(f32, f32, bool)> {
+    Ok((p.get_f32()?, p.get_f32()?, p.get_bool()?))
+}
+
+/// Build [disconnect](https://wiki.vg/index.php?title=Protocol&oldid=14204#Disconnect_.28play.29) packet on play stage
+pub fn create_disconnect(reason: &ChatMessage) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(serde_json::to_string(reason).unwrap());
+    bytes.create_packet(0x1A)
+}
+
+/// Build [chat message](https://wiki.vg/index.php?title=Protocol&oldid=14204#Chat_Message_.28clientbound.29) packet.
+/// Position: 0 - chat, 1 - system message, 2 - above hotbar
+pub fn create_chat_message(message: &ChatMessage, position: i8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(serde_json::to_string(message).unwrap());
+    bytes.write_i8(position);
+    bytes.create_packet(0x0F)
+}
+
+/// Trying to read [chat message](https://wiki.vg/index.php?title=Protocol&oldid=14204#Chat_Message_.28serverbound.29) packet's content
+pub fn read_chat_message(p: &mut Vec<u8>) -> SResult<String> {
+    p.get_string()
+}
+
+/// Build [player list item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Item) packet with latency's updates(UUID and ping in ms)
+pub fn create_player_list_latency(players: &[(u128, i32)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Action 2 - update latency
+    bytes.write_varint(2);
+    bytes.write_varint(players.len() as i32);
+    for (uuid, ping) in players {
+        bytes.write_u128(*uuid);
+        bytes.write_varint(*ping);
+    }
+    bytes.create_packet(0x2E)
+}
+
+// Writing optional display name in player list
+fn write_display_name(bytes: &mut Vec<u8>, display_name: Option<&ChatMessage>) {
+    bytes.write_bool(display_name.is_some());
+    if let Some(name) = display_name {
+        bytes.write_string(serde_json::to_string(name).unwrap());
+    }
+}
+
+/// Build [player list item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Item) packet adding players(UUID, name, game's mode, ping in ms and display name)
+pub fn create_player_list_add(players: &[(u128, &str, u8, i32, Option<&ChatMessage>)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Action 0 - add player
+    bytes.write_varint(0);
+    bytes.write_varint(players.len() as i32);
+    for (uuid, name, gamemode, ping, display_name) in players {
+        bytes.write_u128(*uuid);
+        bytes.write_string(name.to_string());
+        // No skin's properties in offline mode
+        bytes.write_varint(0);
+        bytes.write_varint(*gamemode as i32);
+        bytes.write_varint(*ping);
+        write_display_name(&mut bytes, *display_name);
+    }
+    bytes.create_packet(0x2E)
+}
+
+/// Build [player list item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Item) packet with game mode's updates
+pub fn create_player_list_gamemode(players: &[(u128, u8)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Action 1 - update game's mode
+    bytes.write_varint(1);
+    bytes.write_varint(players.len() as i32);
+    for (uuid, gamemode) in players {
+        bytes.write_u128(*uuid);
+        bytes.write_varint(*gamemode as i32);
+    }
+    bytes.create_packet(0x2E)
+}
+
+/// Build [player list item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Item) packet with display name's updates(None - player's name)
+pub fn create_player_list_display_name(players: &[(u128, Option<&ChatMessage>)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Action 3 - update display name
+    bytes.write_varint(3);
+    bytes.write_varint(players.len() as i32);
+    for (uuid, display_name) in players {
+        bytes.write_u128(*uuid);
+        write_display_name(&mut bytes, *display_name);
+    }
+    bytes.create_packet(0x2E)
+}
+
+/// Build [player list item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Item) packet removing players
+pub fn create_player_list_remove(players: &[u128]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Action 4 - remove player
+    bytes.write_varint(4);
+    bytes.write_varint(players.len() as i32);
+    for uuid in players {
+        bytes.write_u128(*uuid);
+    }
+    bytes.create_packet(0x2E)
+}
+
+/// Build [player list header and footer](https://wiki.vg/index.php?title=Protocol&oldid=14204#Player_List_Header_And_Footer) packet
+pub fn create_player_list_header_footer(header: &ChatMessage, footer: &ChatMessage) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_string(serde_json::to_string(header).unwrap());
+    bytes.write_string(serde_json::to_string(footer).unwrap());
+    bytes.create_packet(0x4A)
+}
+
+/// Build [change game state](https://wiki.vg/index.php?title=Protocol&oldid=14204#Change_Game_State) packet(reason 3 - game mode's change)
+pub fn create_change_game_state(reason: u8, value: f32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(reason);
+    bytes.write_f32(value);
+    bytes.create_packet(0x1E)
+}
+
+/// Build [open window](https://wiki.vg/index.php?title=Protocol&oldid=14204#Open_Window) packet(window's type like minecraft:chest)
+pub fn create_open_window(window_id: u8, kind: &str, title: &ChatMessage, slots: u8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(window_id);
+    bytes.write_string(kind.to_string());
+    bytes.write_string(serde_json::to_string(title).unwrap());
+    bytes.write_u8(slots);
+    bytes.create_packet(0x13)
+}
+
+/// Build [close window](https://wiki.vg/index.php?title=Protocol&oldid=14204#Close_Window_.28clientbound.29) packet
+pub fn create_close_window(window_id: u8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(window_id);
+    bytes.create_packet(0x12)
+}
+
+/// Build [window items](https://wiki.vg/index.php?title=Protocol&oldid=14204#Window_Items) packet with all window's slots
+pub fn create_window_items(window_id: u8, items: &[Option<ItemStack>]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(window_id);
+    bytes.write_i16(items.len() as i16);
+    for item in items {
+        bytes.write_slot(item.as_ref());
+    }
+    bytes.create_packet(0x14)
+}
+
+/// Build [set slot](https://wiki.vg/index.php?title=Protocol&oldid=14204#Set_Slot) packet(window -1 and slot -1 - cursor's item)
+pub fn create_set_slot(window_id: i8, slot: i16, item: Option<&ItemStack>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i8(window_id);
+    bytes.write_i16(slot);
+    bytes.write_slot(item);
+    bytes.create_packet(0x16)
+}
+
+/// Build [confirm transaction](https://wiki.vg/index.php?title=Protocol&oldid=14204#Confirm_Transaction_.28clientbound.29) packet
+pub fn create_confirm_transaction(window_id: i8, action: i16, accepted: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i8(window_id);
+    bytes.write_i16(action);
+    bytes.write_bool(accepted);
+    bytes.create_packet(0x11)
+}
+
+/// Build [held item change](https://wiki.vg/index.php?title=Protocol&oldid=14204#Held_Item_Change_.28clientbound.29) packet
+pub fn create_held_item_change(slot: u8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_u8(slot);
+    bytes.create_packet(0x3A)
+}
+
+/// Trying to read [click window](https://wiki.vg/index.php?title=Protocol&oldid=14204#Click_Window) packet's content.
+/// Returns window's id, slot, button, action's number, mode and clicked item
+pub fn read_click_window(p: &mut Vec<u8>) -> SResult<(u8, i16, i8, i16, i32, Option<ItemStack>)> {
+    Ok((
+        p.get_u8()?,
+        p.get_i16()?,
+        p.get_i8()?,
+        p.get_i16()?,
+        p.get_varint()?,
+        p.get_slot()?,
+    ))
+}
+
+/// Trying to read [confirm transaction](https://wiki.vg/index.php?title=Protocol&oldid=14204#Confirm_Transaction_.28serverbound.29) packet's content(window's id, action's number and accepted)
+pub fn read_confirm_transaction(p: &mut Vec<u8>) -> SResult<(i8, i16, bool)> {
+    Ok((p.get_i8()?, p.get_i16()?, p.get_bool()?))
+}
+
+/// Trying to read [close window](https://wiki.vg/index.php?title=Protocol&oldid=14204#Close_Window_.28serverbound.29) packet's content
+pub fn read_close_window(p: &mut Vec<u8>) -> SResult<u8> {
+    p.get_u8()
+}
+
+/// Trying to read [held item change](https://wiki.vg/index.php?title=Protocol&oldid=14204#Held_Item_Change_.28serverbound.29) packet's content
+pub fn read_held_item_change(p: &mut Vec<u8>) -> SResult<i16> {
+    p.get_i16()
+}
+
+/// Trying to read [creative inventory action](https://wiki.vg/index.php?title=Protocol&oldid=14204#Creative_Inventory_Action) packet's content(slot and item)
+pub fn read_creative_inventory_action(p: &mut Vec<u8>) -> SResult<(i16, Option<ItemStack>)> {
+    Ok((p.get_i16()?, p.get_slot()?))
+}
+
+/// Angle in degrees as steps of 1/256 of a full turn
+pub fn to_angle(degrees: f32) -> u8 {
+    (degrees * 256.0 / 360.0).floor() as i32 as u8
+}
+
+/// Velocity in blocks per tick as units of 1/8000 block(max 3.9 blocks per tick)
+pub fn to_velocity(value: f64) -> i16 {
+    (value.clamp(-3.9, 3.9) * 8000.0) as i16
+}
+
+// Writing velocity's x, y and z
+fn write_velocity(bytes: &mut Vec<u8>, (x, y, z): (i16, i16, i16)) {
+    bytes.write_i16(x);
+    bytes.write_i16(y);
+    bytes.write_i16(z);
+}
+
+// Writing position's x, y and z
+fn write_entity_position(bytes: &mut Vec<u8>, (x, y, z): (f64, f64, f64)) {
+    bytes.write_f64(x);
+    bytes.write_f64(y);
+    bytes.write_f64(z);
+}
+
+/// Build [spawn object](https://wiki.vg/index.php?title=Protocol&oldid=14204#Spawn_Object) packet.
+/// Data depends on object's type(like falling block's state)
+pub fn create_spawn_object(
+    entity_id: i32,
+    uuid: u128,
+    kind: u8,
+    position: (f64, f64, f64),
+    (yaw, pitch): (u8, u8),
+    data: i32,
+    velocity: (i16, i16, i16),
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_u128(uuid);
+    bytes.write_u8(kind);
+    write_entity_position(&mut bytes, position);
+    bytes.write_u8(pitch);
+    bytes.write_u8(yaw);
+    bytes.write_i32(data);
+    write_velocity(&mut bytes, velocity);
+    bytes.create_packet(0x00)
+}
+
+/// Build [spawn experience orb](https://wiki.vg/index.php?title=Protocol&oldid=14204#Spawn_Experience_Orb) packet
+pub fn create_spawn_experience_orb(
+    entity_id: i32,
+    position: (f64, f64, f64),
+    count: i16,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    write_entity_position(&mut bytes, position);
+    bytes.write_i16(count);
+    bytes.create_packet(0x01)
+}
+
+/// Build [spawn mob](https://wiki.vg/index.php?title=Protocol&oldid=14204#Spawn_Mob) packet with encoded metadata
+pub fn create_spawn_mob(
+    entity_id: i32,
+    uuid: u128,
+    kind: i32,
+    position: (f64, f64, f64),
+    (yaw, pitch, head_yaw): (u8, u8, u8),
+    velocity: (i16, i16, i16),
+    metadata: Vec<u8>,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_u128(uuid);
+    bytes.write_varint(kind);
+    write_entity_position(&mut bytes, position);
+    bytes.write_u8(yaw);
+    bytes.write_u8(pitch);
+    bytes.write_u8(head_yaw);
+    write_velocity(&mut bytes, velocity);
+    bytes.write_vec_bytes(metadata);
+    bytes.create_packet(0x03)
+}
+
+/// Build [spawn player](https://wiki.vg/index.php?title=Protocol&oldid=14204#Spawn_Player) packet.
+/// Player must be in client's player list
+pub fn create_spawn_player(
+    entity_id: i32,
+    uuid: u128,
+    position: (f64, f64, f64),
+    (yaw, pitch): (u8, u8),
+    metadata: Vec<u8>,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_u128(uuid);
+    write_entity_position(&mut bytes, position);
+    bytes.write_u8(yaw);
+    bytes.write_u8(pitch);
+    bytes.write_vec_bytes(metadata);
+    bytes.create_packet(0x05)
+}
+
+/// Build [destroy entities](https://wiki.vg/index.php?title=Protocol&oldid=14204#Destroy_Entities) packet
+pub fn create_destroy_entities(entity_ids: &[i32]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_ids.len() as i32);
+    for id in entity_ids {
+        bytes.write_varint(*id);
+    }
+    bytes.create_packet(0x32)
+}
+
+/// Build [entity relative move](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Relative_Move) packet.
+/// Delta is in units of 1/4096 block
+pub fn create_entity_relative_move(
+    entity_id: i32,
+    (dx, dy, dz): (i16, i16, i16),
+    on_ground: bool,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_i16(dx);
+    bytes.write_i16(dy);
+    bytes.write_i16(dz);
+    bytes.write_bool(on_ground);
+    bytes.create_packet(0x26)
+}
+
+/// Build [entity look and relative move](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Look_And_Relative_Move) packet
+pub fn create_entity_look_relative_move(
+    entity_id: i32,
+    (dx, dy, dz): (i16, i16, i16),
+    (yaw, pitch): (u8, u8),
+    on_ground: bool,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_i16(dx);
+    bytes.write_i16(dy);
+    bytes.write_i16(dz);
+    bytes.write_u8(yaw);
+    bytes.write_u8(pitch);
+    bytes.write_bool(on_ground);
+    bytes.create_packet(0x27)
+}
+
+/// Build [entity look](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Look) packet
+pub fn create_entity_look(entity_id: i32, (yaw, pitch): (u8, u8), on_ground: bool) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_u8(yaw);
+    bytes.write_u8(pitch);
+    bytes.write_bool(on_ground);
+    bytes.create_packet(0x28)
+}
+
+/// Build [collect item](https://wiki.vg/index.php?title=Protocol&oldid=14204#Collect_Item) packet(item flies to collector)
+pub fn create_collect_item(collected_id: i32, collector_id: i32, count: i32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(collected_id);
+    bytes.write_varint(collector_id);
+    bytes.write_varint(count);
+    bytes.create_packet(0x4B)
+}
+
+/// Build [entity teleport](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Teleport) packet
+pub fn create_entity_teleport(
+    entity_id: i32,
+    position: (f64, f64, f64),
+    (yaw, pitch): (u8, u8),
+    on_ground: bool,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    write_entity_position(&mut bytes, position);
+    bytes.write_u8(yaw);
+    bytes.write_u8(pitch);
+    bytes.write_bool(on_ground);
+    bytes.create_packet(0x4C)
+}
+
+/// Build [entity head look](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Head_Look) packet
+pub fn create_entity_head_look(entity_id: i32, head_yaw: u8) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_u8(head_yaw);
+    bytes.create_packet(0x36)
+}
+
+/// Build [entity velocity](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Velocity) packet
+pub fn create_entity_velocity(entity_id: i32, velocity: (i16, i16, i16)) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    write_velocity(&mut bytes, velocity);
+    bytes.create_packet(0x3E)
+}
+
+/// Build [entity metadata](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Metadata) packet with encoded metadata
+pub fn create_entity_metadata(entity_id: i32, metadata: Vec<u8>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_varint(entity_id);
+    bytes.write_vec_bytes(metadata);
+    bytes.create_packet(0x3C)
+}
+
+/// Trying to read [entity action](https://wiki.vg/index.php?title=Protocol&oldid=14204#Entity_Action) packet's content.
+/// Returns entity's id, action's id and jump boost
+pub fn read_entity_action(p: &mut Vec<u8>) -> SResult<(i32, i32, i32)> {
+    Ok((p.get_varint()?, p.get_varint()?, p.get_varint()?))
+}
+
+// Writing section's blocks and light like in chunk's data
+fn write_chunk_section(bytes: &mut Vec<u8>, section: &ChunkSection, sky_light: bool) {
+    bytes.write_u8(section.bits());
+    // Empty palette for global palette
+    bytes.write_varint(section.palette().len() as i32);
+    for state in section.palette() {
+        bytes.write_varint(*state as i32);
+    }
+    bytes.write_varint(section.data().len() as i32);
+    for long in section.data() {
+        bytes.write_i64(*long as i64);
+    }
+    bytes.extend_from_slice(&section.block_light.0);
+    if sky_light {
+        bytes.extend_from_slice(&section.sky_light.0);
+    }
+}
+
+/// Build [chunk data](https://wiki.vg/index.php?title=Protocol&oldid=14204#Chunk_Data) packet with sections from mask.
+/// Full chunk also has biomes and replaces client's chunk
+pub fn create_chunk_data(column: &ChunkColumn, full_chunk: bool, mask: u16) -> Vec<u8> {
+    // Air sections aren't sent
+    let mask = mask & column.section_mask();
+    let mut data = Vec::new();
+    for (i, section) in column.sections.iter().enumerate() {
+        if let Some(section) = section.as_ref().filter(|_| mask & 1 << i != 0) {
+            write_chunk_section(&mut data, section, column.has_sky_light);
+        }
+    }
+    if full_chunk {
+        data.extend_from_slice(&column.biomes);
+    }
+    let mut bytes = Vec::new();
+    bytes.write_i32(column.x);
+    bytes.write_i32(column.z);
+    bytes.write_bool(full_chunk);
+    bytes.write_varint(mask as i32);
+    bytes.write_varint(data.len() as i32);
+    bytes.write_vec_bytes(data);
+    bytes.write_varint(column.block_entities.len() as i32);
+    for block_entity in &column.block_entities {
+        bytes.write_nbt(Some(block_entity));
+    }
+    bytes.create_packet(0x20)
+}
+
+/// Build [unload chunk](https://wiki.vg/index.php?title=Protocol&oldid=14204#Unload_Chunk) packet
+pub fn create_unload_chunk(x: i32, z: i32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.write_i32(x);
+    bytes.write_i32(z);
+    bytes.create_packet(0x1D)
+}
+
+/// Trying to read [client settings](https://wiki.vg/index.php?title=Protocol&oldid=14204#Client_Settings) packet's content.
+/// Returns locale and view distance(other settings are skipped)
+pub fn read_client_settings(p: &mut Vec<u8>) -> SResult<(String, i8)> {
+    let locale = p.get_string()?;
+    Ok((locale, p.get_i8()?))
+}
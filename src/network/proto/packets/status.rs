@@ -1,4 +1,4 @@
-use crate::config::PROTOCOL_VERSION;
+use crate::config::{MAX_PLAYERS, PROTOCOL_VERSION};
 use crate::network::proto::packet_write::PacketWriter;
 use crate::utils::chat::ChatMessage;
 
@@ -41,7 +41,7 @@ pub fn create_server_list_ping_response() -> Vec<u8> {
                 protocol: PROTOCOL_VERSION,
             },
             players: ListPingResponsePlayers {
-                max: 10,
+                max: MAX_PLAYERS as u32,
                 online: 0,
                 sample: vec![],
             },
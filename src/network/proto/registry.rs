@@ -0,0 +1,1021 @@
+use crate::game::entity::metadata::Metadata;
+use crate::network::network_client::ConnectionType;
+use crate::network::network_client::ConnectionType::{HANDSHAKING, LOGIN, PLAY, STATUS};
+use crate::network::proto::PacketReader;
+use crate::utils::uuid::uuid_to_string;
+use crate::SResult;
+
+/// Packet's direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    // Client -> Server
+    Serverbound,
+    // Server -> Client
+    Clientbound,
+}
+
+/// Information about known packet
+pub struct PacketInfo {
+    pub state: ConnectionType,
+    pub direction: Direction,
+    pub id: i32,
+    pub name: &'static str,
+    // Decoding a packet's fields(after PacketID) as readable text
+    pub describe: fn(&mut Vec<u8>) -> SResult<String>,
+}
+
+// All known packets
+pub const PACKETS: &[PacketInfo] = &[
+    // Handshaking
+    PacketInfo {
+        state: HANDSHAKING,
+        direction: Direction::Serverbound,
+        id: 0x00,
+        name: "Handshake",
+        describe: describe_handshake,
+    },
+    // Status
+    PacketInfo {
+        state: STATUS,
+        direction: Direction::Serverbound,
+        id: 0x00,
+        name: "Request",
+        describe: describe_empty,
+    },
+    PacketInfo {
+        state: STATUS,
+        direction: Direction::Serverbound,
+        id: 0x01,
+        name: "Ping",
+        describe: describe_ping,
+    },
+    PacketInfo {
+        state: STATUS,
+        direction: Direction::Clientbound,
+        id: 0x00,
+        name: "Response",
+        describe: describe_response,
+    },
+    PacketInfo {
+        state: STATUS,
+        direction: Direction::Clientbound,
+        id: 0x01,
+        name: "Pong",
+        describe: describe_ping,
+    },
+    // Login
+    PacketInfo {
+        state: LOGIN,
+        direction: Direction::Serverbound,
+        id: 0x00,
+        name: "Login Start",
+        describe: describe_string,
+    },
+    PacketInfo {
+        state: LOGIN,
+        direction: Direction::Clientbound,
+        id: 0x00,
+        name: "Disconnect",
+        describe: describe_string,
+    },
+    PacketInfo {
+        state: LOGIN,
+        direction: Direction::Clientbound,
+        id: 0x02,
+        name: "Login Success",
+        describe: describe_login_success,
+    },
+    // Play(serverbound)
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x00,
+        name: "Teleport Confirm",
+        describe: describe_varint,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x02,
+        name: "Chat Message",
+        describe: describe_string,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x03,
+        name: "Client Status",
+        describe: describe_client_status,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x04,
+        name: "Client Settings",
+        describe: describe_client_settings,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x05,
+        name: "Confirm Transaction",
+        describe: describe_confirm_transaction,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x07,
+        name: "Click Window",
+        describe: describe_click_window,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x08,
+        name: "Close Window",
+        describe: describe_window_id,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x09,
+        name: "Plugin Message",
+        describe: describe_plugin_message,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x0B,
+        name: "Keep Alive",
+        describe: describe_i64,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x0C,
+        name: "Player",
+        describe: describe_on_ground,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x0D,
+        name: "Player Position",
+        describe: describe_player_position,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x0E,
+        name: "Player Position And Look",
+        describe: describe_player_position_look,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x0F,
+        name: "Player Look",
+        describe: describe_player_look,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x15,
+        name: "Entity Action",
+        describe: describe_entity_action,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x1A,
+        name: "Held Item Change",
+        describe: describe_held_item_change,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Serverbound,
+        id: 0x1B,
+        name: "Creative Inventory Action",
+        describe: describe_creative_inventory_action,
+    },
+    // Play(clientbound)
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x00,
+        name: "Spawn Object",
+        describe: describe_spawn_object,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x01,
+        name: "Spawn Experience Orb",
+        describe: describe_spawn_experience_orb,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x03,
+        name: "Spawn Mob",
+        describe: describe_spawn_mob,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x05,
+        name: "Spawn Player",
+        describe: describe_spawn_player,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x0D,
+        name: "Server Difficulty",
+        describe: describe_u8,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x0F,
+        name: "Chat Message",
+        describe: describe_chat_message,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x11,
+        name: "Confirm Transaction",
+        describe: describe_confirm_transaction,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x12,
+        name: "Close Window",
+        describe: describe_window_id,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x13,
+        name: "Open Window",
+        describe: describe_open_window,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x14,
+        name: "Window Items",
+        describe: describe_window_items,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x16,
+        name: "Set Slot",
+        describe: describe_set_slot,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x18,
+        name: "Plugin Message",
+        describe: describe_plugin_message,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x1A,
+        name: "Disconnect",
+        describe: describe_string,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x1D,
+        name: "Unload Chunk",
+        describe: describe_chunk_position,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x1E,
+        name: "Change Game State",
+        describe: describe_change_game_state,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x1F,
+        name: "Keep Alive",
+        describe: describe_i64,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x20,
+        name: "Chunk Data",
+        describe: describe_chunk_data,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x23,
+        name: "Join Game",
+        describe: describe_join_game,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x26,
+        name: "Entity Relative Move",
+        describe: describe_entity_relative_move,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x27,
+        name: "Entity Look And Relative Move",
+        describe: describe_entity_look_relative_move,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x28,
+        name: "Entity Look",
+        describe: describe_entity_look,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x2C,
+        name: "Player Abilities",
+        describe: describe_player_abilities,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x2E,
+        name: "Player List Item",
+        describe: describe_player_list_item,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x2F,
+        name: "Player Position And Look",
+        describe: describe_server_position_look,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x32,
+        name: "Destroy Entities",
+        describe: describe_destroy_entities,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x35,
+        name: "Respawn",
+        describe: describe_respawn,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x36,
+        name: "Entity Head Look",
+        describe: describe_entity_head_look,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x3A,
+        name: "Held Item Change",
+        describe: describe_window_id,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x3C,
+        name: "Entity Metadata",
+        describe: describe_entity_metadata,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x3E,
+        name: "Entity Velocity",
+        describe: describe_entity_velocity,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x40,
+        name: "Set Experience",
+        describe: describe_set_experience,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x41,
+        name: "Update Health",
+        describe: describe_update_health,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x46,
+        name: "Spawn Position",
+        describe: describe_position,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x47,
+        name: "Time Update",
+        describe: describe_time_update,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x4A,
+        name: "Player List Header And Footer",
+        describe: describe_player_list_header_footer,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x4B,
+        name: "Collect Item",
+        describe: describe_collect_item,
+    },
+    PacketInfo {
+        state: PLAY,
+        direction: Direction::Clientbound,
+        id: 0x4C,
+        name: "Entity Teleport",
+        describe: describe_entity_teleport,
+    },
+];
+
+/// Finding a packet's information
+pub fn find_packet(
+    state: ConnectionType,
+    direction: Direction,
+    id: i32,
+) -> Option<&'static PacketInfo> {
+    PACKETS
+        .iter()
+        .find(|v| v.state == state && v.direction == direction && v.id == id)
+}
+
+/// Decoded frame
+#[derive(Debug, Clone)]
+pub struct DecodedPacket {
+    pub id: i32,
+    // Packet's name or None if it unknown
+    pub name: Option<&'static str>,
+    pub fields: String,
+}
+
+/// Decoding a frame(length prefix + packet) to readable form
+pub fn decode_frame(
+    state: ConnectionType,
+    direction: Direction,
+    frame: &[u8],
+) -> SResult<DecodedPacket> {
+    let mut p = frame.to_vec();
+    let (_, id) = p.read_base()?;
+    Ok(match find_packet(state, direction, id) {
+        Some(info) => DecodedPacket {
+            id,
+            name: Some(info.name),
+            fields: (info.describe)(&mut p)?,
+        },
+        None => DecodedPacket {
+            id,
+            name: None,
+            fields: format!("{} bytes", p.len()),
+        },
+    })
+}
+
+/// Connection's state after the frame(length prefix + packet)
+pub fn state_after(state: ConnectionType, direction: Direction, frame: &[u8]) -> ConnectionType {
+    let mut p = frame.to_vec();
+    let id = match p.read_base() {
+        Ok((_, id)) => id,
+        Err(_) => return state,
+    };
+    match (state, direction, id) {
+        (HANDSHAKING, Direction::Serverbound, 0x00) => {
+            // Skipping version, address and port
+            let next_state = p
+                .get_varint()
+                .and_then(|_| p.get_string())
+                .and_then(|_| p.get_u16())
+                .and_then(|_| p.get_varint());
+            match next_state {
+                Ok(1) => STATUS,
+                _ => LOGIN,
+            }
+        }
+        (LOGIN, Direction::Clientbound, 0x02) => PLAY,
+        _ => state,
+    }
+}
+
+// Packets without fields
+fn describe_empty(_: &mut Vec<u8>) -> SResult<String> {
+    Ok(String::new())
+}
+
+fn describe_handshake(p: &mut Vec<u8>) -> SResult<String> {
+    let version = p.get_varint()?;
+    let address = p.get_string()?;
+    let port = p.get_u16()?;
+    let next_state = p.get_varint()?;
+    Ok(format!(
+        "version={} address={:?} port={} next_state={}",
+        version, address, port, next_state
+    ))
+}
+
+fn describe_ping(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("payload={}", p.get_i64()?))
+}
+
+fn describe_response(p: &mut Vec<u8>) -> SResult<String> {
+    p.get_string()
+}
+
+fn describe_string(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("{:?}", p.get_string()?))
+}
+
+fn describe_varint(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("{}", p.get_varint()?))
+}
+
+fn describe_i64(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("{}", p.get_i64()?))
+}
+
+fn describe_login_success(p: &mut Vec<u8>) -> SResult<String> {
+    let uuid = p.get_string()?;
+    let name = p.get_string()?;
+    Ok(format!("uuid={} name={:?}", uuid, name))
+}
+
+fn describe_on_ground(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("on_ground={}", p.get_bool()?))
+}
+
+fn describe_player_position(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "x={} y={} z={} on_ground={}",
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_client_status(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("action={}", p.get_varint()?))
+}
+
+fn describe_player_position_look(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "x={} y={} z={} yaw={} pitch={} on_ground={}",
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f32()?,
+        p.get_f32()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_player_look(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "yaw={} pitch={} on_ground={}",
+        p.get_f32()?,
+        p.get_f32()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_chat_message(p: &mut Vec<u8>) -> SResult<String> {
+    let message = p.get_string()?;
+    Ok(format!("position={} {}", p.get_i8()?, message))
+}
+
+fn describe_server_position_look(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "x={} y={} z={} yaw={} pitch={} flags={:#04x} teleport_id={}",
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f32()?,
+        p.get_f32()?,
+        p.get_u8()?,
+        p.get_varint()?
+    ))
+}
+
+fn describe_u8(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("{}", p.get_u8()?))
+}
+
+fn describe_position(p: &mut Vec<u8>) -> SResult<String> {
+    let (x, y, z) = p.get_position()?;
+    Ok(format!("x={} y={} z={}", x, y, z))
+}
+
+fn describe_time_update(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "world_age={} time_of_day={}",
+        p.get_i64()?,
+        p.get_i64()?
+    ))
+}
+
+fn describe_plugin_message(p: &mut Vec<u8>) -> SResult<String> {
+    let channel = p.get_string()?;
+    Ok(format!(
+        "channel={:?} data={:?}",
+        channel,
+        String::from_utf8_lossy(p)
+    ))
+}
+
+fn describe_client_settings(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "locale={:?} view_distance={} chat_mode={} chat_colors={} skin_parts={:#04x} main_hand={}",
+        p.get_string()?,
+        p.get_i8()?,
+        p.get_varint()?,
+        p.get_bool()?,
+        p.get_u8()?,
+        p.get_varint()?
+    ))
+}
+
+fn describe_join_game(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} gamemode={} dimension={} difficulty={} max_players={} level_type={:?} reduced_debug_info={}",
+        p.get_i32()?,
+        p.get_u8()?,
+        p.get_i32()?,
+        p.get_u8()?,
+        p.get_u8()?,
+        p.get_string()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_respawn(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "dimension={} difficulty={} gamemode={} level_type={:?}",
+        p.get_i32()?,
+        p.get_u8()?,
+        p.get_u8()?,
+        p.get_string()?
+    ))
+}
+
+fn describe_set_experience(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "progress={} level={} total={}",
+        p.get_f32()?,
+        p.get_varint()?,
+        p.get_varint()?
+    ))
+}
+
+fn describe_update_health(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "health={} food={} saturation={}",
+        p.get_f32()?,
+        p.get_varint()?,
+        p.get_f32()?
+    ))
+}
+
+fn describe_player_abilities(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "flags={:#04x} flying_speed={} fov_modifier={}",
+        p.get_u8()?,
+        p.get_f32()?,
+        p.get_f32()?
+    ))
+}
+
+fn describe_player_list_item(p: &mut Vec<u8>) -> SResult<String> {
+    let action = p.get_varint()?;
+    let count = p.get_varint()?;
+    let mut text = format!("action={} count={}", action, count);
+    for _ in 0..count {
+        text.push_str(&format!(" {}", uuid_to_string(p.get_u128()?)));
+        match action {
+            // Adding shows name, game's mode and ping
+            0 => {
+                let name = p.get_string()?;
+                for _ in 0..p.get_varint()? {
+                    p.get_string()?;
+                    p.get_string()?;
+                    if p.get_bool()? {
+                        p.get_string()?;
+                    }
+                }
+                text.push_str(&format!(
+                    "={}(gamemode={} ping={}ms)",
+                    name,
+                    p.get_varint()?,
+                    p.get_varint()?
+                ));
+                if p.get_bool()? {
+                    text.push_str(&format!(" display_name={}", p.get_string()?));
+                }
+            }
+            1 => text.push_str(&format!(" gamemode={}", p.get_varint()?)),
+            2 => text.push_str(&format!("={}ms", p.get_varint()?)),
+            3 if p.get_bool()? => text.push_str(&format!(" display_name={}", p.get_string()?)),
+            _ => {}
+        }
+    }
+    Ok(text)
+}
+
+fn describe_player_list_header_footer(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "header={} footer={}",
+        p.get_string()?,
+        p.get_string()?
+    ))
+}
+
+fn describe_change_game_state(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("reason={} value={}", p.get_u8()?, p.get_f32()?))
+}
+
+fn describe_chunk_position(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("x={} z={}", p.get_i32()?, p.get_i32()?))
+}
+
+fn describe_chunk_data(p: &mut Vec<u8>) -> SResult<String> {
+    let position = describe_chunk_position(p)?;
+    Ok(format!(
+        "{} full_chunk={} mask={:#06x} size={}",
+        position,
+        p.get_bool()?,
+        p.get_varint()?,
+        p.get_varint()?
+    ))
+}
+
+// Angle in degrees from steps of 1/256 of a full turn
+fn angle(p: &mut Vec<u8>) -> SResult<f32> {
+    Ok(p.get_u8()? as f32 * 360.0 / 256.0)
+}
+
+fn describe_spawn_object(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} uuid={} type={} x={} y={} z={} pitch={} yaw={} data={} velocity=({}, {}, {})",
+        p.get_varint()?,
+        uuid_to_string(p.get_u128()?),
+        p.get_u8()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        angle(p)?,
+        angle(p)?,
+        p.get_i32()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_i16()?
+    ))
+}
+
+fn describe_spawn_experience_orb(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} x={} y={} z={} count={}",
+        p.get_varint()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_i16()?
+    ))
+}
+
+fn describe_spawn_mob(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} uuid={} type={} x={} y={} z={} yaw={} pitch={} head_yaw={} velocity=({}, {}, {})",
+        p.get_varint()?,
+        uuid_to_string(p.get_u128()?),
+        p.get_varint()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        angle(p)?,
+        angle(p)?,
+        angle(p)?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_i16()?
+    ))
+}
+
+fn describe_spawn_player(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} uuid={} x={} y={} z={} yaw={} pitch={}",
+        p.get_varint()?,
+        uuid_to_string(p.get_u128()?),
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        angle(p)?,
+        angle(p)?
+    ))
+}
+
+fn describe_destroy_entities(p: &mut Vec<u8>) -> SResult<String> {
+    let count = p.get_varint()?;
+    let mut ids = Vec::new();
+    for _ in 0..count {
+        ids.push(p.get_varint()?.to_string());
+    }
+    Ok(format!("entity_ids=[{}]", ids.join(", ")))
+}
+
+fn describe_collect_item(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "collected_id={} collector_id={} count={}",
+        p.get_varint()?,
+        p.get_varint()?,
+        p.get_varint()?
+    ))
+}
+
+fn describe_entity_relative_move(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} delta=({}, {}, {}) on_ground={}",
+        p.get_varint()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_entity_look_relative_move(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} delta=({}, {}, {}) yaw={} pitch={} on_ground={}",
+        p.get_varint()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        angle(p)?,
+        angle(p)?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_entity_look(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} yaw={} pitch={} on_ground={}",
+        p.get_varint()?,
+        angle(p)?,
+        angle(p)?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_entity_teleport(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} x={} y={} z={} yaw={} pitch={} on_ground={}",
+        p.get_varint()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        p.get_f64()?,
+        angle(p)?,
+        angle(p)?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_entity_head_look(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} head_yaw={}",
+        p.get_varint()?,
+        angle(p)?
+    ))
+}
+
+fn describe_entity_velocity(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} velocity=({}, {}, {})",
+        p.get_varint()?,
+        p.get_i16()?,
+        p.get_i16()?,
+        p.get_i16()?
+    ))
+}
+
+fn describe_entity_metadata(p: &mut Vec<u8>) -> SResult<String> {
+    let entity_id = p.get_varint()?;
+    let metadata = Metadata::read(p)?;
+    let indexes: Vec<String> = metadata.iter().map(|(k, _)| k.to_string()).collect();
+    Ok(format!(
+        "entity_id={} indexes=[{}]",
+        entity_id,
+        indexes.join(", ")
+    ))
+}
+
+fn describe_entity_action(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "entity_id={} action={} jump_boost={}",
+        p.get_varint()?,
+        p.get_varint()?,
+        p.get_varint()?
+    ))
+}
+
+// Item in slot as "id:damage x count"
+fn describe_slot(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(match p.get_slot()? {
+        Some(item) => format!("{}:{} x{}", item.id, item.damage, item.count),
+        None => String::from("empty"),
+    })
+}
+
+fn describe_window_id(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("id={}", p.get_u8()?))
+}
+
+fn describe_confirm_transaction(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "window_id={} action={} accepted={}",
+        p.get_i8()?,
+        p.get_i16()?,
+        p.get_bool()?
+    ))
+}
+
+fn describe_click_window(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "window_id={} slot={} button={} action={} mode={} item={}",
+        p.get_u8()?,
+        p.get_i16()?,
+        p.get_i8()?,
+        p.get_i16()?,
+        p.get_varint()?,
+        describe_slot(p)?
+    ))
+}
+
+fn describe_held_item_change(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("slot={}", p.get_i16()?))
+}
+
+fn describe_creative_inventory_action(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!("slot={} item={}", p.get_i16()?, describe_slot(p)?))
+}
+
+fn describe_open_window(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "window_id={} type={} title={} slots={}",
+        p.get_u8()?,
+        p.get_string()?,
+        p.get_string()?,
+        p.get_u8()?
+    ))
+}
+
+fn describe_window_items(p: &mut Vec<u8>) -> SResult<String> {
+    let window_id = p.get_u8()?;
+    let count = p.get_i16()?;
+    let mut items = 0;
+    for _ in 0..count {
+        if p.get_slot()?.is_some() {
+            items += 1;
+        }
+    }
+    Ok(format!(
+        "window_id={} slots={} not_empty={}",
+        window_id, count, items
+    ))
+}
+
+fn describe_set_slot(p: &mut Vec<u8>) -> SResult<String> {
+    Ok(format!(
+        "window_id={} slot={} item={}",
+        p.get_i8()?,
+        p.get_i16()?,
+        describe_slot(p)?
+    ))
+}
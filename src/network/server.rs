@@ -1,12 +1,14 @@
-use crate::network::handler::{handshaking, status_handler};
-use crate::network::network_client::ConnectionType::HANDSHAKING;
-use crate::network::network_client::NetworkClient;
-use ahash::AHashMap;
+use crate::config::NETWORK_WORKERS;
+use crate::network::capture;
+use crate::network::messages::{NetEvent, Outbound};
+use crate::network::worker::NetworkWorker;
 use mio::net::TcpListener;
 use mio::{Events, Interest, Poll, Token};
 use std::io;
-use std::sync::mpsc::Sender;
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Mutex;
+use std::thread;
 use std::time::Duration;
 
 // Declare global variables
@@ -27,26 +29,99 @@ fn next(current: &mut Token) -> Token {
     Token(next)
 }
 
-// Start a network server
-pub fn network_server_start(address: String, tx: &Sender<bool>) -> std::io::Result<()> {
+/// Started network server
+pub struct NetworkServer {
+    // Bound address
+    pub address: SocketAddr,
+    // Events from workers to game's thread
+    pub events: Receiver<NetEvent>,
+    // Commands from game's thread to workers
+    pub outbound: Outbound,
+}
+
+// Count of network's workers(config's value or count of cores)
+fn workers_count() -> usize {
+    match NETWORK_WORKERS {
+        0 => thread::available_parallelism()
+            .map(|v| v.get())
+            .unwrap_or(1),
+        v => v,
+    }
+}
+
+// Start a network server in another thread and wait for its start
+pub fn spawn_network_server(address: String) -> std::io::Result<NetworkServer> {
+    // Creating channel for communication with network's thread
+    let (tx, rx) = channel::<std::io::Result<NetworkServer>>();
+    thread::Builder::new()
+        .name(String::from("Network Acceptor"))
+        .spawn(move || {
+            // If failed to start when return error
+            if let Err(err) = network_server_start(address, &tx) {
+                error!("{}", err);
+                tx.send(Err(err));
+            }
+        })?;
+    // Wait for status from server's network
+    rx.recv()
+        .unwrap_or_else(|_| Err(io::Error::other("Network server stopped")))
+}
+
+// Start a network server(acceptor and workers which handle connections)
+pub fn network_server_start(
+    address: String,
+    tx: &Sender<std::io::Result<NetworkServer>>,
+) -> std::io::Result<()> {
     // Creating Network Pool
     let mut poll = Poll::new()?;
     // Creating Network Events Pool
     let mut events = Events::with_capacity(128);
     // Converting String's address to SocketAddr
-    let addr = address.parse().unwrap();
+    let addr = match address.parse() {
+        Ok(v) => v,
+        Err(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid address: {}", address),
+            ))
+        }
+    };
     // Starting a Network Listener
     let mut server = TcpListener::bind(addr)?;
     // Register server's Token
     poll.registry()
         .register(&mut server, SERVER, Interest::READABLE)?;
 
-    // Creating a list of connections
-    let mut connections: AHashMap<Token, NetworkClient> = AHashMap::new();
+    // Starting workers
+    let (events_tx, events_rx) = channel::<NetEvent>();
+    let mut outbound = Outbound::new();
+    let mut workers = Vec::new();
+    let mut incoming = Vec::new();
+    for i in 0..workers_count() {
+        let (incoming_tx, incoming_rx) = channel();
+        let (commands_tx, commands_rx) = channel();
+        let worker = NetworkWorker::new(incoming_rx, commands_rx, events_tx.clone())?;
+        outbound.add_worker(commands_tx, worker.waker.clone());
+        incoming.push(incoming_tx);
+        workers.push(
+            thread::Builder::new()
+                .name(format!("Network Worker #{}", i))
+                .spawn(move || {
+                    if let Err(err) = worker.run() {
+                        error!("Network worker #{} failed: {}", i, err);
+                    }
+                })?,
+        );
+    }
+    drop(events_tx);
     // Creating a variable with latest token.
     let mut unique_token = Token(SERVER.0 + 1);
     // Send over the channel that the server has been successfully started
-    tx.send(true);
+    tx.send(Ok(NetworkServer {
+        address: server.local_addr()?,
+        events: events_rx,
+        outbound: outbound.clone(),
+    }));
 
     // Network Events getting timeout
     let timeout = Some(Duration::from_millis(10));
@@ -54,70 +129,53 @@ pub fn network_server_start(address: String, tx: &Sender<bool>) -> std::io::Resu
     loop {
         // Checks whether it is necessary to shutdown the network server
         if *SHUTDOWN_SERVER.lock().unwrap() {
+            // Waiting for workers
+            outbound.wake_all();
+            for worker in workers {
+                worker.join();
+            }
+            // Writing a rest of captured packets
+            capture::finish_capture();
             *NET_SERVER_WORKS.lock().unwrap() = false;
             info!("Network Server Stopped!");
             return Ok(());
         }
         // Getting a events from pool to event's pool with timeout
-        poll.poll(&mut events, timeout)?;
-        // Handing a events
+        if let Err(err) = poll.poll(&mut events, timeout) {
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+        // Handing a events(only server's token is registered)
         for event in events.iter() {
-            // Handing event by token
-            match event.token() {
-                // If it server's event
-                // Reading a all incoming connection
-                SERVER => loop {
-                    // Accepting connection
-                    let (mut connection, _) = match server.accept() {
-                        // If successful
-                        Ok(v) => v,
-                        // If not exists incoming connection
-                        Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
-                            break;
-                        }
-                        // If failed to get incoming connection
-                        Err(e) => {
-                            return Err(e);
-                        }
-                    };
-
-                    // Generating new token for this connection
-                    let token = next(&mut unique_token);
-                    // Registering connection with token
-                    poll.registry().register(
-                        &mut connection,
-                        token,
-                        Interest::READABLE.add(Interest::WRITABLE),
-                    )?;
-                    // Pushing connection into connection's list
-                    connections.insert(
-                        token,
-                        NetworkClient {
-                            stream: connection,
-                            conn_type: HANDSHAKING,
-                        },
-                    );
-                },
-                // Handing event from client
-                token => {
-                    // Handing event by connection's stage
-                    let done = if let Some(connection) = connections.get_mut(&token) {
-                        let m = match &connection.conn_type {
-                            HANDSHAKING => handshaking,
-                            _ => status_handler,
-                        };
-                        // Trying to handing
-                        m(connection, &event).unwrap_or(false)
-                    } else {
-                        false
-                    };
-                    // If needs to close connection - removing from list, unregister and close connection's stream
-                    if done {
-                        if let Some(mut connection) = connections.remove(&token) {
-                            poll.registry().deregister(&mut connection.stream)?;
-                            connections.remove(&token);
-                        }
+            if event.token() != SERVER {
+                continue;
+            }
+            // Reading a all incoming connection
+            loop {
+                // Accepting connection
+                let (connection, address) = match server.accept() {
+                    // If successful
+                    Ok(v) => v,
+                    // If not exists incoming connection
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        break;
+                    }
+                    // If failed to get incoming connection(like too many open files)
+                    Err(e) => {
+                        warn!("Failed to accept connection: {}", e);
+                        break;
                     }
+                };
+                // Generating new token for this connection
+                let token = next(&mut unique_token);
+                // Recording new connection if capturing
+                capture::record_open(token.0, address);
+                // Passing connection to its worker
+                let worker = outbound.worker_of(token.0);
+                if incoming[worker].send((token.0, connection)).is_ok() {
+                    outbound.wake(token.0);
                 }
             }
         }
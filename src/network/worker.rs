@@ -0,0 +1,145 @@
+use crate::network::capture;
+use crate::network::handler::handle_event;
+use crate::network::messages::{NetCommand, NetEvent};
+use crate::network::network_client::ConnectionType::PLAY;
+use crate::network::network_client::NetworkClient;
+use crate::network::server::SHUTDOWN_SERVER;
+use ahash::AHashMap;
+use mio::net::TcpStream;
+use mio::{Events, Interest, Poll, Registry, Token, Waker};
+use std::io;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
+
+// Worker's waker Token(connections start from 1)
+pub const WAKER: Token = Token(0);
+
+// Network worker's event loop which owns a part of connections
+pub struct NetworkWorker {
+    poll: Poll,
+    pub waker: Arc<Waker>,
+    connections: AHashMap<Token, NetworkClient>,
+    // New connections from acceptor
+    incoming: Receiver<(usize, TcpStream)>,
+    // Commands from game's thread
+    commands: Receiver<NetCommand>,
+    // Events to game's thread
+    events: Sender<NetEvent>,
+}
+
+// Closing a connection(and notifying the game if player was in game)
+fn close(registry: &Registry, mut connection: NetworkClient, events: &Sender<NetEvent>) {
+    registry.deregister(&mut connection.stream);
+    capture::record_close(connection.id);
+    if connection.conn_type == PLAY {
+        events.send(NetEvent::Left {
+            conn: connection.id,
+        });
+    }
+}
+
+impl NetworkWorker {
+    pub fn new(
+        incoming: Receiver<(usize, TcpStream)>,
+        commands: Receiver<NetCommand>,
+        events: Sender<NetEvent>,
+    ) -> io::Result<Self> {
+        let poll = Poll::new()?;
+        let waker = Arc::new(Waker::new(poll.registry(), WAKER)?);
+        Ok(Self {
+            poll,
+            waker,
+            connections: AHashMap::new(),
+            incoming,
+            commands,
+            events,
+        })
+    }
+
+    // Worker's loop(until server's shutdown)
+    pub fn run(mut self) -> io::Result<()> {
+        let mut events = Events::with_capacity(256);
+        loop {
+            // Checks whether it is necessary to shutdown the network server
+            if *SHUTDOWN_SERVER.lock().unwrap() {
+                for (_, connection) in self.connections.drain() {
+                    close(self.poll.registry(), connection, &self.events);
+                }
+                return Ok(());
+            }
+            // Waiting for events(waker wakes on new connections, commands and shutdown)
+            if let Err(err) = self.poll.poll(&mut events, None) {
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            for event in events.iter() {
+                match event.token() {
+                    WAKER => {
+                        self.accept_incoming();
+                        self.handle_commands();
+                    }
+                    token => {
+                        let done = match self.connections.get_mut(&token) {
+                            // Trying to handing(closing connection on error or broken packet)
+                            Some(connection) => {
+                                handle_event(connection, event, &self.events).unwrap_or(true)
+                            }
+                            None => false,
+                        };
+                        // If needs to close connection - removing from list, unregister and close connection's stream
+                        if done {
+                            if let Some(connection) = self.connections.remove(&token) {
+                                close(self.poll.registry(), connection, &self.events);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Registering new connections from acceptor
+    fn accept_incoming(&mut self) {
+        while let Ok((id, mut stream)) = self.incoming.try_recv() {
+            let token = Token(id);
+            if let Err(err) = self.poll.registry().register(
+                &mut stream,
+                token,
+                Interest::READABLE.add(Interest::WRITABLE),
+            ) {
+                error!("Failed to register connection #{}: {}", id, err);
+                capture::record_close(id);
+                continue;
+            }
+            self.connections
+                .insert(token, NetworkClient::new(id, stream));
+        }
+    }
+
+    // Handing commands from game's thread
+    fn handle_commands(&mut self) {
+        while let Ok(command) = self.commands.try_recv() {
+            let (id, disconnect) = match command {
+                NetCommand::Send(id, frame) => match self.connections.get_mut(&Token(id)) {
+                    Some(connection) => (id, connection.send(&frame).is_err()),
+                    None => continue,
+                },
+                NetCommand::Disconnect(id) => match self.connections.get_mut(&Token(id)) {
+                    // Closing after sending all waiting bytes
+                    Some(connection) => {
+                        connection.closing = true;
+                        (id, connection.flush().is_err() || connection.is_flushed())
+                    }
+                    None => continue,
+                },
+            };
+            if disconnect {
+                if let Some(connection) = self.connections.remove(&Token(id)) {
+                    close(self.poll.registry(), connection, &self.events);
+                }
+            }
+        }
+    }
+}
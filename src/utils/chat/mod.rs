@@ -23,6 +23,14 @@ impl ChatMessage {
             extra: vec![],
         }
     }
+    // Creating a component without replacing a formatting(text is shown as is)
+    pub fn raw(text: String) -> Self {
+        Self {
+            text,
+            bold: String::new(),
+            extra: vec![],
+        }
+    }
     // Updating bold style
     pub fn set_bold(&mut self, value: bool) {
         self.bold = value.to_string();
@@ -1,7 +1,16 @@
+use crate::game::commands::console_command;
+use crate::game::GAME_WORKS;
 use crate::network::{NET_SERVER_WORKS, SHUTDOWN_SERVER};
 use std::time::Duration;
 use std::{io, process, thread};
 
+// Waiting for shutdown network's server and saving the world
+fn wait_for_shutdown() {
+    while *NET_SERVER_WORKS.lock().unwrap() || *GAME_WORKS.lock().unwrap() {
+        thread::sleep(Duration::from_millis(25));
+    }
+}
+
 // Loop for handling input
 pub fn start_input_handler() -> std::io::Result<()> {
     // Input buffer
@@ -12,8 +21,11 @@ pub fn start_input_handler() -> std::io::Result<()> {
     loop {
         // Before write buffer we need to clear buffer
         inp.clear();
-        // Reading a line
-        stdin.read_line(&mut inp)?;
+        // Reading a line(closed input, like /dev/null, doesn't stop the server)
+        if stdin.read_line(&mut inp)? == 0 {
+            info!("Console's input is closed");
+            break;
+        }
         // Clearing input's buffer
         inp = inp.replace("\n", "");
         // Simple realisation of stop command, but in updates be removed from here in another place
@@ -21,23 +33,21 @@ pub fn start_input_handler() -> std::io::Result<()> {
             // Sending status to shutdown network server
             *SHUTDOWN_SERVER.lock().unwrap() = true;
             info!("Stopping server...");
-            // Running process killing in 6 secs if failed to common shutdown
+            // Running process killing in 6 secs if failed to common shutdown(but not while saving the world)
             thread::spawn(|| {
                 thread::sleep(Duration::from_secs(6));
-                process::exit(0);
-            });
-            // Waiting for shutdown network's server
-            loop {
-                if *NET_SERVER_WORKS.lock().unwrap() == true {
+                while *GAME_WORKS.lock().unwrap() {
                     thread::sleep(Duration::from_millis(25));
-                } else {
-                    break;
                 }
-            }
+                process::exit(0);
+            });
+            wait_for_shutdown();
             // Disabling the input
             return Ok(());
         }
-        // If it's not stop command - when display buffer, but in updates be removed
-        info!("Entered: {}", inp);
+        // Another commands run on game's thread
+        console_command(inp.trim());
     }
+    wait_for_shutdown();
+    Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::Color;
+use fern::colors::Color;
 use fern::colors::ColoredLevelConfig;
 use std::fs;
 
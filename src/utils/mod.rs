@@ -1,2 +1,4 @@
 pub mod chat;
 pub mod logger;
+pub mod nbt;
+pub mod uuid;
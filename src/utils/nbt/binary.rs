@@ -0,0 +1,329 @@
+use crate::network::proto::PacketWriter;
+use crate::utils::nbt::*;
+use crate::{SResult, SimpleError};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use std::io::{Read, Write};
+
+// Max depth of lists and compounds(as vanilla)
+const MAX_DEPTH: usize = 512;
+
+fn error(text: &str) -> SimpleError {
+    SimpleError(format!("Invalid NBT: {}", text), None)
+}
+
+// Reader of bytes' slice(PacketReader removes bytes from Vec's start,
+// which is too slow for big chunks and files)
+struct Input<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Input<'a> {
+    fn take(&mut self, len: usize) -> SResult<&'a [u8]> {
+        if self.data.len() - self.pos < len {
+            return Err(error("unexpected end of data"));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn get_u8(&mut self) -> SResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn get_i16(&mut self) -> SResult<i16> {
+        Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn get_i32(&mut self) -> SResult<i32> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn get_i64(&mut self) -> SResult<i64> {
+        Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    // Array's length(can't be bigger than remaining data)
+    fn get_length(&mut self, element_size: usize) -> SResult<usize> {
+        let len = self.get_i32()?;
+        if len < 0 || len as usize * element_size > self.data.len() - self.pos {
+            return Err(error("invalid length"));
+        }
+        Ok(len as usize)
+    }
+
+    // String( u16 as length and Modified UTF-8 )
+    fn get_string(&mut self) -> SResult<String> {
+        let len = u16::from_be_bytes(self.take(2)?.try_into().unwrap());
+        let bytes = self.take(len as usize)?;
+        // Most of strings are ASCII
+        match std::str::from_utf8(bytes) {
+            Ok(v) if !v.contains('\0') => Ok(String::from(v)),
+            _ => decode_mutf8(bytes),
+        }
+    }
+
+    // Tag's payload by type's id
+    fn get_tag(&mut self, id: u8, depth: usize) -> SResult<Tag> {
+        if depth > MAX_DEPTH {
+            return Err(error("too deep"));
+        }
+        Ok(match id {
+            TAG_BYTE => Tag::Byte(self.get_u8()? as i8),
+            TAG_SHORT => Tag::Short(self.get_i16()?),
+            TAG_INT => Tag::Int(self.get_i32()?),
+            TAG_LONG => Tag::Long(self.get_i64()?),
+            TAG_FLOAT => Tag::Float(f32::from_bits(self.get_i32()? as u32)),
+            TAG_DOUBLE => Tag::Double(f64::from_bits(self.get_i64()? as u64)),
+            TAG_BYTE_ARRAY => {
+                let len = self.get_length(1)?;
+                Tag::ByteArray(self.take(len)?.iter().map(|v| *v as i8).collect())
+            }
+            TAG_STRING => Tag::String(self.get_string()?),
+            TAG_LIST => {
+                let element = self.get_u8()?;
+                let len = self.get_length(0)?;
+                // Empty lists can have any type(even TAG_End)
+                if element == TAG_END && len > 0 {
+                    return Err(error("list of TAG_End"));
+                }
+                let mut list = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    list.push(self.get_tag(element, depth + 1)?);
+                }
+                Tag::List(list)
+            }
+            TAG_COMPOUND => {
+                let mut compound = Compound::new();
+                loop {
+                    let id = self.get_u8()?;
+                    if id == TAG_END {
+                        break;
+                    }
+                    let name = self.get_string()?;
+                    compound.insert(name, self.get_tag(id, depth + 1)?);
+                }
+                Tag::Compound(compound)
+            }
+            TAG_INT_ARRAY => {
+                let len = self.get_length(4)?;
+                let mut array = Vec::with_capacity(len);
+                for _ in 0..len {
+                    array.push(self.get_i32()?);
+                }
+                Tag::IntArray(array)
+            }
+            TAG_LONG_ARRAY => {
+                let len = self.get_length(8)?;
+                let mut array = Vec::with_capacity(len);
+                for _ in 0..len {
+                    array.push(self.get_i64()?);
+                }
+                Tag::LongArray(array)
+            }
+            _ => return Err(error(&format!("unknown tag's type {}", id))),
+        })
+    }
+}
+
+// Decoding Java's Modified UTF-8(zero as two bytes, supplementary characters as surrogate pairs)
+fn decode_mutf8(bytes: &[u8]) -> SResult<String> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i] as u16;
+        let (unit, size) = match byte {
+            0x01..=0x7F => (byte, 1),
+            0xC0..=0xDF if i + 1 < bytes.len() => {
+                (((byte & 0x1F) << 6) | (bytes[i + 1] as u16 & 0x3F), 2)
+            }
+            0xE0..=0xEF if i + 2 < bytes.len() => (
+                ((byte & 0x0F) << 12)
+                    | ((bytes[i + 1] as u16 & 0x3F) << 6)
+                    | (bytes[i + 2] as u16 & 0x3F),
+                3,
+            ),
+            _ => return Err(error("malformed string")),
+        };
+        units.push(unit);
+        i += size;
+    }
+    Ok(String::from_utf16_lossy(&units))
+}
+
+// Writing a string as u16 length and Modified UTF-8(too long strings are cut)
+fn write_string(p: &mut Vec<u8>, value: &str) {
+    let mut bytes = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x01..=0x7F => bytes.push(unit as u8),
+            0x00..=0x7FF => {
+                bytes.push(0xC0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+            _ => {
+                bytes.push(0xE0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3F) as u8);
+                bytes.push(0x80 | (unit & 0x3F) as u8);
+            }
+        }
+    }
+    if bytes.len() > u16::MAX as usize {
+        let mut len = u16::MAX as usize;
+        // Not cutting in the middle of character
+        while bytes[len] & 0xC0 == 0x80 {
+            len -= 1;
+        }
+        bytes.truncate(len);
+    }
+    p.write_u16(bytes.len() as u16);
+    p.extend_from_slice(&bytes);
+}
+
+// Writing tag's payload
+fn write_tag(p: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(v) => p.write_i8(*v),
+        Tag::Short(v) => p.write_i16(*v),
+        Tag::Int(v) => p.write_i32(*v),
+        Tag::Long(v) => p.write_i64(*v),
+        Tag::Float(v) => p.write_f32(*v),
+        Tag::Double(v) => p.write_f64(*v),
+        Tag::ByteArray(v) => {
+            p.write_i32(v.len() as i32);
+            p.extend(v.iter().map(|v| *v as u8));
+        }
+        Tag::String(v) => write_string(p, v),
+        Tag::List(v) => {
+            // Empty list has TAG_End's type
+            p.write_u8(v.first().map(|v| v.id()).unwrap_or(TAG_END));
+            p.write_i32(v.len() as i32);
+            for element in v {
+                write_tag(p, element);
+            }
+        }
+        Tag::Compound(v) => write_compound(p, v),
+        Tag::IntArray(v) => {
+            p.write_i32(v.len() as i32);
+            for element in v {
+                p.write_i32(*element);
+            }
+        }
+        Tag::LongArray(v) => {
+            p.write_i32(v.len() as i32);
+            for element in v {
+                p.write_i64(*element);
+            }
+        }
+    }
+}
+
+// Writing compound's named tags and TAG_End
+fn write_compound(p: &mut Vec<u8>, compound: &Compound) {
+    for (name, tag) in compound {
+        p.write_u8(tag.id());
+        write_string(p, name);
+        write_tag(p, tag);
+    }
+    p.write_u8(TAG_END);
+}
+
+/// Reading a root compound with its name from bytes' start(TAG_End instead of root - None)
+pub fn read_nbt(p: &mut Vec<u8>) -> SResult<Option<(String, Compound)>> {
+    let mut input = Input { data: p, pos: 0 };
+    let result = match input.get_u8()? {
+        TAG_END => None,
+        TAG_COMPOUND => {
+            let name = input.get_string()?;
+            match input.get_tag(TAG_COMPOUND, 0)? {
+                Tag::Compound(v) => Some((name, v)),
+                _ => unreachable!(),
+            }
+        }
+        _ => return Err(error("root isn't a compound")),
+    };
+    // Removing read bytes
+    let len = input.pos;
+    p.drain(..len);
+    Ok(result)
+}
+
+/// Writing a named root compound
+pub fn write_nbt(p: &mut Vec<u8>, name: &str, root: &Compound) {
+    p.write_u8(TAG_COMPOUND);
+    write_string(p, name);
+    write_compound(p, root);
+}
+
+// Reading a root compound from whole decompressed data
+fn read_root(mut data: Vec<u8>) -> SResult<Compound> {
+    match read_nbt(&mut data)? {
+        Some((_, v)) => Ok(v),
+        None => Err(error("no root compound")),
+    }
+}
+
+// Decompressing all data from decoder
+fn decompress(mut decoder: impl Read) -> SResult<Vec<u8>> {
+    let mut data = Vec::new();
+    match decoder.read_to_end(&mut data) {
+        Ok(_) => Ok(data),
+        Err(err) => Err(SimpleError(
+            String::from("Failed to decompress NBT"),
+            Some(err),
+        )),
+    }
+}
+
+/// Reading a root compound from gzip compressed data(like level.dat)
+pub fn read_gzip(data: &[u8]) -> SResult<Compound> {
+    read_root(decompress(GzDecoder::new(data))?)
+}
+
+/// Reading a root compound from zlib compressed data(like chunks in region's files)
+pub fn read_zlib(data: &[u8]) -> SResult<Compound> {
+    read_root(decompress(ZlibDecoder::new(data))?)
+}
+
+/// Reading a root compound from gzip, zlib or uncompressed data(detected by header)
+pub fn read_compressed(data: &[u8]) -> SResult<Compound> {
+    match data {
+        [0x1F, 0x8B, ..] => read_gzip(data),
+        [0x78, ..] => read_zlib(data),
+        _ => read_root(data.to_vec()),
+    }
+}
+
+// Finishing compression
+fn compress(mut encoder: impl Write, root: &Compound) -> SResult<()> {
+    let mut data = Vec::new();
+    write_nbt(&mut data, "", root);
+    match encoder.write_all(&data) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(SimpleError(
+            String::from("Failed to compress NBT"),
+            Some(err),
+        )),
+    }
+}
+
+/// Writing a root compound(without name) with gzip compression
+pub fn write_gzip(root: &Compound) -> SResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    compress(&mut encoder, root)?;
+    encoder
+        .finish()
+        .map_err(|err| SimpleError(String::from("Failed to compress NBT"), Some(err)))
+}
+
+/// Writing a root compound(without name) with zlib compression
+pub fn write_zlib(root: &Compound) -> SResult<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    compress(&mut encoder, root)?;
+    encoder
+        .finish()
+        .map_err(|err| SimpleError(String::from("Failed to compress NBT"), Some(err)))
+}
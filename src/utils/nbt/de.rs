@@ -0,0 +1,387 @@
+use crate::utils::nbt::{Compound, Tag, BYTE_ARRAY_TOKEN, INT_ARRAY_TOKEN, LONG_ARRAY_TOKEN};
+use crate::{SResult, SimpleError};
+use serde::de::value::StringDeserializer;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use std::fmt::{self, Display};
+
+impl de::Error for SimpleError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SimpleError(msg.to_string(), None)
+    }
+}
+
+/// Converting a tag to value(compounds become structs or maps, lists and arrays - sequences)
+pub fn from_tag<T: DeserializeOwned>(tag: Tag) -> SResult<T> {
+    T::deserialize(Deserializer(tag))
+}
+
+/// Converting a compound to value(like root of NBT's file to struct)
+pub fn from_compound<T: DeserializeOwned>(compound: Compound) -> SResult<T> {
+    from_tag(Tag::Compound(compound))
+}
+
+// Deserializer from owned tag
+struct Deserializer(Tag);
+
+// Array's elements as tags for sequences
+fn into_list(tag: Tag) -> Option<Vec<Tag>> {
+    Some(match tag {
+        Tag::List(v) => v,
+        Tag::ByteArray(v) => v.into_iter().map(Tag::Byte).collect(),
+        Tag::IntArray(v) => v.into_iter().map(Tag::Int).collect(),
+        Tag::LongArray(v) => v.into_iter().map(Tag::Long).collect(),
+        _ => return None,
+    })
+}
+
+fn unexpected(tag: &Tag, expected: &str) -> SimpleError {
+    SimpleError(
+        format!(
+            "Failed to deserialize NBT: expected {}, found {}",
+            expected, tag
+        ),
+        None,
+    )
+}
+
+// Unsigned numbers are read from signed of the same size
+macro_rules! deserialize_unsigned {
+    ($($method:ident => $visit:ident($ty:ty as $signed:ty)),*) => {
+        $(fn $method<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+            match self.0.as_i64() {
+                Some(v) => visitor.$visit(v as $signed as $ty),
+                None => Err(unexpected(&self.0, "number")),
+            }
+        })*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = SimpleError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        match self.0 {
+            Tag::Byte(v) => visitor.visit_i8(v),
+            Tag::Short(v) => visitor.visit_i16(v),
+            Tag::Int(v) => visitor.visit_i32(v),
+            Tag::Long(v) => visitor.visit_i64(v),
+            Tag::Float(v) => visitor.visit_f32(v),
+            Tag::Double(v) => visitor.visit_f64(v),
+            Tag::String(v) => visitor.visit_string(v),
+            Tag::List(v) => visitor.visit_seq(SeqAccess(v.into_iter())),
+            Tag::Compound(v) => visitor.visit_map(MapAccess {
+                entries: v.into_iter(),
+                value: None,
+            }),
+            // Arrays are maps with single special key, so Tag knows array's type
+            array => {
+                let token = match array {
+                    Tag::ByteArray(_) => BYTE_ARRAY_TOKEN,
+                    Tag::IntArray(_) => INT_ARRAY_TOKEN,
+                    _ => LONG_ARRAY_TOKEN,
+                };
+                let mut compound = Compound::new();
+                compound.insert(String::from(token), Tag::List(into_list(array).unwrap()));
+                visitor.visit_map(MapAccess {
+                    entries: compound.into_iter(),
+                    value: None,
+                })
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        match self.0.as_bool() {
+            Some(v) => visitor.visit_bool(v),
+            None => Err(unexpected(&self.0, "byte")),
+        }
+    }
+
+    deserialize_unsigned!(
+        deserialize_u8 => visit_u8(u8 as i8),
+        deserialize_u16 => visit_u16(u16 as i16),
+        deserialize_u32 => visit_u32(u32 as i32),
+        deserialize_u64 => visit_u64(u64 as i64)
+    );
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        match self.0 {
+            Tag::ByteArray(v) => visitor.visit_byte_buf(v.into_iter().map(|v| v as u8).collect()),
+            tag => Deserializer(tag).deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    // Missing fields are None, so existing tag is always Some
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> SResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> SResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        match into_list(self.0) {
+            Some(v) => visitor.visit_seq(SeqAccess(v.into_iter())),
+            None => Err(de::Error::custom(
+                "Failed to deserialize NBT: expected list",
+            )),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> SResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> SResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        match self.0 {
+            Tag::Compound(v) => visitor.visit_map(MapAccess {
+                entries: v.into_iter(),
+                value: None,
+            }),
+            tag => Err(unexpected(&tag, "compound")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> SResult<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    // Unit variants are strings, another - compounds with single variant's key
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> SResult<V::Value> {
+        match self.0 {
+            Tag::String(v) => visitor.visit_enum(v.into_deserializer()),
+            Tag::Compound(v) if v.len() == 1 => {
+                let (variant, value) = v.into_iter().next().unwrap();
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            tag => Err(unexpected(&tag, "enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> SResult<V::Value> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u128 f32 f64 char str string identifier
+    }
+}
+
+// List's elements
+struct SeqAccess(std::vec::IntoIter<Tag>);
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = SimpleError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> SResult<Option<T::Value>> {
+        match self.0.next() {
+            Some(v) => seed.deserialize(Deserializer(v)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+// Compound's entries
+struct MapAccess {
+    entries: std::collections::btree_map::IntoIter<String, Tag>,
+    // Value of the last key
+    value: Option<Tag>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = SimpleError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> SResult<Option<K::Value>> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                let key: StringDeserializer<SimpleError> = key.into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> SResult<V::Value> {
+        match self.value.take() {
+            Some(v) => seed.deserialize(Deserializer(v)),
+            None => Err(de::Error::custom(
+                "Failed to deserialize NBT: value without key",
+            )),
+        }
+    }
+}
+
+// Enum's variant with value
+struct EnumAccess {
+    variant: String,
+    value: Tag,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = SimpleError;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> SResult<(V::Value, Deserializer)> {
+        let variant: StringDeserializer<SimpleError> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, Deserializer(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = SimpleError;
+
+    fn unit_variant(self) -> SResult<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> SResult<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> SResult<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> SResult<V::Value> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+// Tag is deserialized from any self-describing format(and from itself)
+impl<'de> de::Deserialize<'de> for Tag {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TagVisitor)
+    }
+}
+
+struct TagVisitor;
+
+impl<'de> Visitor<'de> for TagVisitor {
+    type Value = Tag;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("NBT's tag")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Tag, E> {
+        Ok(Tag::Byte(v as i8))
+    }
+
+    fn visit_i8<E>(self, v: i8) -> Result<Tag, E> {
+        Ok(Tag::Byte(v))
+    }
+
+    fn visit_i16<E>(self, v: i16) -> Result<Tag, E> {
+        Ok(Tag::Short(v))
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<Tag, E> {
+        Ok(Tag::Int(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Tag, E> {
+        Ok(Tag::Long(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Tag, E> {
+        Ok(Tag::Long(v as i64))
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<Tag, E> {
+        Ok(Tag::Float(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Tag, E> {
+        Ok(Tag::Double(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Tag, E> {
+        Ok(Tag::String(String::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Tag, E> {
+        Ok(Tag::String(v))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Tag, A::Error> {
+        let mut list = Vec::new();
+        while let Some(v) = seq.next_element()? {
+            list.push(v);
+        }
+        Ok(Tag::List(list))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Tag, A::Error> {
+        let mut compound = Compound::new();
+        while let Some(key) = map.next_key::<String>()? {
+            // Array from NBT's deserializer
+            let array = match key.as_str() {
+                BYTE_ARRAY_TOKEN => Some(Tag::ByteArray(map.next_value()?)),
+                INT_ARRAY_TOKEN => Some(Tag::IntArray(map.next_value()?)),
+                LONG_ARRAY_TOKEN => Some(Tag::LongArray(map.next_value()?)),
+                _ => None,
+            };
+            match array {
+                Some(v) if compound.is_empty() => return Ok(v),
+                Some(v) => compound.insert(key, v),
+                None => compound.insert(key, map.next_value()?),
+            };
+        }
+        Ok(Tag::Compound(compound))
+    }
+}
@@ -0,0 +1,212 @@
+mod binary;
+mod de;
+mod ser;
+mod snbt;
+
+pub use binary::{
+    read_compressed, read_gzip, read_nbt, read_zlib, write_gzip, write_nbt, write_zlib,
+};
+pub use de::{from_compound, from_tag};
+pub use ser::{to_compound, to_tag};
+pub use snbt::parse_snbt;
+use std::collections::BTreeMap;
+
+// Tag's types ids
+pub const TAG_END: u8 = 0;
+pub const TAG_BYTE: u8 = 1;
+pub const TAG_SHORT: u8 = 2;
+pub const TAG_INT: u8 = 3;
+pub const TAG_LONG: u8 = 4;
+pub const TAG_FLOAT: u8 = 5;
+pub const TAG_DOUBLE: u8 = 6;
+pub const TAG_BYTE_ARRAY: u8 = 7;
+pub const TAG_STRING: u8 = 8;
+pub const TAG_LIST: u8 = 9;
+pub const TAG_COMPOUND: u8 = 10;
+pub const TAG_INT_ARRAY: u8 = 11;
+pub const TAG_LONG_ARRAY: u8 = 12;
+
+// Names of newtype structs which are serialized as arrays(not lists)
+const BYTE_ARRAY_TOKEN: &str = "__nbt_byte_array";
+const INT_ARRAY_TOKEN: &str = "__nbt_int_array";
+const LONG_ARRAY_TOKEN: &str = "__nbt_long_array";
+
+/// Named tags(sorted by names)
+pub type Compound = BTreeMap<String, Tag>;
+
+/// [NBT](https://wiki.vg/NBT)'s tag
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    // All elements have the same type
+    List(Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    // Tag's type id
+    pub fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => TAG_BYTE,
+            Tag::Short(_) => TAG_SHORT,
+            Tag::Int(_) => TAG_INT,
+            Tag::Long(_) => TAG_LONG,
+            Tag::Float(_) => TAG_FLOAT,
+            Tag::Double(_) => TAG_DOUBLE,
+            Tag::ByteArray(_) => TAG_BYTE_ARRAY,
+            Tag::String(_) => TAG_STRING,
+            Tag::List(_) => TAG_LIST,
+            Tag::Compound(_) => TAG_COMPOUND,
+            Tag::IntArray(_) => TAG_INT_ARRAY,
+            Tag::LongArray(_) => TAG_LONG_ARRAY,
+        }
+    }
+
+    // Any number as i64(None if tag isn't a number)
+    pub fn as_i64(&self) -> Option<i64> {
+        Some(match self {
+            Tag::Byte(v) => *v as i64,
+            Tag::Short(v) => *v as i64,
+            Tag::Int(v) => *v as i64,
+            Tag::Long(v) => *v,
+            Tag::Float(v) => *v as i64,
+            Tag::Double(v) => *v as i64,
+            _ => return None,
+        })
+    }
+
+    // Any number as f64(None if tag isn't a number)
+    pub fn as_f64(&self) -> Option<f64> {
+        Some(match self {
+            Tag::Float(v) => *v as f64,
+            Tag::Double(v) => *v,
+            _ => self.as_i64()? as f64,
+        })
+    }
+
+    // Number casted to smaller types like vanilla does
+    pub fn as_i8(&self) -> Option<i8> {
+        self.as_i64().map(|v| v as i8)
+    }
+
+    pub fn as_i16(&self) -> Option<i16> {
+        self.as_i64().map(|v| v as i16)
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_i64().map(|v| v as i32)
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        self.as_f64().map(|v| v as f32)
+    }
+
+    // Byte as bool(0 - false, another - true)
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_i64().map(|v| v != 0)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&Vec<Tag>> {
+        match self {
+            Tag::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Tag::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound_mut(&mut self) -> Option<&mut Compound> {
+        match self {
+            Tag::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+// Creating tags from Rust's values
+macro_rules! tag_from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(impl From<$ty> for Tag {
+            fn from(value: $ty) -> Self {
+                Tag::$variant(value)
+            }
+        })*
+    };
+}
+
+tag_from!(
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    String => String,
+    Vec<Tag> => List,
+    Compound => Compound
+);
+
+impl From<bool> for Tag {
+    fn from(value: bool) -> Self {
+        Tag::Byte(value as i8)
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(value: &str) -> Self {
+        Tag::String(String::from(value))
+    }
+}
+
+// Wrappers for serde which are serialized as NBT's arrays instead of lists
+macro_rules! nbt_array {
+    ($($(#[$doc:meta])* $name:ident($ty:ty) => $token:ident),*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, PartialEq, Default)]
+            pub struct $name(pub Vec<$ty>);
+
+            impl serde::Serialize for $name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_newtype_struct($token, &self.0)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Vec::<$ty>::deserialize(deserializer).map($name)
+                }
+            }
+        )*
+    };
+}
+
+nbt_array!(
+    /// Byte array for serde(TAG_Byte_Array instead of list of bytes)
+    ByteArray(i8) => BYTE_ARRAY_TOKEN,
+    /// Int array for serde(TAG_Int_Array instead of list of ints)
+    IntArray(i32) => INT_ARRAY_TOKEN,
+    /// Long array for serde(TAG_Long_Array instead of list of longs)
+    LongArray(i64) => LONG_ARRAY_TOKEN
+);
@@ -0,0 +1,426 @@
+use crate::utils::nbt::{Compound, Tag, BYTE_ARRAY_TOKEN, INT_ARRAY_TOKEN, LONG_ARRAY_TOKEN};
+use crate::{SResult, SimpleError};
+use serde::ser::{self, Serialize};
+use std::fmt::Display;
+
+impl ser::Error for SimpleError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SimpleError(msg.to_string(), None)
+    }
+}
+
+fn error(text: &str) -> SimpleError {
+    SimpleError(format!("Failed to serialize NBT: {}", text), None)
+}
+
+/// Converting a value to tag(structs and maps become compounds, sequences - lists)
+pub fn to_tag<T: Serialize + ?Sized>(value: &T) -> SResult<Tag> {
+    value
+        .serialize(Serializer)?
+        .ok_or_else(|| error("value is None"))
+}
+
+/// Converting a value to compound(like struct to root of NBT's file)
+pub fn to_compound<T: Serialize + ?Sized>(value: &T) -> SResult<Compound> {
+    match to_tag(value)? {
+        Tag::Compound(v) => Ok(v),
+        _ => Err(error("value isn't a compound")),
+    }
+}
+
+// Wrapping a tag into compound with variant's name(like serde_json does)
+fn variant(name: &str, tag: Tag) -> Tag {
+    let mut compound = Compound::new();
+    compound.insert(String::from(name), tag);
+    Tag::Compound(compound)
+}
+
+// Converting list of numbers from wrappers into array
+fn to_array(token: &str, tag: Tag) -> SResult<Tag> {
+    let list = match tag {
+        Tag::List(v) => v,
+        _ => return Err(error("array isn't a sequence")),
+    };
+    let numbers = list.iter().map(|v| {
+        v.as_i64()
+            .ok_or_else(|| error("array's element isn't a number"))
+    });
+    Ok(match token {
+        BYTE_ARRAY_TOKEN => Tag::ByteArray(
+            numbers
+                .map(|v| v.map(|v| v as i8))
+                .collect::<SResult<_>>()?,
+        ),
+        INT_ARRAY_TOKEN => Tag::IntArray(
+            numbers
+                .map(|v| v.map(|v| v as i32))
+                .collect::<SResult<_>>()?,
+        ),
+        _ => Tag::LongArray(numbers.collect::<SResult<_>>()?),
+    })
+}
+
+// Serializer to tag(None is returned for Option::None and fields with it are skipped)
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = ListSerializer;
+    type SerializeMap = CompoundSerializer;
+    type SerializeStruct = CompoundSerializer;
+    type SerializeStructVariant = CompoundSerializer;
+
+    fn serialize_bool(self, v: bool) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Byte(v as i8)))
+    }
+
+    fn serialize_i8(self, v: i8) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Byte(v)))
+    }
+
+    fn serialize_i16(self, v: i16) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Short(v)))
+    }
+
+    fn serialize_i32(self, v: i32) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Int(v)))
+    }
+
+    fn serialize_i64(self, v: i64) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Long(v)))
+    }
+
+    // Unsigned numbers are stored as signed of the same size
+    fn serialize_u8(self, v: u8) -> SResult<Option<Tag>> {
+        self.serialize_i8(v as i8)
+    }
+
+    fn serialize_u16(self, v: u16) -> SResult<Option<Tag>> {
+        self.serialize_i16(v as i16)
+    }
+
+    fn serialize_u32(self, v: u32) -> SResult<Option<Tag>> {
+        self.serialize_i32(v as i32)
+    }
+
+    fn serialize_u64(self, v: u64) -> SResult<Option<Tag>> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_f32(self, v: f32) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Float(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Double(v)))
+    }
+
+    fn serialize_char(self, v: char) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::String(v.to_string())))
+    }
+
+    fn serialize_str(self, v: &str) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::String(String::from(v))))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::ByteArray(v.iter().map(|v| *v as i8).collect())))
+    }
+
+    fn serialize_none(self) -> SResult<Option<Tag>> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> SResult<Option<Tag>> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> SResult<Option<Tag>> {
+        Ok(Some(Tag::Compound(Compound::new())))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> SResult<Option<Tag>> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> SResult<Option<Tag>> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> SResult<Option<Tag>> {
+        let tag = value.serialize(self)?;
+        match name {
+            BYTE_ARRAY_TOKEN | INT_ARRAY_TOKEN | LONG_ARRAY_TOKEN => match tag {
+                Some(tag) => to_array(name, tag).map(Some),
+                None => Ok(None),
+            },
+            _ => Ok(tag),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        name: &'static str,
+        value: &T,
+    ) -> SResult<Option<Tag>> {
+        Ok(value.serialize(self)?.map(|v| variant(name, v)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> SResult<ListSerializer> {
+        Ok(ListSerializer {
+            list: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> SResult<ListSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> SResult<ListSerializer> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        name: &'static str,
+        len: usize,
+    ) -> SResult<ListSerializer> {
+        Ok(ListSerializer {
+            list: Vec::with_capacity(len),
+            variant: Some(name),
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> SResult<CompoundSerializer> {
+        Ok(CompoundSerializer {
+            compound: Compound::new(),
+            key: None,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> SResult<CompoundSerializer> {
+        self.serialize_map(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        name: &'static str,
+        _: usize,
+    ) -> SResult<CompoundSerializer> {
+        Ok(CompoundSerializer {
+            compound: Compound::new(),
+            key: None,
+            variant: Some(name),
+        })
+    }
+}
+
+// Sequence's serializer(all elements must have the same type)
+struct ListSerializer {
+    list: Vec<Tag>,
+    variant: Option<&'static str>,
+}
+
+impl ListSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        let tag = match value.serialize(Serializer)? {
+            Some(v) => v,
+            None => return Err(error("list can't contain None")),
+        };
+        if let Some(first) = self.list.first() {
+            if first.id() != tag.id() {
+                return Err(error("list's elements have different types"));
+            }
+        }
+        self.list.push(tag);
+        Ok(())
+    }
+
+    fn finish(self) -> SResult<Option<Tag>> {
+        let list = Tag::List(self.list);
+        Ok(Some(match self.variant {
+            Some(name) => variant(name, list),
+            None => list,
+        }))
+    }
+}
+
+impl ser::SerializeSeq for ListSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for ListSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+// Map's and struct's serializer(keys must be strings or numbers)
+struct CompoundSerializer {
+    compound: Compound,
+    // Map's key waiting for value
+    key: Option<String>,
+    variant: Option<&'static str>,
+}
+
+impl CompoundSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> SResult<()> {
+        // Fields with None are skipped
+        if let Some(tag) = value.serialize(Serializer)? {
+            self.compound.insert(key, tag);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> SResult<Option<Tag>> {
+        let compound = Tag::Compound(self.compound);
+        Ok(Some(match self.variant {
+            Some(name) => variant(name, compound),
+            None => compound,
+        }))
+    }
+}
+
+impl ser::SerializeMap for CompoundSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> SResult<()> {
+        self.key = Some(match key.serialize(Serializer)? {
+            Some(Tag::String(v)) => v,
+            Some(v) if v.as_i64().is_some() && !matches!(v, Tag::Float(_) | Tag::Double(_)) => {
+                v.as_i64().unwrap().to_string()
+            }
+            _ => return Err(error("compound's key isn't a string")),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> SResult<()> {
+        match self.key.take() {
+            Some(key) => self.insert(key, value),
+            None => Err(error("compound's value without key")),
+        }
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for CompoundSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SResult<()> {
+        self.insert(String::from(key), value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for CompoundSerializer {
+    type Ok = Option<Tag>;
+    type Error = SimpleError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> SResult<()> {
+        self.insert(String::from(key), value)
+    }
+
+    fn end(self) -> SResult<Option<Tag>> {
+        self.finish()
+    }
+}
+
+// Tag is serialized as itself(so it can be a field of serialized struct)
+impl Serialize for Tag {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Tag::Byte(v) => serializer.serialize_i8(*v),
+            Tag::Short(v) => serializer.serialize_i16(*v),
+            Tag::Int(v) => serializer.serialize_i32(*v),
+            Tag::Long(v) => serializer.serialize_i64(*v),
+            Tag::Float(v) => serializer.serialize_f32(*v),
+            Tag::Double(v) => serializer.serialize_f64(*v),
+            Tag::ByteArray(v) => serializer.serialize_newtype_struct(BYTE_ARRAY_TOKEN, v),
+            Tag::String(v) => serializer.serialize_str(v),
+            Tag::List(v) => v.serialize(serializer),
+            Tag::Compound(v) => v.serialize(serializer),
+            Tag::IntArray(v) => serializer.serialize_newtype_struct(INT_ARRAY_TOKEN, v),
+            Tag::LongArray(v) => serializer.serialize_newtype_struct(LONG_ARRAY_TOKEN, v),
+        }
+    }
+}
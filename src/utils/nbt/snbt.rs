@@ -0,0 +1,325 @@
+use crate::utils::nbt::{Compound, Tag};
+use crate::{SResult, SimpleError};
+use std::fmt;
+
+// Characters of strings which can be written without quotes
+fn is_unquoted(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+// Writing a string(quoted if needed, including strings which would be read as numbers or booleans)
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    if !value.is_empty()
+        && value.chars().all(is_unquoted)
+        && matches!(infer(value.to_string()), Tag::String(_))
+    {
+        return f.write_str(value);
+    }
+    f.write_str("\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    f.write_str("\"")
+}
+
+// Writing a float with suffix(NaN and infinity are written by names)
+fn write_float(f: &mut fmt::Formatter, value: f64, suffix: &str) -> fmt::Result {
+    match value {
+        _ if value.is_nan() => write!(f, "NaN{}", suffix),
+        _ if value.is_infinite() && value > 0.0 => write!(f, "Infinity{}", suffix),
+        _ if value.is_infinite() => write!(f, "-Infinity{}", suffix),
+        // Debug's format always has a dot or exponent
+        _ => write!(f, "{:?}{}", value, suffix),
+    }
+}
+
+// Writing an array like [I;1,2,3]
+fn write_array<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    prefix: &str,
+    suffix: &str,
+    values: &[T],
+) -> fmt::Result {
+    write!(f, "[{};", prefix)?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{}{}", value, suffix)?;
+    }
+    f.write_str("]")
+}
+
+// Printing a tag as SNBT(Stringified NBT, like in commands)
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tag::Byte(v) => write!(f, "{}b", v),
+            Tag::Short(v) => write!(f, "{}s", v),
+            Tag::Int(v) => write!(f, "{}", v),
+            Tag::Long(v) => write!(f, "{}L", v),
+            Tag::Float(v) if v.is_finite() => write!(f, "{:?}f", v),
+            Tag::Float(v) => write_float(f, *v as f64, "f"),
+            Tag::Double(v) => write_float(f, *v, "d"),
+            Tag::ByteArray(v) => write_array(f, "B", "b", v),
+            Tag::String(v) => write_string(f, v),
+            Tag::List(v) => {
+                f.write_str("[")?;
+                for (i, value) in v.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Tag::Compound(v) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in v.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+            Tag::IntArray(v) => write_array(f, "I", "", v),
+            Tag::LongArray(v) => write_array(f, "L", "L", v),
+        }
+    }
+}
+
+// SNBT's parser
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, text: &str) -> SimpleError {
+        SimpleError(format!("Invalid SNBT at {}: {}", self.pos, text), None)
+    }
+
+    // Next character after whitespaces
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.text[self.pos..];
+        let trimmed = rest.trim_start();
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    // Skipping expected character
+    fn expect(&mut self, c: char) -> SResult<()> {
+        if self.peek() != Some(c) {
+            return Err(self.error(&format!("expected '{}'", c)));
+        }
+        self.pos += c.len_utf8();
+        Ok(())
+    }
+
+    // Skipping the character if it's next
+    fn accept(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            return true;
+        }
+        false
+    }
+
+    // Quoted or unquoted string(returns the string and whether it was quoted)
+    fn string(&mut self) -> SResult<(String, bool)> {
+        let quote = match self.peek() {
+            Some(c @ ('"' | '\'')) => c,
+            _ => {
+                let rest = &self.text[self.pos..];
+                let len = rest.find(|c| !is_unquoted(c)).unwrap_or(rest.len());
+                if len == 0 {
+                    return Err(self.error("expected a value"));
+                }
+                self.pos += len;
+                return Ok((String::from(&rest[..len]), false));
+            }
+        };
+        self.pos += 1;
+        let mut value = String::new();
+        let mut escaped = false;
+        for (i, c) in self.text[self.pos..].char_indices() {
+            if escaped {
+                if c != quote && c != '\\' {
+                    return Err(self.error(&format!("invalid escape '\\{}'", c)));
+                }
+                value.push(c);
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                self.pos += i + 1;
+                return Ok((value, true));
+            } else {
+                value.push(c);
+            }
+        }
+        Err(self.error("unclosed string"))
+    }
+
+    fn value(&mut self, depth: usize) -> SResult<Tag> {
+        if depth > 512 {
+            return Err(self.error("too deep"));
+        }
+        match self.peek() {
+            Some('{') => self.compound(depth).map(Tag::Compound),
+            Some('[') => self.list(depth),
+            _ => {
+                let (value, quoted) = self.string()?;
+                Ok(if quoted {
+                    Tag::String(value)
+                } else {
+                    infer(value)
+                })
+            }
+        }
+    }
+
+    fn compound(&mut self, depth: usize) -> SResult<Compound> {
+        self.expect('{')?;
+        let mut compound = Compound::new();
+        if self.accept('}') {
+            return Ok(compound);
+        }
+        loop {
+            let (name, _) = self.string()?;
+            self.expect(':')?;
+            compound.insert(name, self.value(depth + 1)?);
+            if !self.accept(',') {
+                break;
+            }
+        }
+        self.expect('}')?;
+        Ok(compound)
+    }
+
+    fn list(&mut self, depth: usize) -> SResult<Tag> {
+        self.expect('[')?;
+        // Arrays have type's prefix like [I;1,2,3]
+        let rest = &self.text[self.pos..];
+        for (prefix, array) in [("B;", 'B'), ("I;", 'I'), ("L;", 'L')] {
+            if rest.starts_with(prefix) {
+                self.pos += 2;
+                return self.array(array, depth);
+            }
+        }
+        let mut list = Vec::new();
+        if self.accept(']') {
+            return Ok(Tag::List(list));
+        }
+        loop {
+            let value = self.value(depth + 1)?;
+            if list.first().map(|v: &Tag| v.id() != value.id()) == Some(true) {
+                return Err(self.error("list's elements have different types"));
+            }
+            list.push(value);
+            if !self.accept(',') {
+                break;
+            }
+        }
+        self.expect(']')?;
+        Ok(Tag::List(list))
+    }
+
+    fn array(&mut self, array: char, depth: usize) -> SResult<Tag> {
+        let mut values = Vec::new();
+        if !self.accept(']') {
+            loop {
+                values.push(self.value(depth + 1)?);
+                if !self.accept(',') {
+                    break;
+                }
+            }
+            self.expect(']')?;
+        }
+        let invalid = || self.error("array's element has wrong type");
+        Ok(match array {
+            'B' => Tag::ByteArray(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Tag::Byte(v) => Ok(*v),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<SResult<_>>()?,
+            ),
+            'I' => Tag::IntArray(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Tag::Int(v) => Ok(*v),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<SResult<_>>()?,
+            ),
+            _ => Tag::LongArray(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Tag::Long(v) => Ok(*v),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<SResult<_>>()?,
+            ),
+        })
+    }
+}
+
+// Checking that text is a number(Rust also parses words like "inf" and "NaN")
+fn is_number(text: &str, float: bool) -> bool {
+    // Not finite floats
+    if float && matches!(text, "NaN" | "Infinity" | "-Infinity") {
+        return true;
+    }
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if !digits.starts_with(|c: char| c.is_ascii_digit() || (float && c == '.')) {
+        return false;
+    }
+    digits
+        .chars()
+        .all(|c| c.is_ascii_digit() || (float && matches!(c, '.' | 'e' | 'E' | '-' | '+')))
+}
+
+// Type of unquoted value by its suffix and format(not numbers are strings)
+fn infer(value: String) -> Tag {
+    match value.as_str() {
+        "true" => return Tag::Byte(1),
+        "false" => return Tag::Byte(0),
+        _ => {}
+    }
+    let (number, suffix) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&value[..i], Some(c.to_ascii_lowercase())),
+        _ => (value.as_str(), None),
+    };
+    let tag = match suffix {
+        Some('b') if is_number(number, false) => number.parse().ok().map(Tag::Byte),
+        Some('s') if is_number(number, false) => number.parse().ok().map(Tag::Short),
+        Some('l') if is_number(number, false) => number.parse().ok().map(Tag::Long),
+        Some('f') if is_number(number, true) => number.parse().ok().map(Tag::Float),
+        Some('d') if is_number(number, true) => number.parse().ok().map(Tag::Double),
+        None if is_number(number, false) => number.parse().ok().map(Tag::Int),
+        // Numbers with dot or exponent without suffix are doubles
+        None if is_number(number, true) => number.parse().ok().map(Tag::Double),
+        _ => None,
+    };
+    tag.unwrap_or(Tag::String(value))
+}
+
+/// Parsing a SNBT(Stringified NBT, like {id:"minecraft:stone",Count:1b})
+pub fn parse_snbt(text: &str) -> SResult<Tag> {
+    let mut parser = Parser { text, pos: 0 };
+    let tag = parser.value(0)?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected data after value"));
+    }
+    Ok(tag)
+}
@@ -0,0 +1,52 @@
+use crate::world::generator::noise::Random;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Counter mixed with time, so UUIDs created at the same time differ
+static UUID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Offline player's UUID(version 3 from "OfflinePlayer:<name>") as vanilla server does
+pub fn offline_uuid(name: &str) -> u128 {
+    let mut bytes = md5::compute(format!("OfflinePlayer:{}", name)).0;
+    // Setting version(3) and variant(IETF)
+    bytes[6] = bytes[6] & 0x0f | 0x30;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    u128::from_be_bytes(bytes)
+}
+
+// Random UUID(version 4) for entities which aren't players
+pub fn random_uuid() -> u128 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_nanos() as u64)
+        .unwrap_or(0);
+    let seed = nanos ^ UUID_COUNTER.fetch_add(1, Ordering::Relaxed).rotate_left(32);
+    let mut random = Random::new(seed as i64);
+    let mut bytes = ((random.next_u64() as u128) << 64 | random.next_u64() as u128).to_be_bytes();
+    // Setting version(4) and variant(IETF)
+    bytes[6] = bytes[6] & 0x0f | 0x40;
+    bytes[8] = bytes[8] & 0x3f | 0x80;
+    u128::from_be_bytes(bytes)
+}
+
+// UUID as string with hyphens(like 069a79f4-44e9-4726-a5be-fca90e38aaf5)
+pub fn uuid_to_string(uuid: u128) -> String {
+    let hex = format!("{:032x}", uuid);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+// Parsing UUID from string(with or without hyphens)
+pub fn uuid_from_string(value: &str) -> Option<u128> {
+    let hex = value.replace('-', "");
+    if hex.len() != 32 {
+        return None;
+    }
+    u128::from_str_radix(&hex, 16).ok()
+}
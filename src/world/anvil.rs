@@ -0,0 +1,284 @@
+use crate::utils::nbt::{Compound, Tag};
+use crate::world::bits::NibbleArray;
+use crate::world::chunk::SECTIONS;
+use crate::world::light::light_column;
+use crate::world::region::RegionFile;
+use crate::world::section::{BlockState, ChunkSection, SECTION_VOLUME};
+use crate::world::ChunkColumn;
+use crate::{SResult, SimpleError};
+use ahash::AHashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Chunk's data version of 1.12.2
+pub const DATA_VERSION: i32 = 1343;
+// Level's values which are written from chunk(others are kept as is)
+const OWN_TAGS: &[&str] = &[
+    "xPos",
+    "zPos",
+    "LightPopulated",
+    "Biomes",
+    "HeightMap",
+    "Sections",
+    "TileEntities",
+];
+
+fn error(text: &str) -> SimpleError {
+    SimpleError(format!("Invalid chunk's NBT: {}", text), None)
+}
+
+// Byte array from compound(None if it's missing or has another length)
+fn byte_array<'a>(compound: &'a Compound, name: &str, len: usize) -> Option<&'a [i8]> {
+    match compound.get(name) {
+        Some(Tag::ByteArray(v)) if v.len() == len => Some(v),
+        _ => None,
+    }
+}
+
+// Nibbles from byte array of section(default value if it's missing)
+fn nibbles(compound: &Compound, name: &str, default: u8) -> NibbleArray {
+    match byte_array(compound, name, SECTION_VOLUME / 2) {
+        Some(v) => NibbleArray(v.iter().map(|v| *v as u8).collect()),
+        None => NibbleArray::new(SECTION_VOLUME, default),
+    }
+}
+
+// Section from Anvil's Blocks, Add and Data arrays
+fn section_from_nbt(compound: &Compound) -> SResult<ChunkSection> {
+    let blocks =
+        byte_array(compound, "Blocks", SECTION_VOLUME).ok_or_else(|| error("no blocks"))?;
+    let add = byte_array(compound, "Add", SECTION_VOLUME / 2).map(nibbles_of);
+    let data = nibbles(compound, "Data", 0);
+    let mut states = vec![0 as BlockState; SECTION_VOLUME];
+    for (i, state) in states.iter_mut().enumerate() {
+        let mut id = blocks[i] as u8 as u16;
+        if let Some(add) = &add {
+            id |= (add.get(i) as u16) << 8;
+        }
+        *state = id << 4 | data.get(i) as u16;
+    }
+    let mut section = ChunkSection::from_states(&states);
+    section.block_light = nibbles(compound, "BlockLight", 0);
+    section.sky_light = nibbles(compound, "SkyLight", 15);
+    Ok(section)
+}
+
+fn nibbles_of(bytes: &[i8]) -> NibbleArray {
+    NibbleArray(bytes.iter().map(|v| *v as u8).collect())
+}
+
+fn to_byte_array(bytes: &[u8]) -> Tag {
+    Tag::ByteArray(bytes.iter().map(|v| *v as i8).collect())
+}
+
+// Section to Anvil's Blocks, Add(only if needed) and Data arrays
+fn section_to_nbt(y: usize, section: &ChunkSection, sky_light: bool) -> Compound {
+    let mut blocks = vec![0u8; SECTION_VOLUME];
+    let mut add = NibbleArray::new(SECTION_VOLUME, 0);
+    let mut data = NibbleArray::new(SECTION_VOLUME, 0);
+    let mut has_add = false;
+    for (i, block) in blocks.iter_mut().enumerate() {
+        let state = section.get_index(i);
+        let id = state >> 4;
+        *block = id as u8;
+        if id > 0xFF {
+            has_add = true;
+            add.set(i, (id >> 8) as u8);
+        }
+        data.set(i, (state & 15) as u8);
+    }
+    let mut compound = Compound::new();
+    compound.insert(String::from("Y"), Tag::Byte(y as i8));
+    compound.insert(String::from("Blocks"), to_byte_array(&blocks));
+    if has_add {
+        compound.insert(String::from("Add"), to_byte_array(&add.0));
+    }
+    compound.insert(String::from("Data"), to_byte_array(&data.0));
+    compound.insert(
+        String::from("BlockLight"),
+        to_byte_array(&section.block_light.0),
+    );
+    if sky_light {
+        compound.insert(
+            String::from("SkyLight"),
+            to_byte_array(&section.sky_light.0),
+        );
+    }
+    compound
+}
+
+/// Chunk from Anvil's chunk NBT(root with Level)
+pub fn chunk_from_nbt(root: &Compound, has_sky_light: bool) -> SResult<ChunkColumn> {
+    // Chunks of newer versions have other format
+    if let Some(version) = root.get("DataVersion").and_then(|v| v.as_i32()) {
+        if version > DATA_VERSION {
+            return Err(error(&format!("unsupported DataVersion {}", version)));
+        }
+    }
+    let level = root
+        .get("Level")
+        .and_then(|v| v.as_compound())
+        .ok_or_else(|| error("no Level"))?;
+    let position = |name: &str| {
+        level
+            .get(name)
+            .and_then(|v| v.as_i32())
+            .ok_or_else(|| error("no position"))
+    };
+    let mut column = ChunkColumn::new(position("xPos")?, position("zPos")?, has_sky_light);
+    if let Some(sections) = level.get("Sections").and_then(|v| v.as_list()) {
+        for section in sections.iter().filter_map(|v| v.as_compound()) {
+            let y = section.get("Y").and_then(|v| v.as_i8()).unwrap_or(-1);
+            if !(0..SECTIONS as i8).contains(&y) {
+                continue;
+            }
+            column.sections[y as usize] = Some(Box::new(section_from_nbt(section)?));
+        }
+    }
+    if let Some(biomes) = byte_array(level, "Biomes", 256) {
+        for (i, biome) in biomes.iter().enumerate() {
+            column.biomes[i] = *biome as u8;
+        }
+    }
+    if let Some(entities) = level.get("TileEntities").and_then(|v| v.as_list()) {
+        column.block_entities = entities
+            .iter()
+            .filter_map(|v| v.as_compound().cloned())
+            .collect();
+    }
+    column.other = level
+        .iter()
+        .filter(|(k, _)| !OWN_TAGS.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    // Chunks saved without light are lit again
+    if level.get("LightPopulated").and_then(|v| v.as_i8()) != Some(1) {
+        light_column(&mut column);
+    }
+    column.dirty = false;
+    Ok(column)
+}
+
+/// Chunk to Anvil's chunk NBT(root with Level and DataVersion)
+pub fn chunk_to_nbt(column: &ChunkColumn) -> Compound {
+    let mut level = column.other.clone();
+    // Values of chunks without loaded Level
+    let defaults = [
+        ("LastUpdate", Tag::Long(0)),
+        ("InhabitedTime", Tag::Long(0)),
+        ("TerrainPopulated", Tag::Byte(1)),
+        ("V", Tag::Byte(1)),
+        ("Entities", Tag::List(Vec::new())),
+    ];
+    for (name, value) in defaults {
+        level.entry(String::from(name)).or_insert(value);
+    }
+    level.insert(String::from("xPos"), Tag::Int(column.x));
+    level.insert(String::from("zPos"), Tag::Int(column.z));
+    level.insert(String::from("LightPopulated"), Tag::Byte(1));
+    level.insert(String::from("Biomes"), to_byte_array(&column.biomes));
+    let mut height_map = Vec::with_capacity(256);
+    for z in 0..16 {
+        for x in 0..16 {
+            height_map.push(
+                column
+                    .highest_block(x, z)
+                    .map(|v| v as i32 + 1)
+                    .unwrap_or(0),
+            );
+        }
+    }
+    level.insert(String::from("HeightMap"), Tag::IntArray(height_map));
+    let sections = column
+        .sections
+        .iter()
+        .enumerate()
+        .filter_map(|(y, v)| Some((y, v.as_ref()?)))
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(y, v)| Tag::Compound(section_to_nbt(y, v, column.has_sky_light)))
+        .collect();
+    level.insert(String::from("Sections"), Tag::List(sections));
+    level.insert(
+        String::from("TileEntities"),
+        Tag::List(
+            column
+                .block_entities
+                .iter()
+                .cloned()
+                .map(Tag::Compound)
+                .collect(),
+        ),
+    );
+    let mut root = Compound::new();
+    root.insert(String::from("DataVersion"), Tag::Int(DATA_VERSION));
+    root.insert(String::from("Level"), Tag::Compound(level));
+    root
+}
+
+/// Region's files of a dimension(like world/region)
+pub struct RegionStorage {
+    directory: PathBuf,
+    regions: AHashMap<(i32, i32), RegionFile>,
+}
+
+impl RegionStorage {
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+            regions: AHashMap::new(),
+        }
+    }
+
+    // Region's file which has chunk(None if file doesn't exist and create is false)
+    fn region(&mut self, x: i32, z: i32, create: bool) -> SResult<Option<&mut RegionFile>> {
+        let key = (x >> 5, z >> 5);
+        if !self.regions.contains_key(&key) {
+            let path = self.directory.join(format!("r.{}.{}.mca", key.0, key.1));
+            if !create && !path.exists() {
+                return Ok(None);
+            }
+            fs::create_dir_all(&self.directory).map_err(|err| {
+                SimpleError(
+                    String::from("Failed to create region's directory"),
+                    Some(err),
+                )
+            })?;
+            self.regions.insert(key, RegionFile::open(&path)?);
+        }
+        Ok(self.regions.get_mut(&key))
+    }
+
+    /// Loading a chunk(None if it isn't saved)
+    pub fn load_chunk(
+        &mut self,
+        x: i32,
+        z: i32,
+        has_sky_light: bool,
+    ) -> SResult<Option<ChunkColumn>> {
+        let region = match self.region(x, z, false)? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        match region.read_chunk(x, z)? {
+            Some(v) => chunk_from_nbt(&v, has_sky_light).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Saving a chunk into its region
+    pub fn save_chunk(&mut self, column: &ChunkColumn) -> SResult<()> {
+        let nbt = chunk_to_nbt(column);
+        match self.region(column.x, column.z, true)? {
+            Some(v) => v.write_chunk(column.x, column.z, &nbt),
+            None => Ok(()),
+        }
+    }
+
+    /// Removing free sectors from all opened regions
+    pub fn compact(&mut self) -> SResult<()> {
+        for region in self.regions.values_mut() {
+            region.compact()?;
+        }
+        Ok(())
+    }
+}
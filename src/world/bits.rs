@@ -0,0 +1,100 @@
+// Values with fixed count of bits packed into longs(value can span two longs as in 1.12)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitArray {
+    bits: u8,
+    len: usize,
+    data: Vec<u64>,
+}
+
+impl BitArray {
+    // Array of zeroes
+    pub fn new(bits: u8, len: usize) -> Self {
+        Self {
+            bits,
+            len,
+            data: vec![0; (len * bits as usize).div_ceil(64)],
+        }
+    }
+
+    // Array from packed longs(None if count of longs doesn't match)
+    pub fn from_data(bits: u8, len: usize, data: Vec<u64>) -> Option<Self> {
+        if data.len() != (len * bits as usize).div_ceil(64) {
+            return None;
+        }
+        Some(Self { bits, len, data })
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Packed longs(for sending and saving)
+    pub fn data(&self) -> &[u64] {
+        &self.data
+    }
+
+    fn mask(&self) -> u64 {
+        (1 << self.bits) - 1
+    }
+
+    pub fn get(&self, index: usize) -> u32 {
+        let bit = index * self.bits as usize;
+        let (long, offset) = (bit / 64, bit % 64);
+        let mut value = self.data[long] >> offset;
+        // Value continues in next long
+        if offset + self.bits as usize > 64 {
+            value |= self.data[long + 1] << (64 - offset);
+        }
+        (value & self.mask()) as u32
+    }
+
+    pub fn set(&mut self, index: usize, value: u32) {
+        let mask = self.mask();
+        let value = value as u64 & mask;
+        let bit = index * self.bits as usize;
+        let (long, offset) = (bit / 64, bit % 64);
+        self.data[long] = self.data[long] & !(mask << offset) | value << offset;
+        if offset + self.bits as usize > 64 {
+            let shift = 64 - offset;
+            self.data[long + 1] = self.data[long + 1] & !(mask >> shift) | value >> shift;
+        }
+    }
+
+    // Copy of the array with another count of bits per value
+    pub fn resize(&self, bits: u8) -> Self {
+        let mut array = Self::new(bits, self.len);
+        for i in 0..self.len {
+            array.set(i, self.get(i));
+        }
+        array
+    }
+}
+
+// 4-bit values(light), two per byte with lower half first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NibbleArray(pub Vec<u8>);
+
+impl NibbleArray {
+    // Array of len values filled by value
+    pub fn new(len: usize, value: u8) -> Self {
+        Self(vec![(value & 0x0F) * 0x11; len / 2])
+    }
+
+    pub fn get(&self, index: usize) -> u8 {
+        (self.0[index / 2] >> ((index & 1) * 4)) & 0x0F
+    }
+
+    pub fn set(&mut self, index: usize, value: u8) {
+        let shift = (index & 1) * 4;
+        let byte = &mut self.0[index / 2];
+        *byte = *byte & !(0x0F << shift) | (value & 0x0F) << shift;
+    }
+}
@@ -0,0 +1,383 @@
+use crate::world::section::BlockState;
+use ahash::AHashMap;
+
+// Blocks of 1.12.2 with their properties' encoding in metadata
+const BLOCKS_DATA: &str = include_str!("blocks.json");
+
+/// Block's collision shape(box is in block's coordinates from 0.0 to 1.0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Collision {
+    None,
+    Full,
+    Box([f64; 6]),
+    // Shapes which depend on state's facing and open
+    Door,
+    Trapdoor,
+    FenceGate,
+}
+
+// Collision as it's written in data file("none", "full" or box)
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CollisionData {
+    Name(String),
+    Box([f64; 6]),
+}
+
+/// Property stored in metadata's bits(values by bits' value, empty - invalid value)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Property {
+    pub name: String,
+    pub shift: u8,
+    pub bits: u8,
+    pub values: Vec<String>,
+}
+
+impl Property {
+    // Mask of property's bits in metadata
+    fn mask(&self) -> u8 {
+        ((1 << self.bits) - 1) << self.shift
+    }
+}
+
+// Block as it's written in data file
+#[derive(Deserialize)]
+struct BlockData {
+    id: u16,
+    name: String,
+    hardness: f32,
+    #[serde(default = "opaque")]
+    opacity: u8,
+    #[serde(default)]
+    light: u8,
+    #[serde(default)]
+    collision: Option<CollisionData>,
+    #[serde(default)]
+    properties: Vec<(String, u8, u8, String)>,
+    #[serde(default)]
+    variants: Vec<String>,
+}
+
+fn opaque() -> u8 {
+    15
+}
+
+/// Block's type with its states and metadata used by lighting and physics
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockInfo {
+    pub id: u16,
+    // Name with "minecraft:"
+    pub name: String,
+    // Time multiplier of breaking(-1 - unbreakable)
+    pub hardness: f32,
+    // How much light is lost passing through the block(0 - transparent, 15 - opaque)
+    pub opacity: u8,
+    // Emitted light's level
+    pub light: u8,
+    pub collision: Collision,
+    // Properties stored in metadata's bits
+    pub properties: Vec<Property>,
+    // Properties by metadata for blocks which can't be described by bits(like doors)
+    pub variants: Vec<String>,
+}
+
+// Parsing properties like "facing=east,half=lower"
+fn parse_properties(text: &str) -> Vec<(String, String)> {
+    text.split(',')
+        .filter_map(|v| v.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect()
+}
+
+impl BlockInfo {
+    fn from_data(data: BlockData) -> Self {
+        let collision = match data.collision {
+            None => Collision::Full,
+            Some(CollisionData::Name(v)) => match v.as_str() {
+                "none" => Collision::None,
+                "door" => Collision::Door,
+                "trapdoor" => Collision::Trapdoor,
+                "fence_gate" => Collision::FenceGate,
+                _ => Collision::Full,
+            },
+            Some(CollisionData::Box(v)) => Collision::Box(v),
+        };
+        Self {
+            id: data.id,
+            name: format!("minecraft:{}", data.name),
+            hardness: data.hardness,
+            opacity: data.opacity.min(15),
+            light: data.light.min(15),
+            collision,
+            properties: data
+                .properties
+                .into_iter()
+                .map(|(name, shift, bits, values)| Property {
+                    name,
+                    shift,
+                    bits,
+                    values: values.split(',').map(String::from).collect(),
+                })
+                .collect(),
+            variants: data.variants,
+        }
+    }
+
+    /// Properties of block's state with metadata(None if metadata isn't valid)
+    pub fn properties(&self, meta: u8) -> Option<Vec<(String, String)>> {
+        if !self.variants.is_empty() {
+            return match self.variants.get(meta as usize) {
+                Some(v) if !v.is_empty() => Some(parse_properties(v)),
+                _ => None,
+            };
+        }
+        let mut used = 0;
+        let mut properties = Vec::with_capacity(self.properties.len());
+        for property in &self.properties {
+            used |= property.mask();
+            let index = ((meta & property.mask()) >> property.shift) as usize;
+            match property.values.get(index) {
+                Some(v) if !v.is_empty() => properties.push((property.name.clone(), v.clone())),
+                _ => return None,
+            }
+        }
+        // Other bits must be empty
+        if meta & !used != 0 {
+            return None;
+        }
+        Some(properties)
+    }
+
+    /// Metadata by properties(missing properties get default values,
+    /// properties which aren't stored in metadata like stairs' shape are ignored)
+    pub fn meta(&self, properties: &[(&str, &str)]) -> Option<u8> {
+        let names = self.property_names();
+        'metas: for meta in 0..16 {
+            let stored = match self.properties(meta) {
+                Some(v) => v,
+                None => continue,
+            };
+            for (name, value) in properties {
+                if !names.contains(name) {
+                    continue;
+                }
+                if !stored.iter().any(|(k, v)| k == name && v == value) {
+                    continue 'metas;
+                }
+            }
+            return Some(meta);
+        }
+        None
+    }
+
+    // Names of all properties stored in metadata
+    fn property_names(&self) -> Vec<&str> {
+        if self.variants.is_empty() {
+            return self.properties.iter().map(|v| v.name.as_str()).collect();
+        }
+        let mut names: Vec<&str> = self
+            .variants
+            .iter()
+            .flat_map(|v| v.split(','))
+            .filter_map(|v| v.split_once('=').map(|v| v.0))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+// All blocks by ids and names
+struct BlockRegistry {
+    blocks: Vec<Option<BlockInfo>>,
+    by_name: AHashMap<String, u16>,
+}
+
+impl BlockRegistry {
+    fn load() -> Self {
+        let data: Vec<BlockData> =
+            serde_json::from_str(BLOCKS_DATA).expect("Invalid bundled blocks' data");
+        let mut registry = Self {
+            blocks: vec![None; 256],
+            by_name: AHashMap::new(),
+        };
+        for block in data {
+            let block = BlockInfo::from_data(block);
+            registry.by_name.insert(block.name.clone(), block.id);
+            let id = block.id as usize;
+            registry.blocks[id] = Some(block);
+        }
+        registry
+    }
+}
+
+lazy_static! {
+    static ref BLOCKS: BlockRegistry = BlockRegistry::load();
+}
+
+/// Block's type by its id
+pub fn block(id: u16) -> Option<&'static BlockInfo> {
+    BLOCKS.blocks.get(id as usize)?.as_ref()
+}
+
+/// Block's type by its name(with or without "minecraft:")
+pub fn block_by_name(name: &str) -> Option<&'static BlockInfo> {
+    let id = match name.contains(':') {
+        true => BLOCKS.by_name.get(name),
+        false => BLOCKS.by_name.get(&format!("minecraft:{}", name)),
+    };
+    block(*id?)
+}
+
+/// Block's id by its name(with or without "minecraft:")
+pub fn block_id(name: &str) -> Option<u16> {
+    block_by_name(name).map(|v| v.id)
+}
+
+/// Block's name with "minecraft:" by its id(None if id is unused)
+pub fn block_name(id: u16) -> Option<String> {
+    block(id).map(|v| v.name.clone())
+}
+
+/// Block's state by name and properties
+pub fn state_of(name: &str, properties: &[(&str, &str)]) -> Option<BlockState> {
+    let block = block_by_name(name)?;
+    Some(block.id << 4 | block.meta(properties)? as u16)
+}
+
+/// Block's name and properties of state(None if state isn't valid)
+pub fn state_properties(state: BlockState) -> Option<(&'static str, Vec<(String, String)>)> {
+    let block = block(state >> 4)?;
+    Some((&block.name, block.properties((state & 15) as u8)?))
+}
+
+/// State as text like "minecraft:oak_stairs[facing=east,half=bottom]"
+pub fn state_to_string(state: BlockState) -> String {
+    match state_properties(state) {
+        Some((name, properties)) if properties.is_empty() => name.to_string(),
+        Some((name, properties)) => {
+            let properties: Vec<String> = properties
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            format!("{}[{}]", name, properties.join(","))
+        }
+        None => format!("{}:{}", state >> 4, state & 15),
+    }
+}
+
+/// Block's state from text like "minecraft:oak_stairs[facing=east]",
+/// "minecraft:stone:1", "stone" or "1:1"(id and metadata)
+pub fn parse_block_state(text: &str) -> Option<BlockState> {
+    let text = text.trim();
+    if let Some((name, properties)) = text.split_once('[') {
+        let properties = parse_properties(properties.strip_suffix(']')?);
+        let properties: Vec<(&str, &str)> = properties
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        return state_of(name, &properties);
+    }
+    let text = text.strip_prefix("minecraft:").unwrap_or(text);
+    let (name, meta) = match text.rsplit_once(':') {
+        Some((name, meta)) => (name, meta.parse::<u8>().ok()?),
+        None => (text, 0),
+    };
+    let block = match name.parse::<u16>() {
+        Ok(v) => block(v)?,
+        Err(_) => block_by_name(name)?,
+    };
+    block.properties(meta)?;
+    Some(block.id << 4 | meta as u16)
+}
+
+/// Breaking time's multiplier of state(-1 - unbreakable, unknown blocks are unbreakable)
+pub fn hardness(state: BlockState) -> f32 {
+    block(state >> 4).map(|v| v.hardness).unwrap_or(-1.0)
+}
+
+/// Light's opacity of state(0 - transparent, 15 - opaque, unknown blocks are opaque)
+pub fn opacity(state: BlockState) -> u8 {
+    block(state >> 4).map(|v| v.opacity).unwrap_or(15)
+}
+
+/// Light's level emitted by state
+pub fn light_emission(state: BlockState) -> u8 {
+    block(state >> 4).map(|v| v.light).unwrap_or(0)
+}
+
+// Value of state's property
+fn property(state: BlockState, name: &str) -> Option<String> {
+    let (_, properties) = state_properties(state)?;
+    properties
+        .into_iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v)
+}
+
+// Thin box(3/16 of block) at block's side
+fn side_box(side: &str) -> [f64; 6] {
+    match side {
+        "north" => [0.0, 0.0, 0.8125, 1.0, 1.0, 1.0],
+        "south" => [0.0, 0.0, 0.0, 1.0, 1.0, 0.1875],
+        "west" => [0.8125, 0.0, 0.0, 1.0, 1.0, 1.0],
+        _ => [0.0, 0.0, 0.0, 0.1875, 1.0, 1.0],
+    }
+}
+
+/// Collision's box of door by its halves(lower half has facing and open, upper half has hinge)
+pub fn door_box(lower: BlockState, upper: BlockState) -> [f64; 6] {
+    let facing = property(lower, "facing").unwrap_or_default();
+    if property(lower, "open").as_deref() != Some("true") {
+        return side_box(&facing);
+    }
+    // Open door turns around its hinge
+    let right = property(upper, "hinge").as_deref() == Some("right");
+    let side = match (facing.as_str(), right) {
+        ("south", true) | ("north", false) => "east",
+        ("west", true) | ("east", false) => "south",
+        ("north", true) | ("south", false) => "west",
+        _ => "north",
+    };
+    side_box(side)
+}
+
+/// Collision's box of state(None - entities go through it).
+/// Half-height boxes with half=top are moved to the top of block,
+/// doors are checked only by own half(see [door_box] for whole door)
+pub fn collision_box(state: BlockState) -> Option<[f64; 6]> {
+    let block = match block(state >> 4) {
+        Some(v) => v,
+        None => return Some([0.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+    };
+    let open = || property(state, "open").as_deref() == Some("true");
+    match block.collision {
+        Collision::None => None,
+        Collision::Full => Some([0.0, 0.0, 0.0, 1.0, 1.0, 1.0]),
+        Collision::Box(mut shape) => {
+            let top = block
+                .properties((state & 15) as u8)
+                .map(|v| v.iter().any(|(k, v)| k == "half" && v == "top"))
+                .unwrap_or(false);
+            if top && shape[4] <= 0.5 {
+                shape[1] += 0.5;
+                shape[4] += 0.5;
+            }
+            Some(shape)
+        }
+        Collision::Door => Some(door_box(state, state)),
+        Collision::Trapdoor if open() => {
+            Some(side_box(&property(state, "facing").unwrap_or_default()))
+        }
+        Collision::Trapdoor => match property(state, "half").as_deref() {
+            Some("top") => Some([0.0, 0.8125, 0.0, 1.0, 1.0, 1.0]),
+            _ => Some([0.0, 0.0, 0.0, 1.0, 0.1875, 1.0]),
+        },
+        Collision::FenceGate if open() => None,
+        // Closed gate is higher than a block like fences
+        Collision::FenceGate => match property(state, "facing").as_deref() {
+            Some("north" | "south") => Some([0.0, 0.0, 0.375, 1.0, 1.5, 0.625]),
+            _ => Some([0.375, 0.0, 0.0, 0.625, 1.5, 1.0]),
+        },
+    }
+}
@@ -0,0 +1,141 @@
+use crate::utils::nbt::Compound;
+use crate::world::section::{BlockState, ChunkSection, AIR};
+
+// Count of sections in column(256 blocks of height)
+pub const SECTIONS: usize = 16;
+// Biome by default(plains)
+pub const DEFAULT_BIOME: u8 = 1;
+
+/// 16x256x16 blocks column of sections with biomes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkColumn {
+    // Chunk's coordinates(block's coordinate >> 4)
+    pub x: i32,
+    pub z: i32,
+    // Sections from bottom(None - air)
+    pub sections: [Option<Box<ChunkSection>>; SECTIONS],
+    // Biome's id by z << 4 | x
+    pub biomes: [u8; 256],
+    // Dimension has sky light(overworld)
+    pub has_sky_light: bool,
+    // Block entities(chests, signs and etc.) as NBT with x, y, z
+    pub block_entities: Vec<Compound>,
+    // Other Level's values(entities, ticks, structures and etc., kept as is on saving)
+    pub other: Compound,
+    // Chunk was changed after loading or saving
+    pub dirty: bool,
+}
+
+impl ChunkColumn {
+    // Column of air
+    pub fn new(x: i32, z: i32, has_sky_light: bool) -> Self {
+        Self {
+            x,
+            z,
+            sections: Default::default(),
+            biomes: [DEFAULT_BIOME; 256],
+            has_sky_light,
+            block_entities: Vec::new(),
+            other: Compound::new(),
+            dirty: true,
+        }
+    }
+
+    // Section by its index(0 - bottom), None if it's air
+    pub fn section(&self, index: usize) -> Option<&ChunkSection> {
+        self.sections.get(index)?.as_deref()
+    }
+
+    // Section by its index, created if it's air
+    pub fn section_mut(&mut self, index: usize) -> &mut ChunkSection {
+        self.sections[index].get_or_insert_with(Default::default)
+    }
+
+    // Bit mask of sections with blocks(bit per section from bottom)
+    pub fn section_mask(&self) -> u16 {
+        self.sections
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.as_ref().map(|v| !v.is_empty()).unwrap_or(false))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    // Block's state by chunk's local coordinates(x and z - 0..16, y - 0..256)
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> BlockState {
+        match self.section(y >> 4) {
+            Some(v) => v.get(x, y & 15, z),
+            None => AIR,
+        }
+    }
+
+    // Setting block's state, returns previous state
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, state: BlockState) -> BlockState {
+        if y >= SECTIONS * 16 {
+            return AIR;
+        }
+        // Air doesn't need a new section
+        if state == AIR && self.sections[y >> 4].is_none() {
+            return AIR;
+        }
+        let previous = self.section_mut(y >> 4).set(x, y & 15, z, state);
+        self.dirty |= previous != state;
+        previous
+    }
+
+    pub fn get_block_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        match self.section(y >> 4) {
+            Some(v) => v.get_block_light(x, y & 15, z),
+            None => 0,
+        }
+    }
+
+    pub fn set_block_light(&mut self, x: usize, y: usize, z: usize, value: u8) {
+        if y < SECTIONS * 16 {
+            self.section_mut(y >> 4)
+                .set_block_light(x, y & 15, z, value)
+        }
+    }
+
+    // Sky light(full in air sections, none without sky)
+    pub fn get_sky_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        if !self.has_sky_light {
+            return 0;
+        }
+        match self.section(y >> 4) {
+            Some(v) => v.get_sky_light(x, y & 15, z),
+            None => 15,
+        }
+    }
+
+    pub fn set_sky_light(&mut self, x: usize, y: usize, z: usize, value: u8) {
+        if self.has_sky_light && y < SECTIONS * 16 {
+            self.dirty = true;
+            self.section_mut(y >> 4).set_sky_light(x, y & 15, z, value)
+        }
+    }
+
+    pub fn get_biome(&self, x: usize, z: usize) -> u8 {
+        self.biomes[z << 4 | x]
+    }
+
+    pub fn set_biome(&mut self, x: usize, z: usize, biome: u8) {
+        self.biomes[z << 4 | x] = biome;
+        self.dirty = true;
+    }
+
+    // Highest not air block's y(None if column is empty)
+    pub fn highest_block(&self, x: usize, z: usize) -> Option<usize> {
+        for (index, section) in self.sections.iter().enumerate().rev() {
+            let section = match section {
+                Some(v) if !v.is_empty() => v,
+                _ => continue,
+            };
+            for y in (0..16).rev() {
+                if section.get(x, y, z) != AIR {
+                    return Some(index * 16 + y);
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,145 @@
+use crate::world::block::{block_name, parse_block_state};
+use crate::world::chunk::SECTIONS;
+use crate::world::generator::ChunkGenerator;
+use crate::world::section::{BlockState, AIR};
+use crate::world::ChunkColumn;
+
+// Vanilla's default superflat's preset(classic flat)
+pub const DEFAULT_PRESET: &str = "3;minecraft:bedrock,2*minecraft:dirt,minecraft:grass;1;village";
+
+/// Superflat generator with layers from 1.12's preset
+/// (version;layers;biome;structures like "3;minecraft:bedrock,2*minecraft:dirt,minecraft:grass;1;village")
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatGenerator {
+    // Blocks from bottom(y = 0) to top
+    pub layers: Vec<BlockState>,
+    pub biome: u8,
+    // Structures with options(like "village" or "mineshaft(chance=0.01)"), not generated yet
+    pub structures: Vec<String>,
+}
+
+impl Default for FlatGenerator {
+    fn default() -> Self {
+        Self::parse(DEFAULT_PRESET).unwrap()
+    }
+}
+
+impl FlatGenerator {
+    /// Parsing preset(empty - default preset), None if preset is invalid
+    pub fn parse(preset: &str) -> Option<Self> {
+        let preset = preset.trim();
+        if preset.is_empty() {
+            return Self::parse(DEFAULT_PRESET);
+        }
+        let parts: Vec<&str> = preset.split(';').collect();
+        // Presets without version are from old versions with only layers
+        let (version, parts) = match parts[0].parse::<u8>() {
+            Ok(v) if parts.len() > 1 => (v, &parts[1..]),
+            _ => (0, &parts[..]),
+        };
+        if version > 3 {
+            return None;
+        }
+        let mut layers = Vec::new();
+        for layer in parts[0].split(',') {
+            // Layer is "[count*]block[:meta]"(older versions use "count x block")
+            let separator = if version >= 3 { '*' } else { 'x' };
+            let (count, block) = match layer.split_once(separator) {
+                Some((count, block)) => (count.trim().parse::<usize>().ok()?, block),
+                None => (1, layer),
+            };
+            let state = parse_block_state(block)?;
+            let height = layers.len().checked_add(count)?;
+            if height > SECTIONS * 16 {
+                return None;
+            }
+            layers.resize(height, state);
+        }
+        let biome = match parts.get(1) {
+            Some(v) => v.trim().parse::<u8>().ok()?,
+            None => 1,
+        };
+        let structures = match parts.get(2) {
+            Some(v) => split_structures(v),
+            None => Vec::new(),
+        };
+        Some(Self {
+            layers,
+            biome,
+            structures,
+        })
+    }
+
+    /// Preset's string of this generator
+    pub fn preset(&self) -> String {
+        let mut layers: Vec<(usize, BlockState)> = Vec::new();
+        for state in &self.layers {
+            match layers.last_mut() {
+                Some((count, last)) if last == state => *count += 1,
+                _ => layers.push((1, *state)),
+            }
+        }
+        let layers: Vec<String> = layers
+            .iter()
+            .map(|(count, state)| {
+                let name = block_name(state >> 4).unwrap_or_default();
+                let block = match state & 15 {
+                    0 => name,
+                    meta => format!("{}:{}", name, meta),
+                };
+                match count {
+                    1 => block,
+                    count => format!("{}*{}", count, block),
+                }
+            })
+            .collect();
+        format!(
+            "3;{};{};{}",
+            layers.join(","),
+            self.biome,
+            self.structures.join(",")
+        )
+    }
+}
+
+// Splitting structures by commas outside of options' brackets
+fn split_structures(text: &str) -> Vec<String> {
+    let mut structures = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                structures.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    structures.push(current.trim().to_string());
+    structures.retain(|v| !v.is_empty());
+    structures
+}
+
+impl ChunkGenerator for FlatGenerator {
+    fn generate(&self, x: i32, z: i32, _seed: i64) -> ChunkColumn {
+        let mut column = ChunkColumn::new(x, z, true);
+        column.biomes = [self.biome; 256];
+        for (y, state) in self.layers.iter().enumerate() {
+            if *state == AIR {
+                continue;
+            }
+            let section = column.section_mut(y >> 4);
+            for z in 0..16 {
+                for x in 0..16 {
+                    section.set(x, y & 15, z, *state);
+                }
+            }
+        }
+        column
+    }
+}
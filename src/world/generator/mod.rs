@@ -0,0 +1,48 @@
+pub mod flat;
+pub mod noise;
+pub mod pool;
+pub mod terrain;
+pub mod void;
+
+use crate::config::{VOID_PLATFORM, VOID_PLATFORM_RADIUS};
+use crate::world::block::parse_block_state;
+use crate::world::{ChunkColumn, WorldInfo};
+pub use flat::FlatGenerator;
+pub use pool::GeneratorPool;
+pub use terrain::TerrainGenerator;
+pub use void::VoidGenerator;
+
+/// Generator of new chunks(used when chunk isn't saved on disk).
+/// Same coordinates and seed always give same chunk, so it can run on any thread
+pub trait ChunkGenerator: Send + Sync {
+    /// Generating chunk column by its coordinates and world's seed
+    fn generate(&self, x: i32, z: i32, seed: i64) -> ChunkColumn;
+}
+
+/// Generator by world's type and its options from level.dat
+pub fn create_generator(info: &WorldInfo) -> Box<dyn ChunkGenerator> {
+    match info.generator_name.to_ascii_lowercase().as_str() {
+        "flat" => Box::new(
+            FlatGenerator::parse(&info.generator_options).unwrap_or_else(|| {
+                warn!(
+                    "Invalid superflat's preset \"{}\", using default",
+                    info.generator_options
+                );
+                FlatGenerator::default()
+            }),
+        ),
+        "default" => Box::new(TerrainGenerator::default()),
+        "largebiomes" => Box::new(TerrainGenerator::new(1.0, 4.0)),
+        "amplified" => Box::new(TerrainGenerator::new(2.0, 1.0)),
+        "void" => {
+            let (x, y, z) = info.spawn();
+            let platform = parse_block_state(VOID_PLATFORM)
+                .map(|block| (block, (x, y - 1, z), VOID_PLATFORM_RADIUS));
+            Box::new(VoidGenerator::new(platform))
+        }
+        name => {
+            warn!("Unknown world's type \"{}\", using default", name);
+            Box::new(TerrainGenerator::default())
+        }
+    }
+}
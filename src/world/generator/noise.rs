@@ -0,0 +1,168 @@
+// Deterministic random numbers(splitmix64) for generators
+#[derive(Debug, Clone)]
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: i64) -> Self {
+        Self(seed as u64)
+    }
+
+    // Random from seed and coordinates(same values give same numbers)
+    pub fn at(seed: i64, x: i32, z: i32, salt: u64) -> Self {
+        let mut random = Self::new(seed);
+        random.0 ^= (x as i64 as u64).wrapping_mul(0x9E3779B97F4A7C15)
+            ^ (z as i64 as u64).wrapping_mul(0xC2B2AE3D27D4EB4F)
+            ^ salt.wrapping_mul(0x165667B19E3779F9);
+        random
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut v = self.0;
+        v = (v ^ (v >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        v = (v ^ (v >> 27)).wrapping_mul(0x94D049BB133111EB);
+        v ^ (v >> 31)
+    }
+
+    // Number in 0..bound
+    pub fn next_int(&mut self, bound: u32) -> u32 {
+        (((self.next_u64() >> 32) * bound as u64) >> 32) as u32
+    }
+
+    // Number in 0.0..1.0
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+// Dot product of one of 12 cube's edges' gradients and distance
+fn grad(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+/// [Improved Perlin noise](https://mrl.cs.nyu.edu/~perlin/noise/) with random permutation
+#[derive(Debug, Clone)]
+pub struct PerlinNoise {
+    perm: [u8; 512],
+    // Random offset, so octaves don't have zero at same points
+    offset: (f64, f64, f64),
+}
+
+impl PerlinNoise {
+    pub fn new(random: &mut Random) -> Self {
+        let mut perm = [0u8; 512];
+        for (i, v) in perm.iter_mut().take(256).enumerate() {
+            *v = i as u8;
+        }
+        for i in (1..256).rev() {
+            let j = random.next_int(i as u32 + 1) as usize;
+            perm.swap(i, j);
+        }
+        for i in 0..256 {
+            perm[i + 256] = perm[i];
+        }
+        let offset = (
+            random.next_f64() * 256.0,
+            random.next_f64() * 256.0,
+            random.next_f64() * 256.0,
+        );
+        Self { perm, offset }
+    }
+
+    // Noise's value in about -1.0..1.0
+    pub fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (x, y, z) = (x + self.offset.0, y + self.offset.1, z + self.offset.2);
+        let (fx, fy, fz) = (x.floor(), y.floor(), z.floor());
+        let (xi, yi, zi) = (
+            (fx as i64 & 255) as usize,
+            (fy as i64 & 255) as usize,
+            (fz as i64 & 255) as usize,
+        );
+        let (x, y, z) = (x - fx, y - fy, z - fz);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let p = &self.perm;
+        let a = p[xi] as usize + yi;
+        let (aa, ab) = (p[a] as usize + zi, p[a + 1] as usize + zi);
+        let b = p[xi + 1] as usize + yi;
+        let (ba, bb) = (p[b] as usize + zi, p[b + 1] as usize + zi);
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p[aa], x, y, z), grad(p[ba], x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(p[ab], x, y - 1.0, z),
+                    grad(p[bb], x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p[aa + 1], x, y, z - 1.0),
+                    grad(p[ba + 1], x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p[ab + 1], x, y - 1.0, z - 1.0),
+                    grad(p[bb + 1], x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+}
+
+/// Sum of Perlin noises with doubling frequency and halving amplitude
+#[derive(Debug, Clone)]
+pub struct OctaveNoise {
+    octaves: Vec<PerlinNoise>,
+    // Frequency of the first octave(1 / size of features in blocks)
+    scale: f64,
+}
+
+impl OctaveNoise {
+    pub fn new(random: &mut Random, octaves: usize, scale: f64) -> Self {
+        Self {
+            octaves: (0..octaves).map(|_| PerlinNoise::new(random)).collect(),
+            scale,
+        }
+    }
+
+    // Noise's value in about -1.0..1.0
+    pub fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        let mut value = 0.0;
+        let mut total = 0.0;
+        let mut frequency = self.scale;
+        let mut amplitude = 1.0;
+        for octave in &self.octaves {
+            value += octave.sample(x * frequency, y * frequency, z * frequency) * amplitude;
+            total += amplitude;
+            frequency *= 2.0;
+            amplitude /= 2.0;
+        }
+        value / total
+    }
+
+    // 2D noise(on plane y = 0)
+    pub fn sample2(&self, x: f64, z: f64) -> f64 {
+        self.sample(x, 0.0, z)
+    }
+}
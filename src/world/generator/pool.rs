@@ -0,0 +1,99 @@
+use crate::world::generator::ChunkGenerator;
+use crate::world::light::light_column;
+use crate::world::ChunkColumn;
+use ahash::AHashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Worker's result for requested chunk
+#[derive(Debug)]
+pub enum Generated {
+    Chunk(Box<ChunkColumn>),
+    // Generator panicked(its message is printed by panic hook)
+    Failed(i32, i32),
+    // Request was cancelled before worker took it
+    Cancelled(i32, i32),
+}
+
+/// Worker threads generating chunks in background
+pub struct GeneratorPool {
+    // Chunks' coordinates to generate
+    tasks: Sender<(i32, i32)>,
+    // Queued chunks which aren't needed anymore(workers skip them)
+    cancelled: Arc<Mutex<AHashSet<(i32, i32)>>>,
+    // Generated chunks
+    results: Receiver<Generated>,
+}
+
+impl GeneratorPool {
+    /// Starting workers(they stop when pool is dropped)
+    pub fn new(generator: Arc<dyn ChunkGenerator>, seed: i64, workers: usize) -> Self {
+        let (tasks, tasks_rx) = channel::<(i32, i32)>();
+        let (results_tx, results) = channel();
+        let tasks_rx = Arc::new(Mutex::new(tasks_rx));
+        let cancelled = Arc::new(Mutex::new(AHashSet::new()));
+        for i in 0..workers.max(1) {
+            let generator = generator.clone();
+            let tasks_rx = tasks_rx.clone();
+            let cancelled = cancelled.clone();
+            let results_tx = results_tx.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("Generator #{}", i))
+                .spawn(move || loop {
+                    // Lock is released before generating
+                    let task = tasks_rx.lock().unwrap().recv();
+                    let (x, z) = match task {
+                        Ok(v) => v,
+                        Err(_) => break,
+                    };
+                    let result = if cancelled.lock().unwrap().remove(&(x, z)) {
+                        Generated::Cancelled(x, z)
+                    } else {
+                        // Panic fails only this chunk, so worker keeps working
+                        let generated = catch_unwind(AssertUnwindSafe(|| {
+                            let mut column = generator.generate(x, z, seed);
+                            light_column(&mut column);
+                            column
+                        }));
+                        match generated {
+                            Ok(v) => Generated::Chunk(Box::new(v)),
+                            Err(_) => Generated::Failed(x, z),
+                        }
+                    };
+                    if results_tx.send(result).is_err() {
+                        break;
+                    }
+                });
+            if let Err(err) = spawned {
+                error!("Failed to start generator's worker: {}", err);
+            }
+        }
+        Self {
+            tasks,
+            cancelled,
+            results,
+        }
+    }
+
+    /// Adding chunk to generation's queue
+    pub fn submit(&self, x: i32, z: i32) {
+        let _ = self.tasks.send((x, z));
+    }
+
+    /// Skipping queued chunk(it's reported as cancelled if worker didn't take it yet)
+    pub fn cancel(&self, x: i32, z: i32) {
+        self.cancelled.lock().unwrap().insert((x, z));
+    }
+
+    /// Generating queued chunk again after cancelling
+    pub fn resume(&self, x: i32, z: i32) {
+        self.cancelled.lock().unwrap().remove(&(x, z));
+    }
+
+    /// Results of finished requests
+    pub fn generated(&self) -> Vec<Generated> {
+        self.results.try_iter().collect()
+    }
+}
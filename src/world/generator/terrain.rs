@@ -0,0 +1,407 @@
+use crate::world::chunk::ChunkColumn;
+use crate::world::generator::noise::{OctaveNoise, Random};
+use crate::world::generator::ChunkGenerator;
+use crate::world::section::{BlockState, AIR};
+
+// Highest water's block
+pub const SEA_LEVEL: i32 = 62;
+
+// Blocks used by generator
+const STONE: BlockState = 1 << 4;
+const GRASS: BlockState = 2 << 4;
+const DIRT: BlockState = 3 << 4;
+const BEDROCK: BlockState = 7 << 4;
+const WATER: BlockState = 9 << 4;
+const LAVA: BlockState = 11 << 4;
+const SAND: BlockState = 12 << 4;
+const GRAVEL: BlockState = 13 << 4;
+const SANDSTONE: BlockState = 24 << 4;
+const TALL_GRASS: BlockState = 31 << 4 | 1;
+const DEAD_BUSH: BlockState = 32 << 4;
+const SNOW_LAYER: BlockState = 78 << 4;
+const ICE: BlockState = 79 << 4;
+const CACTUS: BlockState = 81 << 4;
+
+// Kinds of trees(log and leaves)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tree {
+    Oak,
+    Birch,
+    Spruce,
+    Jungle,
+    Acacia,
+}
+
+impl Tree {
+    fn blocks(self) -> (BlockState, BlockState) {
+        match self {
+            Tree::Oak => (17 << 4, 18 << 4),
+            Tree::Spruce => (17 << 4 | 1, 18 << 4 | 1),
+            Tree::Birch => (17 << 4 | 2, 18 << 4 | 2),
+            Tree::Jungle => (17 << 4 | 3, 18 << 4 | 3),
+            Tree::Acacia => (162 << 4, 161 << 4),
+        }
+    }
+}
+
+/// Biome's look: surface's blocks and plants
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biome {
+    pub id: u8,
+    // Surface's top block and blocks under it
+    pub top: BlockState,
+    pub filler: BlockState,
+    // Chances of tree and plant per column
+    pub trees: f64,
+    pub plants: f64,
+    // Surface is covered by snow and water by ice
+    pub snowy: bool,
+}
+
+impl Biome {
+    const fn new(id: u8, top: BlockState, filler: BlockState, trees: f64, plants: f64) -> Self {
+        Self {
+            id,
+            top,
+            filler,
+            trees,
+            plants,
+            snowy: false,
+        }
+    }
+}
+
+pub const OCEAN: Biome = Biome::new(0, GRAVEL, GRAVEL, 0.0, 0.0);
+pub const PLAINS: Biome = Biome::new(1, GRASS, DIRT, 0.002, 0.12);
+pub const DESERT: Biome = Biome::new(2, SAND, SAND, 0.0, 0.01);
+pub const EXTREME_HILLS: Biome = Biome::new(3, GRASS, DIRT, 0.01, 0.03);
+pub const FOREST: Biome = Biome::new(4, GRASS, DIRT, 0.06, 0.05);
+pub const TAIGA: Biome = Biome::new(5, GRASS, DIRT, 0.05, 0.03);
+pub const SWAMPLAND: Biome = Biome::new(6, GRASS, DIRT, 0.015, 0.08);
+pub const ICE_PLAINS: Biome = Biome {
+    snowy: true,
+    ..Biome::new(12, GRASS, DIRT, 0.003, 0.0)
+};
+pub const BEACH: Biome = Biome::new(16, SAND, SAND, 0.0, 0.0);
+pub const JUNGLE: Biome = Biome::new(21, GRASS, DIRT, 0.1, 0.15);
+pub const DEEP_OCEAN: Biome = Biome::new(24, GRAVEL, GRAVEL, 0.0, 0.0);
+pub const COLD_BEACH: Biome = Biome {
+    snowy: true,
+    ..Biome::new(26, SAND, SAND, 0.0, 0.0)
+};
+pub const SAVANNA: Biome = Biome::new(35, GRASS, DIRT, 0.004, 0.1);
+
+// Noises of world's seed
+struct Noises {
+    elevation: OctaveNoise,
+    detail: OctaveNoise,
+    hills: OctaveNoise,
+    temperature: OctaveNoise,
+    rainfall: OctaveNoise,
+    cave_a: OctaveNoise,
+    cave_b: OctaveNoise,
+}
+
+impl Noises {
+    fn new(seed: i64, biome_scale: f64) -> Self {
+        let mut random = Random::new(seed);
+        Self {
+            elevation: OctaveNoise::new(&mut random, 6, 1.0 / 384.0),
+            detail: OctaveNoise::new(&mut random, 4, 1.0 / 64.0),
+            hills: OctaveNoise::new(&mut random, 3, 1.0 / 256.0),
+            temperature: OctaveNoise::new(&mut random, 3, 1.0 / (600.0 * biome_scale)),
+            rainfall: OctaveNoise::new(&mut random, 3, 1.0 / (600.0 * biome_scale)),
+            cave_a: OctaveNoise::new(&mut random, 2, 1.0 / 48.0),
+            cave_b: OctaveNoise::new(&mut random, 2, 1.0 / 48.0),
+        }
+    }
+}
+
+// Noise's value moved into 0.0..1.0
+fn unit(value: f64, spread: f64) -> f64 {
+    (0.5 + value * spread).clamp(0.0, 1.0)
+}
+
+/// Column's terrain: height of the top solid block and biome
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnInfo {
+    pub height: i32,
+    pub biome: Biome,
+    pub temperature: f64,
+}
+
+/// Terrain generator with hills, oceans, caves, biomes and trees(like "default" world's type)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainGenerator {
+    // Multiplier of hills' height(amplified world has 2.0)
+    pub height_scale: f64,
+    // Multiplier of biomes' size(large biomes have 4.0)
+    pub biome_scale: f64,
+}
+
+impl Default for TerrainGenerator {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+impl TerrainGenerator {
+    pub fn new(height_scale: f64, biome_scale: f64) -> Self {
+        Self {
+            height_scale,
+            biome_scale,
+        }
+    }
+
+    // Height and biome at world's coordinates
+    fn column(&self, noises: &Noises, x: i32, z: i32) -> ColumnInfo {
+        let (fx, fz) = (x as f64, z as f64);
+        // Elevation is a bit moved up, so there is more land than ocean
+        let elevation = (noises.elevation.sample2(fx, fz) * 2.2 + 0.2).clamp(-1.0, 1.0);
+        let hills = unit(noises.hills.sample2(fx, fz), 1.5);
+        let detail = noises.detail.sample2(fx, fz);
+        let base = if elevation < 0.0 {
+            SEA_LEVEL as f64 + 1.0 + elevation * 30.0
+        } else {
+            SEA_LEVEL as f64 + 1.0 + elevation * (12.0 + hills * 40.0) * self.height_scale
+        };
+        let height = (base + detail * (3.0 + hills * 10.0)).clamp(5.0, 250.0) as i32;
+        let temperature = unit(noises.temperature.sample2(fx, fz), 1.6);
+        let rainfall = unit(noises.rainfall.sample2(fx, fz), 1.6);
+        let biome = if height < 45 {
+            DEEP_OCEAN
+        } else if height < SEA_LEVEL - 2 {
+            OCEAN
+        } else if height <= SEA_LEVEL + 1 && elevation < 0.05 {
+            if temperature < 0.2 {
+                COLD_BEACH
+            } else {
+                BEACH
+            }
+        } else if temperature < 0.2 {
+            ICE_PLAINS
+        } else if hills > 0.72 && height > 85 {
+            EXTREME_HILLS
+        } else if temperature < 0.4 {
+            if rainfall > 0.35 {
+                TAIGA
+            } else {
+                PLAINS
+            }
+        } else if temperature < 0.7 {
+            if rainfall > 0.7 && height < SEA_LEVEL + 8 {
+                SWAMPLAND
+            } else if rainfall > 0.45 {
+                FOREST
+            } else {
+                PLAINS
+            }
+        } else if rainfall > 0.6 {
+            JUNGLE
+        } else if rainfall > 0.3 {
+            SAVANNA
+        } else {
+            DESERT
+        };
+        ColumnInfo {
+            height,
+            biome,
+            temperature,
+        }
+    }
+
+    /// Height and biome at world's coordinates for seed
+    pub fn column_info(&self, seed: i64, x: i32, z: i32) -> ColumnInfo {
+        self.column(&Noises::new(seed, self.biome_scale), x, z)
+    }
+}
+
+// Filling a column with stone, surface's blocks, water and bedrock
+fn fill_column(
+    column: &mut ChunkColumn,
+    x: usize,
+    z: usize,
+    info: &ColumnInfo,
+    random: &mut Random,
+) {
+    let height = info.height;
+    let biome = info.biome;
+    let underwater = height < SEA_LEVEL;
+    let depth = 3 + random.next_int(2) as i32;
+    let (top, filler) = if underwater {
+        if height > SEA_LEVEL - 8 && biome.id != DEEP_OCEAN.id {
+            (SAND, SAND)
+        } else {
+            (biome.top, biome.filler)
+        }
+    } else {
+        (biome.top, biome.filler)
+    };
+    for y in 0..=height {
+        let state = if y == height {
+            top
+        } else if y > height - depth {
+            filler
+        } else if y > height - depth - 2 && filler == SAND {
+            SANDSTONE
+        } else {
+            STONE
+        };
+        column.set_block(x, y as usize, z, state);
+    }
+    for y in height + 1..=SEA_LEVEL {
+        let state = if y == SEA_LEVEL && info.temperature < 0.2 {
+            ICE
+        } else {
+            WATER
+        };
+        column.set_block(x, y as usize, z, state);
+    }
+    if biome.snowy && !underwater && height < 255 {
+        column.set_block(x, height as usize + 1, z, SNOW_LAYER);
+    }
+    // Bedrock's layer is rough from y = 1 to y = 4
+    column.set_block(x, 0, z, BEDROCK);
+    for y in 1..5 {
+        if random.next_int(5) >= y {
+            column.set_block(x, y as usize, z, BEDROCK);
+        }
+    }
+    column.set_biome(x, z, biome.id);
+}
+
+// Carving tunnels where two 3D noises are near zero
+fn carve_caves(column: &mut ChunkColumn, noises: &Noises, x: usize, z: usize, height: i32) {
+    // Caves don't go under water to avoid flooding
+    let top = if height < SEA_LEVEL {
+        height - 8
+    } else {
+        height - 2
+    };
+    let (fx, fz) = (
+        (column.x * 16 + x as i32) as f64,
+        (column.z * 16 + z as i32) as f64,
+    );
+    for y in 6..top {
+        let fy = y as f64 * 2.0;
+        let a = noises.cave_a.sample(fx, fy, fz);
+        let b = noises.cave_b.sample(fx, fy, fz);
+        if a * a + b * b < 0.004 {
+            let state = if y < 11 { LAVA } else { AIR };
+            column.set_block(x, y as usize, z, state);
+        }
+    }
+}
+
+// Placing block only into air
+fn place(column: &mut ChunkColumn, x: i32, y: i32, z: i32, state: BlockState) {
+    if (0..16).contains(&x) && (0..256).contains(&y) && (0..16).contains(&z) {
+        let (x, y, z) = (x as usize, y as usize, z as usize);
+        if column.get_block(x, y, z) == AIR {
+            column.set_block(x, y, z, state);
+        }
+    }
+}
+
+// Growing a tree with trunk from (x, y, z)
+fn grow_tree(column: &mut ChunkColumn, x: i32, y: i32, z: i32, tree: Tree, random: &mut Random) {
+    let (log, leaves) = tree.blocks();
+    let height = match tree {
+        Tree::Spruce => 6 + random.next_int(3) as i32,
+        Tree::Jungle => 6 + random.next_int(5) as i32,
+        _ => 4 + random.next_int(3) as i32,
+    };
+    if y + height + 1 > 255 {
+        return;
+    }
+    let top = y + height;
+    for leaf_y in y + 2..=top {
+        let radius = match tree {
+            // Cone from rings of radius 2 and 1
+            Tree::Spruce if leaf_y == top => 0,
+            Tree::Spruce => 1 + (top - leaf_y) % 2,
+            _ if leaf_y < top - 3 => continue,
+            _ if leaf_y >= top - 1 => 1,
+            _ => 2,
+        };
+        for dx in -radius..=radius {
+            for dz in -radius..=radius {
+                // Corners are random on wide layers and empty on the top
+                let corner = dx.abs() == radius && dz.abs() == radius && radius > 0;
+                if corner && (leaf_y == top || random.next_int(2) == 0) {
+                    continue;
+                }
+                place(column, x + dx, leaf_y, z + dz, leaves);
+            }
+        }
+    }
+    place(column, x, top + 1, z, leaves);
+    for trunk_y in y..top {
+        column.set_block(x as usize, trunk_y as usize, z as usize, log);
+    }
+}
+
+// Trees and plants on surface(only inside of chunk, so neighbours aren't needed)
+fn decorate(column: &mut ChunkColumn, x: usize, z: usize, info: &ColumnInfo, random: &mut Random) {
+    let y = info.height;
+    if !(SEA_LEVEL..254).contains(&y) {
+        return;
+    }
+    let surface = column.get_block(x, y as usize, z);
+    let above = column.get_block(x, y as usize + 1, z);
+    if above != AIR && above != SNOW_LAYER {
+        return;
+    }
+    let biome = info.biome;
+    let inside = (2..14).contains(&x) && (2..14).contains(&z);
+    if surface == GRASS && inside && random.next_f64() < biome.trees {
+        let tree = match biome.id {
+            5 | 12 => Tree::Spruce,
+            3 if random.next_int(2) == 0 => Tree::Spruce,
+            4 if random.next_int(5) == 0 => Tree::Birch,
+            21 => Tree::Jungle,
+            35 => Tree::Acacia,
+            _ => Tree::Oak,
+        };
+        column.set_block(x, y as usize, z, DIRT);
+        grow_tree(column, x as i32, y + 1, z as i32, tree, random);
+    } else if random.next_f64() < biome.plants {
+        if surface == GRASS {
+            column.set_block(x, y as usize + 1, z, TALL_GRASS);
+        } else if surface == SAND && biome.id == DESERT.id {
+            if random.next_int(3) == 0 {
+                for dy in 1..=1 + random.next_int(3) as usize {
+                    column.set_block(x, y as usize + dy, z, CACTUS);
+                }
+            } else {
+                column.set_block(x, y as usize + 1, z, DEAD_BUSH);
+            }
+        }
+    }
+}
+
+impl ChunkGenerator for TerrainGenerator {
+    fn generate(&self, chunk_x: i32, chunk_z: i32, seed: i64) -> ChunkColumn {
+        let noises = Noises::new(seed, self.biome_scale);
+        let mut column = ChunkColumn::new(chunk_x, chunk_z, true);
+        let mut infos = Vec::with_capacity(256);
+        for z in 0..16 {
+            for x in 0..16 {
+                let (world_x, world_z) = (chunk_x * 16 + x as i32, chunk_z * 16 + z as i32);
+                let info = self.column(&noises, world_x, world_z);
+                let mut random = Random::at(seed, world_x, world_z, 0);
+                fill_column(&mut column, x, z, &info, &mut random);
+                carve_caves(&mut column, &noises, x, z, info.height);
+                infos.push(info);
+            }
+        }
+        for z in 0..16 {
+            for x in 0..16 {
+                let (world_x, world_z) = (chunk_x * 16 + x as i32, chunk_z * 16 + z as i32);
+                let mut random = Random::at(seed, world_x, world_z, 1);
+                decorate(&mut column, x, z, &infos[z * 16 + x], &mut random);
+            }
+        }
+        column
+    }
+}
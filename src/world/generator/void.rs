@@ -0,0 +1,39 @@
+use crate::world::generator::ChunkGenerator;
+use crate::world::section::BlockState;
+use crate::world::ChunkColumn;
+
+/// Generator of empty world with optional platform for spawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoidGenerator {
+    // Platform's block, center(x, y, z) and radius(0 - single block)
+    pub platform: Option<(BlockState, (i32, i32, i32), u8)>,
+}
+
+impl VoidGenerator {
+    pub fn new(platform: Option<(BlockState, (i32, i32, i32), u8)>) -> Self {
+        Self { platform }
+    }
+}
+
+impl ChunkGenerator for VoidGenerator {
+    fn generate(&self, x: i32, z: i32, _seed: i64) -> ChunkColumn {
+        let mut column = ChunkColumn::new(x, z, true);
+        let (block, (center_x, y, center_z), radius) = match self.platform {
+            Some(v) if (0..256).contains(&v.1 .1) => v,
+            _ => return column,
+        };
+        let radius = radius as i32;
+        // Platform's part inside of this chunk
+        for block_x in (center_x - radius).max(x * 16)..=(center_x + radius).min(x * 16 + 15) {
+            for block_z in (center_z - radius).max(z * 16)..=(center_z + radius).min(z * 16 + 15) {
+                column.set_block(
+                    (block_x & 15) as usize,
+                    y as usize,
+                    (block_z & 15) as usize,
+                    block,
+                );
+            }
+        }
+        column
+    }
+}
@@ -0,0 +1,316 @@
+use crate::utils::nbt::{Compound, Tag};
+use ahash::AHashMap;
+
+// Items of 1.12.2(ids below 256 are blocks' items)
+const ITEMS_DATA: &str = include_str!("items.json");
+
+// Enchanted book stores enchantments in another tag
+const ENCHANTED_BOOK: u16 = 403;
+
+// Item as it's written in data file
+#[derive(Deserialize)]
+struct ItemData {
+    id: u16,
+    name: String,
+    #[serde(default = "default_stack")]
+    stack: u8,
+    #[serde(default)]
+    durability: u16,
+    #[serde(default)]
+    subtypes: bool,
+}
+
+fn default_stack() -> u8 {
+    64
+}
+
+/// Item's type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemInfo {
+    pub id: u16,
+    // Name with "minecraft:"
+    pub name: String,
+    pub max_stack: u8,
+    // Max damage of tools and armor(0 - item doesn't break)
+    pub durability: u16,
+    // Damage is item's variant(like wool's color or dye)
+    pub subtypes: bool,
+}
+
+impl ItemInfo {
+    /// Item is block's item(can be placed)
+    pub fn is_block(&self) -> bool {
+        self.id < 256
+    }
+}
+
+// All items by ids and names
+struct ItemRegistry {
+    items: AHashMap<u16, ItemInfo>,
+    by_name: AHashMap<String, u16>,
+}
+
+impl ItemRegistry {
+    fn load() -> Self {
+        let data: Vec<ItemData> =
+            serde_json::from_str(ITEMS_DATA).expect("Invalid bundled items' data");
+        let mut registry = Self {
+            items: AHashMap::new(),
+            by_name: AHashMap::new(),
+        };
+        for item in data {
+            let name = format!("minecraft:{}", item.name);
+            registry.by_name.insert(name.clone(), item.id);
+            registry.items.insert(
+                item.id,
+                ItemInfo {
+                    id: item.id,
+                    name,
+                    max_stack: item.stack,
+                    durability: item.durability,
+                    subtypes: item.subtypes,
+                },
+            );
+        }
+        registry
+    }
+}
+
+lazy_static! {
+    static ref ITEMS: ItemRegistry = ItemRegistry::load();
+}
+
+/// Item's type by its id
+pub fn item(id: u16) -> Option<&'static ItemInfo> {
+    ITEMS.items.get(&id)
+}
+
+/// Item's type by its name(with or without "minecraft:")
+pub fn item_by_name(name: &str) -> Option<&'static ItemInfo> {
+    let id = match name.contains(':') {
+        true => ITEMS.by_name.get(name),
+        false => ITEMS.by_name.get(&format!("minecraft:{}", name)),
+    };
+    item(*id?)
+}
+
+/// Stack of items in [slot](https://wiki.vg/index.php?title=Slot_Data&oldid=14204)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    pub id: u16,
+    pub count: u8,
+    // Durability's damage or item's variant
+    pub damage: i16,
+    // Item's tag(display, enchantments and etc.)
+    pub nbt: Option<Compound>,
+}
+
+impl ItemStack {
+    pub fn new(id: u16, count: u8, damage: i16) -> Self {
+        Self {
+            id,
+            count,
+            damage,
+            nbt: None,
+        }
+    }
+
+    /// Stack by item's name(None if item doesn't exist)
+    pub fn of(name: &str, count: u8) -> Option<Self> {
+        Some(Self::new(item_by_name(name)?.id, count, 0))
+    }
+
+    /// Item's type(None if it's unknown)
+    pub fn item(&self) -> Option<&'static ItemInfo> {
+        item(self.id)
+    }
+
+    /// Max count in one slot
+    pub fn max_stack(&self) -> u8 {
+        self.item().map(|v| v.max_stack).unwrap_or(64)
+    }
+
+    /// Stacks can be merged(same item, damage and tag)
+    pub fn is_similar(&self, other: &ItemStack) -> bool {
+        self.id == other.id && self.damage == other.damage && self.nbt == other.nbt
+    }
+
+    // Item's tag, created if it doesn't exist
+    fn tag_mut(&mut self) -> &mut Compound {
+        self.nbt.get_or_insert_with(Compound::new)
+    }
+
+    // Removing empty display and tag
+    fn clean_tag(&mut self) {
+        if let Some(tag) = &mut self.nbt {
+            let empty_display = tag
+                .get("display")
+                .and_then(|v| v.as_compound())
+                .map(|v| v.is_empty())
+                .unwrap_or(false);
+            if empty_display {
+                tag.remove("display");
+            }
+            if tag.is_empty() {
+                self.nbt = None;
+            }
+        }
+    }
+
+    // Tag's display compound
+    fn display(&self) -> Option<&Compound> {
+        self.nbt.as_ref()?.get("display")?.as_compound()
+    }
+
+    fn display_mut(&mut self) -> &mut Compound {
+        let tag = self.tag_mut();
+        if !matches!(tag.get("display"), Some(Tag::Compound(_))) {
+            tag.insert(String::from("display"), Tag::Compound(Compound::new()));
+        }
+        tag.get_mut("display").unwrap().as_compound_mut().unwrap()
+    }
+
+    /// Custom name(legacy formatted text)
+    pub fn display_name(&self) -> Option<&str> {
+        self.display()?.get("Name")?.as_str()
+    }
+
+    /// Setting custom name(None - item's default name)
+    pub fn set_display_name(&mut self, name: Option<&str>) {
+        match name {
+            Some(name) => {
+                self.display_mut()
+                    .insert(String::from("Name"), Tag::from(name));
+            }
+            None => {
+                self.display_mut().remove("Name");
+                self.clean_tag();
+            }
+        }
+    }
+
+    /// Lines of description under the name
+    pub fn lore(&self) -> Vec<String> {
+        match self.display().and_then(|v| v.get("Lore")?.as_list()) {
+            Some(lines) => lines
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Setting lines of description(empty - no description)
+    pub fn set_lore(&mut self, lines: &[&str]) {
+        if lines.is_empty() {
+            self.display_mut().remove("Lore");
+            self.clean_tag();
+            return;
+        }
+        let lines = lines.iter().map(|v| Tag::from(*v)).collect();
+        self.display_mut()
+            .insert(String::from("Lore"), Tag::List(lines));
+    }
+
+    // Tag's name of enchantments(enchanted book only stores them)
+    fn enchantments_key(&self) -> &'static str {
+        match self.id {
+            ENCHANTED_BOOK => "StoredEnchantments",
+            _ => "ench",
+        }
+    }
+
+    /// Enchantments as ids and levels
+    pub fn enchantments(&self) -> Vec<(i16, i16)> {
+        let list = self
+            .nbt
+            .as_ref()
+            .and_then(|v| v.get(self.enchantments_key())?.as_list());
+        match list {
+            Some(list) => list
+                .iter()
+                .filter_map(|v| v.as_compound())
+                .filter_map(|v| Some((v.get("id")?.as_i16()?, v.get("lvl")?.as_i16()?)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Adding enchantment or changing its level(level 0 removes it)
+    pub fn set_enchantment(&mut self, id: i16, level: i16) {
+        let mut enchantments = self.enchantments();
+        enchantments.retain(|v| v.0 != id);
+        if level > 0 {
+            enchantments.push((id, level));
+        }
+        let key = self.enchantments_key();
+        if enchantments.is_empty() {
+            self.tag_mut().remove(key);
+            self.clean_tag();
+            return;
+        }
+        let list = enchantments
+            .iter()
+            .map(|(id, level)| {
+                let mut enchantment = Compound::new();
+                enchantment.insert(String::from("id"), Tag::Short(*id));
+                enchantment.insert(String::from("lvl"), Tag::Short(*level));
+                Tag::Compound(enchantment)
+            })
+            .collect();
+        self.tag_mut().insert(String::from(key), Tag::List(list));
+    }
+
+    /// Item doesn't lose durability
+    pub fn is_unbreakable(&self) -> bool {
+        self.nbt
+            .as_ref()
+            .and_then(|v| v.get("Unbreakable")?.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn set_unbreakable(&mut self, unbreakable: bool) {
+        if unbreakable {
+            self.tag_mut()
+                .insert(String::from("Unbreakable"), Tag::from(true));
+        } else {
+            self.tag_mut().remove("Unbreakable");
+            self.clean_tag();
+        }
+    }
+
+    /// Stack as NBT for saving(like in player's inventory and chests)
+    pub fn to_nbt(&self) -> Compound {
+        let mut compound = Compound::new();
+        let name = match self.item() {
+            Some(v) => Tag::from(v.name.as_str()),
+            None => Tag::Short(self.id as i16),
+        };
+        compound.insert(String::from("id"), name);
+        compound.insert(String::from("Count"), Tag::Byte(self.count as i8));
+        compound.insert(String::from("Damage"), Tag::Short(self.damage));
+        if let Some(tag) = &self.nbt {
+            compound.insert(String::from("tag"), Tag::Compound(tag.clone()));
+        }
+        compound
+    }
+
+    /// Stack from saved NBT(id is name or number), None if item is unknown or empty
+    pub fn from_nbt(compound: &Compound) -> Option<Self> {
+        let id = match compound.get("id")? {
+            Tag::String(name) => item_by_name(name)?.id,
+            tag => tag.as_i16()? as u16,
+        };
+        let count = compound.get("Count")?.as_i8()?;
+        if count <= 0 {
+            return None;
+        }
+        let damage = compound.get("Damage").and_then(|v| v.as_i16()).unwrap_or(0);
+        Some(Self {
+            id,
+            count: count as u8,
+            damage,
+            nbt: compound.get("tag").and_then(|v| v.as_compound()).cloned(),
+        })
+    }
+}
@@ -0,0 +1,298 @@
+use crate::config::{DEFAULT_GAMEMODE, DIFFICULTY, LEVEL_TYPE, SPAWN_POSITION};
+use crate::utils::nbt::{from_compound, read_gzip, to_compound, write_gzip, Compound, Tag};
+use crate::world::anvil::DATA_VERSION;
+use crate::{io_error, SResult, SimpleError};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Anvil's format version in level.dat
+pub const ANVIL_VERSION: i32 = 19133;
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Game's rules like in 1.12.2(all values are strings in level.dat)
+pub fn default_game_rules() -> BTreeMap<String, String> {
+    [
+        ("announceAdvancements", "true"),
+        ("commandBlockOutput", "true"),
+        ("disableElytraMovementCheck", "false"),
+        ("doDaylightCycle", "true"),
+        ("doEntityDrops", "true"),
+        ("doFireTick", "true"),
+        ("doLimitedCrafting", "false"),
+        ("doMobLoot", "true"),
+        ("doMobSpawning", "true"),
+        ("doTileDrops", "true"),
+        ("doWeatherCycle", "true"),
+        ("gameLoopFunction", "-"),
+        ("keepInventory", "false"),
+        ("logAdminCommands", "true"),
+        ("maxCommandChainLength", "65536"),
+        ("maxEntityCramming", "24"),
+        ("mobGriefing", "true"),
+        ("naturalRegeneration", "true"),
+        ("randomTickSpeed", "3"),
+        ("reducedDebugInfo", "false"),
+        ("sendCommandFeedback", "true"),
+        ("showDeathMessages", "true"),
+        ("spawnRadius", "10"),
+        ("spectatorsGenerateChunks", "true"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+/// Seed from text like vanilla's level-seed(number or Java's string hash, random if empty)
+pub fn parse_seed(text: &str) -> i64 {
+    let text = text.trim();
+    if text.is_empty() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|v| v.as_nanos() as u64)
+            .unwrap_or(0);
+        // Mixing bits of time(splitmix64)
+        let mut v = nanos.wrapping_add(0x9E3779B97F4A7C15);
+        v = (v ^ (v >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        v = (v ^ (v >> 27)).wrapping_mul(0x94D049BB133111EB);
+        return (v ^ (v >> 31)) as i64;
+    }
+    match text.parse::<i64>() {
+        Ok(v) if v != 0 => v,
+        _ => text
+            .encode_utf16()
+            .fold(0i32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as i32)) as i64,
+    }
+}
+
+/// Game's version which saved the world
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct WorldVersion {
+    pub id: i32,
+    pub name: String,
+    pub snapshot: bool,
+}
+
+impl Default for WorldVersion {
+    fn default() -> Self {
+        Self {
+            id: DATA_VERSION,
+            name: String::from("1.12.2"),
+            snapshot: false,
+        }
+    }
+}
+
+/// World's metadata from [level.dat](https://minecraft.fandom.com/wiki/Java_Edition_level_format#level.dat_format)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldInfo {
+    #[serde(rename = "LevelName")]
+    pub name: String,
+    #[serde(rename = "RandomSeed")]
+    pub seed: i64,
+    #[serde(rename = "SpawnX")]
+    pub spawn_x: i32,
+    #[serde(rename = "SpawnY")]
+    pub spawn_y: i32,
+    #[serde(rename = "SpawnZ")]
+    pub spawn_z: i32,
+    // Count of ticks since world's creation
+    #[serde(rename = "Time")]
+    pub time: i64,
+    // Time of day(0 - sunrise, 6000 - noon, 24000 - next day)
+    #[serde(rename = "DayTime")]
+    pub day_time: i64,
+    pub raining: bool,
+    #[serde(rename = "rainTime")]
+    pub rain_time: i32,
+    pub thundering: bool,
+    #[serde(rename = "thunderTime")]
+    pub thunder_time: i32,
+    #[serde(rename = "clearWeatherTime")]
+    pub clear_weather_time: i32,
+    #[serde(rename = "Difficulty")]
+    pub difficulty: u8,
+    #[serde(rename = "DifficultyLocked")]
+    pub difficulty_locked: bool,
+    // Default game's mode of new players
+    #[serde(rename = "GameType")]
+    pub gamemode: i32,
+    pub hardcore: bool,
+    #[serde(rename = "allowCommands")]
+    pub allow_commands: bool,
+    // Generator's name(default, flat, largeBiomes, amplified) and its options
+    #[serde(rename = "generatorName")]
+    pub generator_name: String,
+    #[serde(rename = "generatorVersion")]
+    pub generator_version: i32,
+    #[serde(rename = "generatorOptions")]
+    pub generator_options: String,
+    #[serde(rename = "MapFeatures")]
+    pub map_features: bool,
+    #[serde(rename = "GameRules")]
+    pub game_rules: BTreeMap<String, String>,
+    pub initialized: bool,
+    // Last saving's time in milliseconds
+    #[serde(rename = "LastPlayed")]
+    pub last_played: i64,
+    #[serde(rename = "version")]
+    pub anvil_version: i32,
+    #[serde(rename = "DataVersion")]
+    pub data_version: i32,
+    #[serde(rename = "Version")]
+    pub version: WorldVersion,
+    // Other level.dat's values(kept as is on saving)
+    #[serde(skip)]
+    pub other: Compound,
+}
+
+impl Default for WorldInfo {
+    fn default() -> Self {
+        let (spawn_x, spawn_y, spawn_z) = SPAWN_POSITION;
+        Self {
+            name: String::from("world"),
+            seed: 0,
+            spawn_x,
+            spawn_y,
+            spawn_z,
+            time: 0,
+            day_time: 0,
+            raining: false,
+            rain_time: 0,
+            thundering: false,
+            thunder_time: 0,
+            clear_weather_time: 0,
+            difficulty: DIFFICULTY,
+            difficulty_locked: false,
+            gamemode: DEFAULT_GAMEMODE as i32,
+            hardcore: false,
+            allow_commands: false,
+            generator_name: String::from(LEVEL_TYPE),
+            generator_version: 0,
+            generator_options: String::new(),
+            map_features: true,
+            game_rules: default_game_rules(),
+            initialized: true,
+            last_played: 0,
+            anvil_version: ANVIL_VERSION,
+            data_version: DATA_VERSION,
+            version: WorldVersion::default(),
+            other: Compound::new(),
+        }
+    }
+}
+
+impl WorldInfo {
+    /// New world's metadata with name and seed(other values from config)
+    pub fn new(name: &str, seed: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            seed,
+            ..Self::default()
+        }
+    }
+
+    /// Metadata from level.dat's root compound(with Data)
+    pub fn from_nbt(root: &Compound) -> SResult<Self> {
+        let data = root
+            .get("Data")
+            .and_then(|v| v.as_compound())
+            .ok_or_else(|| SimpleError(String::from("Invalid level.dat: no Data"), None))?;
+        let mut info: Self = from_compound(data.clone())?;
+        // Missing rules get default values
+        for (rule, value) in default_game_rules() {
+            info.game_rules.entry(rule).or_insert(value);
+        }
+        info.other = data.clone();
+        Ok(info)
+    }
+
+    /// Metadata to level.dat's root compound
+    pub fn to_nbt(&self) -> SResult<Compound> {
+        let mut data = self.other.clone();
+        data.extend(to_compound(self)?);
+        let mut root = Compound::new();
+        root.insert(String::from("Data"), Tag::Compound(data));
+        Ok(root)
+    }
+
+    /// Loading from world's directory(level.dat or level.dat_old if level.dat is broken).
+    /// None if world doesn't have level.dat
+    pub fn load(directory: &Path) -> SResult<Option<Self>> {
+        let mut result = Ok(None);
+        for name in ["level.dat", "level.dat_old"] {
+            let path = directory.join(name);
+            if !path.exists() {
+                continue;
+            }
+            result = fs::read(&path)
+                .map_err(|err| io_error("Failed to read level.dat", err))
+                .and_then(|v| read_gzip(&v))
+                .and_then(|v| Self::from_nbt(&v))
+                .map(Some);
+            match &result {
+                Ok(_) => break,
+                Err(err) => warn!("Failed to load {}: {}", path.display(), err),
+            }
+        }
+        result
+    }
+
+    /// Saving into level.dat like vanilla: writing level.dat_new,
+    /// keeping previous level.dat as level.dat_old and renaming level.dat_new
+    pub fn save(&mut self, directory: &Path) -> SResult<()> {
+        self.last_played = now_millis();
+        let data = write_gzip(&self.to_nbt()?)?;
+        fs::create_dir_all(directory)
+            .map_err(|err| io_error("Failed to create world's directory", err))?;
+        let path = directory.join("level.dat");
+        let new = directory.join("level.dat_new");
+        let old = directory.join("level.dat_old");
+        fs::write(&new, data).map_err(|err| io_error("Failed to write level.dat_new", err))?;
+        if path.exists() {
+            fs::rename(&path, &old).map_err(|err| io_error("Failed to backup level.dat", err))?;
+        }
+        fs::rename(&new, &path).map_err(|err| io_error("Failed to replace level.dat", err))
+    }
+
+    /// Game's rule's value(None if rule doesn't exist)
+    pub fn game_rule(&self, name: &str) -> Option<&str> {
+        self.game_rules.get(name).map(|v| v.as_str())
+    }
+
+    /// Game's rule as boolean(false if rule doesn't exist)
+    pub fn game_rule_bool(&self, name: &str) -> bool {
+        self.game_rule(name) == Some("true")
+    }
+
+    /// Spawn point(x, y, z)
+    pub fn spawn(&self) -> (i32, i32, i32) {
+        (self.spawn_x, self.spawn_y, self.spawn_z)
+    }
+
+    /// Time of day for client(negative if day's cycle is stopped)
+    pub fn client_day_time(&self) -> i64 {
+        if self.game_rule_bool("doDaylightCycle") {
+            self.day_time
+        } else {
+            -self.day_time.max(1)
+        }
+    }
+
+    /// Advancing world's time by one tick
+    pub fn tick_time(&mut self) {
+        self.time += 1;
+        if self.game_rule_bool("doDaylightCycle") {
+            self.day_time += 1;
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use crate::world::bits::NibbleArray;
+use crate::world::block::{light_emission, opacity};
+use crate::world::chunk::{ChunkColumn, SECTIONS};
+use crate::world::section::{BlockState, SECTION_VOLUME};
+use ahash::AHashMap;
+use std::collections::VecDeque;
+
+// Height of column in blocks
+const HEIGHT: i32 = (SECTIONS * 16) as i32;
+// Max light's level
+pub const MAX_LIGHT: u8 = 15;
+
+// Offsets of block's neighbours
+const FACES: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Type of light(from blocks like torches or from sky)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightType {
+    Block,
+    Sky,
+}
+
+// Block's coordinates and light's level
+type LightNode = (i32, i32, i32, u8);
+
+/// Height of sky's light in column's local coordinates:
+/// y over the highest block which isn't transparent(0 - sky's light reaches the bottom)
+pub fn light_height(column: &ChunkColumn, x: usize, z: usize) -> usize {
+    for (index, section) in column.sections.iter().enumerate().rev() {
+        let section = match section {
+            Some(v) if !v.is_empty() => v,
+            _ => continue,
+        };
+        for y in (0..16).rev() {
+            if opacity(section.get(x, y, z)) > 0 {
+                return index * 16 + y + 1;
+            }
+        }
+    }
+    0
+}
+
+fn get_light(column: &ChunkColumn, kind: LightType, x: usize, y: usize, z: usize) -> u8 {
+    match kind {
+        LightType::Block => column.get_block_light(x, y, z),
+        LightType::Sky => column.get_sky_light(x, y, z),
+    }
+}
+
+fn set_light(column: &mut ChunkColumn, kind: LightType, x: usize, y: usize, z: usize, value: u8) {
+    // Air sections are created only for not default light
+    let default = match kind {
+        LightType::Block => 0,
+        LightType::Sky => MAX_LIGHT,
+    };
+    if column.section(y >> 4).is_none() && value == default {
+        return;
+    }
+    match kind {
+        LightType::Block => column.set_block_light(x, y, z, value),
+        LightType::Sky => column.set_sky_light(x, y, z, value),
+    }
+    column.dirty = true;
+}
+
+// Blocks and light by coordinates(None - outside of loaded blocks)
+trait LightAccess {
+    fn block(&self, x: i32, y: i32, z: i32) -> Option<BlockState>;
+    fn light(&self, kind: LightType, x: i32, y: i32, z: i32) -> Option<u8>;
+    fn set_light(&mut self, kind: LightType, x: i32, y: i32, z: i32, value: u8);
+}
+
+// Column alone in local coordinates
+impl LightAccess for ChunkColumn {
+    fn block(&self, x: i32, y: i32, z: i32) -> Option<BlockState> {
+        if !(0..16).contains(&x) || !(0..16).contains(&z) {
+            return None;
+        }
+        Some(self.get_block(x as usize, y as usize, z as usize))
+    }
+
+    fn light(&self, kind: LightType, x: i32, y: i32, z: i32) -> Option<u8> {
+        if !(0..16).contains(&x) || !(0..16).contains(&z) {
+            return None;
+        }
+        Some(get_light(self, kind, x as usize, y as usize, z as usize))
+    }
+
+    fn set_light(&mut self, kind: LightType, x: i32, y: i32, z: i32, value: u8) {
+        if (0..16).contains(&x) && (0..16).contains(&z) {
+            set_light(self, kind, x as usize, y as usize, z as usize, value)
+        }
+    }
+}
+
+// Loaded chunks in world's coordinates
+type Chunks = AHashMap<(i32, i32), ChunkColumn>;
+
+impl LightAccess for Chunks {
+    fn block(&self, x: i32, y: i32, z: i32) -> Option<BlockState> {
+        let column = self.get(&(x >> 4, z >> 4))?;
+        Some(column.get_block((x & 15) as usize, y as usize, (z & 15) as usize))
+    }
+
+    fn light(&self, kind: LightType, x: i32, y: i32, z: i32) -> Option<u8> {
+        let column = self.get(&(x >> 4, z >> 4))?;
+        Some(get_light(
+            column,
+            kind,
+            (x & 15) as usize,
+            y as usize,
+            (z & 15) as usize,
+        ))
+    }
+
+    fn set_light(&mut self, kind: LightType, x: i32, y: i32, z: i32, value: u8) {
+        if let Some(column) = self.get_mut(&(x >> 4, z >> 4)) {
+            set_light(
+                column,
+                kind,
+                (x & 15) as usize,
+                y as usize,
+                (z & 15) as usize,
+                value,
+            )
+        }
+    }
+}
+
+// Spreading light from queued blocks to neighbours(BFS)
+fn propagate<A: LightAccess>(access: &mut A, kind: LightType, queue: &mut VecDeque<LightNode>) {
+    while let Some((x, y, z, _)) = queue.pop_front() {
+        let level = match access.light(kind, x, y, z) {
+            Some(v) if v > 1 => v,
+            _ => continue,
+        };
+        for (dx, dy, dz) in FACES {
+            let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+            if !(0..HEIGHT).contains(&ny) {
+                continue;
+            }
+            let state = match access.block(nx, ny, nz) {
+                Some(v) => v,
+                None => continue,
+            };
+            // Every block takes at least one level
+            let value = level.saturating_sub(opacity(state).max(1));
+            if access.light(kind, nx, ny, nz).unwrap_or(MAX_LIGHT) < value {
+                access.set_light(kind, nx, ny, nz, value);
+                queue.push_back((nx, ny, nz, value));
+            }
+        }
+    }
+}
+
+// Removing light which came from queued blocks(with their previous levels),
+// blocks lit by other sources are queued for spreading light again
+fn remove<A: LightAccess>(
+    access: &mut A,
+    kind: LightType,
+    removal: &mut VecDeque<LightNode>,
+    relight: &mut VecDeque<LightNode>,
+) {
+    while let Some((x, y, z, level)) = removal.pop_front() {
+        for (dx, dy, dz) in FACES {
+            let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+            if !(0..HEIGHT).contains(&ny) {
+                continue;
+            }
+            let current = match access.light(kind, nx, ny, nz) {
+                Some(v) if v > 0 => v,
+                _ => continue,
+            };
+            if current >= level {
+                relight.push_back((nx, ny, nz, current));
+                continue;
+            }
+            // Emitting blocks keep their own light
+            let emission = match kind {
+                LightType::Block => access.block(nx, ny, nz).map(light_emission).unwrap_or(0),
+                LightType::Sky => 0,
+            };
+            access.set_light(kind, nx, ny, nz, emission);
+            if emission > 0 {
+                relight.push_back((nx, ny, nz, emission));
+            }
+            removal.push_back((nx, ny, nz, current));
+        }
+    }
+}
+
+/// Computing light of column without its neighbours(after generation or loading without light)
+pub fn light_column(column: &mut ChunkColumn) {
+    let mut heights = [0; 256];
+    for z in 0..16 {
+        for x in 0..16 {
+            heights[z << 4 | x] = light_height(column, x, z);
+        }
+    }
+    let has_sky_light = column.has_sky_light;
+    // Air under blocks is dark, so it needs sections
+    let top = heights.iter().copied().max().unwrap_or(0);
+    if has_sky_light {
+        for index in 0..top.div_ceil(16) {
+            column.section_mut(index);
+        }
+    }
+    let mut block_queue = VecDeque::new();
+    for (index, section) in column.sections.iter_mut().enumerate() {
+        let section = match section {
+            Some(v) => v,
+            None => continue,
+        };
+        section.block_light = NibbleArray::new(SECTION_VOLUME, 0);
+        for y in 0..16 {
+            for z in 0..16 {
+                for x in 0..16 {
+                    let height = heights[z << 4 | x];
+                    let sky = match index * 16 + y >= height {
+                        true => MAX_LIGHT,
+                        false => 0,
+                    };
+                    section.set_sky_light(x, y, z, sky);
+                    let emission = light_emission(section.get(x, y, z));
+                    if emission > 0 {
+                        section.set_block_light(x, y, z, emission);
+                        block_queue.push_back((
+                            x as i32,
+                            (index * 16 + y) as i32,
+                            z as i32,
+                            emission,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    column.dirty = true;
+    propagate(column, LightType::Block, &mut block_queue);
+    if !has_sky_light {
+        return;
+    }
+    // Sky's light spreads from blocks over height to lower neighbours and down
+    let mut sky_queue = VecDeque::new();
+    for z in 0..16 {
+        for x in 0..16 {
+            let height = heights[z << 4 | x];
+            let mut highest = height;
+            for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let (nx, nz) = (x as i32 + dx, z as i32 + dz);
+                if (0..16).contains(&nx) && (0..16).contains(&nz) {
+                    highest = highest.max(heights[(nz << 4 | nx) as usize]);
+                }
+            }
+            for y in height..=highest.min(HEIGHT as usize - 1) {
+                sky_queue.push_back((x as i32, y as i32, z as i32, MAX_LIGHT));
+            }
+        }
+    }
+    propagate(column, LightType::Sky, &mut sky_queue);
+}
+
+/// Spreading light between chunk and its loaded neighbours
+pub fn stitch_chunk(chunks: &mut Chunks, chunk_x: i32, chunk_z: i32) {
+    let column = match chunks.get(&(chunk_x, chunk_z)) {
+        Some(v) => v,
+        None => return,
+    };
+    let kinds: &[LightType] = match column.has_sky_light {
+        true => &[LightType::Block, LightType::Sky],
+        false => &[LightType::Block],
+    };
+    let mut queues = [VecDeque::new(), VecDeque::new()];
+    for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let neighbour = match chunks.get(&(chunk_x + dx, chunk_z + dz)) {
+            Some(v) => v,
+            None => continue,
+        };
+        // Light over all sections is the same
+        let top = [column, neighbour]
+            .iter()
+            .filter_map(|v| v.sections.iter().rposition(|v| v.is_some()))
+            .max()
+            .map(|v| (v + 1) * 16)
+            .unwrap_or(0) as i32;
+        for i in 0..16 {
+            // Blocks on both sides of border
+            let (x, z) = match (dx, dz) {
+                (1, _) => (15, i),
+                (-1, _) => (0, i),
+                (_, 1) => (i, 15),
+                _ => (i, 0),
+            };
+            let (x, z) = (chunk_x * 16 + x, chunk_z * 16 + z);
+            let (nx, nz) = (x + dx, z + dz);
+            for y in 0..top {
+                for (kind, queue) in kinds.iter().zip(queues.iter_mut()) {
+                    let own = chunks.light(*kind, x, y, z).unwrap_or(0);
+                    let other = chunks.light(*kind, nx, y, nz).unwrap_or(0);
+                    if own > other + 1 {
+                        queue.push_back((x, y, z, own));
+                    } else if other > own + 1 {
+                        queue.push_back((nx, y, nz, other));
+                    }
+                }
+            }
+        }
+    }
+    for (kind, queue) in kinds.iter().zip(queues.iter_mut()) {
+        propagate(chunks, *kind, queue);
+    }
+}
+
+/// Updating light after block's change in world's coordinates.
+/// Height is sky light's height of block's column before the change
+pub fn update_light(chunks: &mut Chunks, x: i32, y: i32, z: i32, height: usize) {
+    let column = match chunks.get(&(x >> 4, z >> 4)) {
+        Some(v) => v,
+        None => return,
+    };
+    let has_sky_light = column.has_sky_light;
+    let new_height = light_height(column, (x & 15) as usize, (z & 15) as usize) as i32;
+    let state = column.get_block((x & 15) as usize, y as usize, (z & 15) as usize);
+
+    // Block's light
+    let mut removal = VecDeque::new();
+    let mut relight = VecDeque::new();
+    let level = chunks.light(LightType::Block, x, y, z).unwrap_or(0);
+    chunks.set_light(LightType::Block, x, y, z, 0);
+    removal.push_back((x, y, z, level));
+    remove(chunks, LightType::Block, &mut removal, &mut relight);
+    let emission = light_emission(state);
+    if emission > 0 {
+        chunks.set_light(LightType::Block, x, y, z, emission);
+        relight.push_back((x, y, z, emission));
+    }
+    // Light can come through changed block
+    for (dx, dy, dz) in FACES {
+        relight.push_back((x + dx, y + dy, z + dz, 0));
+    }
+    propagate(chunks, LightType::Block, &mut relight);
+    if !has_sky_light {
+        return;
+    }
+
+    // Sky's light: blocks between old and new height lose or get direct light
+    let height = height as i32;
+    let level = chunks.light(LightType::Sky, x, y, z).unwrap_or(0);
+    chunks.set_light(LightType::Sky, x, y, z, 0);
+    removal.push_back((x, y, z, level));
+    for cy in height..new_height {
+        let level = chunks.light(LightType::Sky, x, cy, z).unwrap_or(0);
+        if level > 0 {
+            chunks.set_light(LightType::Sky, x, cy, z, 0);
+            removal.push_back((x, cy, z, level));
+        }
+    }
+    remove(chunks, LightType::Sky, &mut removal, &mut relight);
+    for cy in new_height..height.max(y + 1) {
+        chunks.set_light(LightType::Sky, x, cy, z, MAX_LIGHT);
+        relight.push_back((x, cy, z, MAX_LIGHT));
+    }
+    for (dx, dy, dz) in FACES {
+        relight.push_back((x + dx, y + dy, z + dz, 0));
+    }
+    propagate(chunks, LightType::Sky, &mut relight);
+}
@@ -0,0 +1,334 @@
+pub mod anvil;
+pub mod bits;
+pub mod block;
+pub mod chunk;
+pub mod generator;
+pub mod item;
+pub mod level;
+pub mod light;
+pub mod region;
+pub mod section;
+
+use crate::world::anvil::RegionStorage;
+use crate::world::block::{block, collision_box, door_box, Collision};
+use crate::world::generator::pool::Generated;
+use crate::world::generator::{ChunkGenerator, GeneratorPool};
+use crate::world::light::{light_column, light_height};
+use ahash::{AHashMap, AHashSet};
+pub use chunk::ChunkColumn;
+pub use level::WorldInfo;
+pub use section::{BlockState, ChunkSection};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Loaded chunks of a dimension
+pub struct World {
+    pub chunks: AHashMap<(i32, i32), ChunkColumn>,
+    // Dimension has sky light(overworld)
+    pub has_sky_light: bool,
+    // Region's files(None - world only in memory)
+    pub storage: Option<RegionStorage>,
+    // Generator of new chunks(None - new chunks are empty) and world's seed
+    pub generator: Option<Arc<dyn ChunkGenerator>>,
+    pub seed: i64,
+    // Workers generating chunks in background and chunks waiting for them
+    pool: Option<GeneratorPool>,
+    pending: AHashSet<(i32, i32)>,
+    // Chunks which failed to load, they aren't saved to keep their data on disk
+    broken: AHashSet<(i32, i32)>,
+}
+
+impl World {
+    // World only in memory
+    pub fn new(has_sky_light: bool) -> Self {
+        Self {
+            chunks: AHashMap::new(),
+            has_sky_light,
+            storage: None,
+            generator: None,
+            seed: 0,
+            pool: None,
+            pending: AHashSet::new(),
+            broken: AHashSet::new(),
+        }
+    }
+
+    // World with chunks from region's files in directory(like world/region)
+    pub fn open(directory: &Path, has_sky_light: bool) -> Self {
+        Self {
+            storage: Some(RegionStorage::new(directory)),
+            ..Self::new(has_sky_light)
+        }
+    }
+
+    // Setting generator of new chunks with world's seed
+    pub fn with_generator(mut self, generator: Box<dyn ChunkGenerator>, seed: i64) -> Self {
+        self.generator = Some(Arc::from(generator));
+        self.seed = seed;
+        self
+    }
+
+    // Starting workers for generating chunks in background
+    pub fn start_workers(&mut self, workers: usize) {
+        if let Some(generator) = &self.generator {
+            self.pool = Some(GeneratorPool::new(generator.clone(), self.seed, workers));
+        }
+    }
+
+    // Loading chunk from disk or starting its generation in background.
+    // Returns true if chunk is ready(without workers chunk is generated immediately)
+    pub fn request_chunk(&mut self, x: i32, z: i32) -> bool {
+        if self.chunks.contains_key(&(x, z)) {
+            return true;
+        }
+        if self.pending.contains(&(x, z)) {
+            if let Some(pool) = &self.pool {
+                pool.resume(x, z);
+            }
+            return false;
+        }
+        if let Some(storage) = &mut self.storage {
+            match storage.load_chunk(x, z, self.has_sky_light) {
+                Ok(Some(v)) => {
+                    self.insert_chunk(v);
+                    return true;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    error!("Failed to load chunk {}, {}: {}", x, z, err);
+                    let column = self.broken_chunk(x, z);
+                    self.insert_chunk(column);
+                    return true;
+                }
+            }
+        }
+        match &self.pool {
+            Some(pool) => {
+                pool.submit(x, z);
+                self.pending.insert((x, z));
+                false
+            }
+            None => {
+                self.chunk(x, z);
+                true
+            }
+        }
+    }
+
+    // Taking chunks generated by workers
+    pub fn receive_generated(&mut self) {
+        let pool = match &self.pool {
+            Some(v) => v,
+            None => return,
+        };
+        for result in pool.generated() {
+            let mut column = match result {
+                Generated::Chunk(v) => *v,
+                // Failed chunk is empty and isn't saved, so it's generated again after restart
+                Generated::Failed(x, z) => {
+                    error!("Failed to generate chunk {}, {}", x, z);
+                    let mut column = ChunkColumn::new(x, z, self.has_sky_light);
+                    light_column(&mut column);
+                    column.dirty = false;
+                    column
+                }
+                Generated::Cancelled(x, z) => {
+                    self.pending.remove(&(x, z));
+                    continue;
+                }
+            };
+            self.pending.remove(&(column.x, column.z));
+            column.has_sky_light = self.has_sky_light;
+            // Chunk could be generated on game's thread while waiting
+            if !self.chunks.contains_key(&(column.x, column.z)) {
+                self.insert_chunk(column);
+            }
+        }
+    }
+
+    // Cancelling background requests of chunks which aren't wanted anymore
+    pub fn cancel_requests(&mut self, wanted: impl Fn(&(i32, i32)) -> bool) {
+        if let Some(pool) = &self.pool {
+            for chunk in self.pending.iter().filter(|v| !wanted(v)) {
+                pool.cancel(chunk.0, chunk.1);
+            }
+        }
+    }
+
+    // Chunk is being generated in background
+    pub fn is_pending(&self, x: i32, z: i32) -> bool {
+        self.pending.contains(&(x, z))
+    }
+
+    // Adding chunk and spreading light between it and its neighbours
+    fn insert_chunk(&mut self, column: ChunkColumn) {
+        let (x, z) = (column.x, column.z);
+        self.chunks.insert((x, z), column);
+        light::stitch_chunk(&mut self.chunks, x, z);
+    }
+
+    // Chunk by its coordinates(loaded from disk or generated if it isn't loaded)
+    pub fn chunk(&mut self, x: i32, z: i32) -> &mut ChunkColumn {
+        if !self.chunks.contains_key(&(x, z)) {
+            let column = self.load_or_generate(x, z);
+            self.insert_chunk(column);
+        }
+        self.chunks.get_mut(&(x, z)).unwrap()
+    }
+
+    // Loading chunk from disk or generating it with light on game's thread
+    fn load_or_generate(&mut self, x: i32, z: i32) -> ChunkColumn {
+        if let Some(storage) = &mut self.storage {
+            match storage.load_chunk(x, z, self.has_sky_light) {
+                Ok(Some(v)) => return v,
+                Ok(None) => {}
+                Err(err) => {
+                    error!("Failed to load chunk {}, {}: {}", x, z, err);
+                    return self.broken_chunk(x, z);
+                }
+            }
+        }
+        let mut column = match &self.generator {
+            Some(generator) => generator.generate(x, z, self.seed),
+            None => ChunkColumn::new(x, z, self.has_sky_light),
+        };
+        column.has_sky_light = self.has_sky_light;
+        light_column(&mut column);
+        column
+    }
+
+    // Empty chunk instead of chunk which failed to load(it's never saved)
+    fn broken_chunk(&mut self, x: i32, z: i32) -> ChunkColumn {
+        let mut column = ChunkColumn::new(x, z, self.has_sky_light);
+        light_column(&mut column);
+        column.dirty = false;
+        self.broken.insert((x, z));
+        column
+    }
+
+    // Height over the highest block at world's coordinates(None if there are no blocks)
+    pub fn surface_height(&mut self, x: i32, z: i32) -> Option<i32> {
+        self.chunk(x >> 4, z >> 4)
+            .highest_block((x & 15) as usize, (z & 15) as usize)
+            .map(|v| v as i32 + 1)
+    }
+
+    // Saving all changed chunks, returns count of saved chunks
+    pub fn save(&mut self) -> usize {
+        let storage = match &mut self.storage {
+            Some(v) => v,
+            None => return 0,
+        };
+        let mut saved = 0;
+        let broken = &self.broken;
+        for column in self.chunks.values_mut() {
+            if !column.dirty || broken.contains(&(column.x, column.z)) {
+                continue;
+            }
+            match storage.save_chunk(column) {
+                Ok(_) => {
+                    column.dirty = false;
+                    saved += 1;
+                }
+                Err(err) => error!("Failed to save chunk {}, {}: {}", column.x, column.z, err),
+            }
+        }
+        saved
+    }
+
+    // Unloading chunks which aren't kept(changed chunks are saved first), returns count of unloaded chunks
+    pub fn unload_chunks(&mut self, keep: impl Fn(&(i32, i32)) -> bool) -> usize {
+        let unloaded: Vec<(i32, i32)> = self.chunks.keys().filter(|v| !keep(v)).copied().collect();
+        let mut count = 0;
+        for position in unloaded {
+            let column = &self.chunks[&position];
+            // Chunk which failed to load is loaded again next time
+            if self.broken.remove(&position) {
+                self.chunks.remove(&position);
+                count += 1;
+                continue;
+            }
+            // Changes of chunk which can't be saved would be lost
+            if column.dirty {
+                let saved = match &mut self.storage {
+                    Some(storage) => storage.save_chunk(column),
+                    None => continue,
+                };
+                if let Err(err) = saved {
+                    error!(
+                        "Failed to save chunk {}, {}: {}",
+                        position.0, position.1, err
+                    );
+                    continue;
+                }
+            }
+            self.chunks.remove(&position);
+            count += 1;
+        }
+        count
+    }
+
+    // Block's state by world's coordinates(air if chunk isn't loaded)
+    pub fn get_block(&self, x: i32, y: i32, z: i32) -> BlockState {
+        if !(0..256).contains(&y) {
+            return section::AIR;
+        }
+        match self.chunks.get(&(x >> 4, z >> 4)) {
+            Some(v) => v.get_block((x & 15) as usize, y as usize, (z & 15) as usize),
+            None => section::AIR,
+        }
+    }
+
+    /// Collision's box of block by world's coordinates(door's halves are combined)
+    pub fn collision_box(&self, x: i32, y: i32, z: i32) -> Option<[f64; 6]> {
+        let state = self.get_block(x, y, z);
+        if block(state >> 4).map(|v| v.collision) != Some(Collision::Door) {
+            return collision_box(state);
+        }
+        let upper = state & 8 != 0;
+        let (lower, upper) = match upper {
+            true => (self.get_block(x, y - 1, z), state),
+            false => (state, self.get_block(x, y + 1, z)),
+        };
+        Some(door_box(lower, upper))
+    }
+
+    // Setting block's state by world's coordinates, returns previous state
+    pub fn set_block(&mut self, x: i32, y: i32, z: i32, state: BlockState) -> BlockState {
+        if !(0..256).contains(&y) {
+            return section::AIR;
+        }
+        let (local_x, local_z) = ((x & 15) as usize, (z & 15) as usize);
+        let column = self.chunk(x >> 4, z >> 4);
+        let height = light_height(column, local_x, local_z);
+        let previous = column.set_block(local_x, y as usize, local_z, state);
+        if previous != state {
+            light::update_light(&mut self.chunks, x, y, z, height);
+        }
+        previous
+    }
+
+    // Block's light by world's coordinates(0 if chunk isn't loaded)
+    pub fn get_block_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        if !(0..256).contains(&y) {
+            return 0;
+        }
+        match self.chunks.get(&(x >> 4, z >> 4)) {
+            Some(v) => v.get_block_light((x & 15) as usize, y as usize, (z & 15) as usize),
+            None => 0,
+        }
+    }
+
+    // Sky's light by world's coordinates(full over world and in not loaded chunks)
+    pub fn get_sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        if !self.has_sky_light {
+            return 0;
+        }
+        match self.chunks.get(&(x >> 4, z >> 4)) {
+            Some(v) if (0..256).contains(&y) => {
+                v.get_sky_light((x & 15) as usize, y as usize, (z & 15) as usize)
+            }
+            _ => light::MAX_LIGHT,
+        }
+    }
+}
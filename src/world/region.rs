@@ -0,0 +1,247 @@
+use crate::utils::nbt::{read_compressed, read_gzip, read_zlib, write_zlib, Compound};
+use crate::{io_error, SResult, SimpleError};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Region's file is divided into sectors
+pub const SECTOR_SIZE: usize = 4096;
+// Header's sectors(chunks' locations and timestamps)
+const HEADER_SECTORS: usize = 2;
+// Chunk's location stores count of sectors in one byte
+const MAX_CHUNK_SECTORS: usize = 255;
+
+// Chunk's compression types
+pub const COMPRESSION_GZIP: u8 = 1;
+pub const COMPRESSION_ZLIB: u8 = 2;
+pub const COMPRESSION_NONE: u8 = 3;
+
+fn error(text: &str) -> SimpleError {
+    SimpleError(String::from(text), None)
+}
+
+// Chunk's index in header by chunk's coordinates(any, only lower 5 bits are used)
+fn chunk_index(x: i32, z: i32) -> usize {
+    ((x & 31) + (z & 31) * 32) as usize
+}
+
+fn now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|v| v.as_secs() as u32)
+        .unwrap_or(0)
+}
+
+/// Anvil's region file(.mca) with 32x32 chunks
+pub struct RegionFile {
+    path: PathBuf,
+    file: File,
+    // Chunk's first sector << 8 | count of sectors(0 - no chunk)
+    locations: [u32; 1024],
+    // Last chunk's saving time in seconds
+    timestamps: [u32; 1024],
+    // Sectors which are used by header and chunks
+    used: Vec<bool>,
+}
+
+impl RegionFile {
+    /// Opening region's file(created if doesn't exist)
+    pub fn open(path: &Path) -> SResult<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(|err| io_error("Failed to open region's file", err))?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)
+            .map_err(|err| io_error("Failed to read region's file", err))?;
+        // New or broken file gets an empty header
+        if data.len() < HEADER_SECTORS * SECTOR_SIZE {
+            data.resize(HEADER_SECTORS * SECTOR_SIZE, 0);
+            file.seek(SeekFrom::Start(0))
+                .and_then(|_| file.write_all(&data))
+                .map_err(|err| io_error("Failed to write region's header", err))?;
+        }
+        let mut region = Self {
+            path: path.to_path_buf(),
+            file,
+            locations: [0; 1024],
+            timestamps: [0; 1024],
+            used: vec![true; HEADER_SECTORS],
+        };
+        let sectors = data.len().div_ceil(SECTOR_SIZE);
+        region.used.resize(sectors, false);
+        for i in 0..1024 {
+            let entry = |offset: usize| {
+                u32::from_be_bytes(data[offset + i * 4..offset + i * 4 + 4].try_into().unwrap())
+            };
+            region.timestamps[i] = entry(SECTOR_SIZE);
+            let location = entry(0);
+            let (start, count) = ((location >> 8) as usize, (location & 0xFF) as usize);
+            // Empty chunks, chunks outside of file or over header are ignored
+            if count == 0 || start < HEADER_SECTORS || start + count > sectors {
+                continue;
+            }
+            region.locations[i] = location;
+            region.used[start..start + count].fill(true);
+        }
+        Ok(region)
+    }
+
+    // Region's file's path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chunk is saved in region
+    pub fn has_chunk(&self, x: i32, z: i32) -> bool {
+        self.locations[chunk_index(x, z)] != 0
+    }
+
+    /// Last chunk's saving time in seconds
+    pub fn timestamp(&self, x: i32, z: i32) -> u32 {
+        self.timestamps[chunk_index(x, z)]
+    }
+
+    /// Reading chunk's NBT(None if chunk isn't saved)
+    pub fn read_chunk(&mut self, x: i32, z: i32) -> SResult<Option<Compound>> {
+        let location = self.locations[chunk_index(x, z)];
+        if location == 0 {
+            return Ok(None);
+        }
+        let (start, count) = ((location >> 8) as usize, (location & 0xFF) as usize);
+        let mut data = vec![0; count * SECTOR_SIZE];
+        self.file
+            .seek(SeekFrom::Start((start * SECTOR_SIZE) as u64))
+            .and_then(|_| self.file.read_exact(&mut data))
+            .map_err(|err| io_error("Failed to read chunk", err))?;
+        // Length(with compression's type) and compression's type
+        if data.len() < 5 {
+            return Err(error("Invalid chunk's length in region"));
+        }
+        let length = u32::from_be_bytes(data[0..4].try_into().unwrap()) as usize;
+        if length == 0 || length + 4 > data.len() {
+            return Err(error("Invalid chunk's length in region"));
+        }
+        let compression = data[4];
+        let data = &data[5..length + 4];
+        Ok(Some(match compression {
+            COMPRESSION_GZIP => read_gzip(data)?,
+            COMPRESSION_ZLIB => read_zlib(data)?,
+            COMPRESSION_NONE => read_compressed(data)?,
+            v => return Err(error(&format!("Unknown chunk's compression {}", v))),
+        }))
+    }
+
+    /// Writing chunk's NBT with zlib(sectors are reused if chunk fits)
+    pub fn write_chunk(&mut self, x: i32, z: i32, chunk: &Compound) -> SResult<()> {
+        let compressed = write_zlib(chunk)?;
+        let mut data = Vec::with_capacity(compressed.len() + 5);
+        data.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+        data.push(COMPRESSION_ZLIB);
+        data.extend_from_slice(&compressed);
+        let count = data.len().div_ceil(SECTOR_SIZE);
+        if count > MAX_CHUNK_SECTORS {
+            return Err(error("Chunk is too big for region's file"));
+        }
+        data.resize(count * SECTOR_SIZE, 0);
+        let index = chunk_index(x, z);
+        let location = self.locations[index];
+        let (old_start, old_count) = ((location >> 8) as usize, (location & 0xFF) as usize);
+        // Old sectors are free for new data
+        if location != 0 {
+            self.used[old_start..old_start + old_count].fill(false);
+        }
+        let start = if location != 0 && old_count >= count {
+            old_start
+        } else {
+            self.allocate(count)
+        };
+        self.file
+            .seek(SeekFrom::Start((start * SECTOR_SIZE) as u64))
+            .and_then(|_| self.file.write_all(&data))
+            .map_err(|err| io_error("Failed to write chunk", err))?;
+        if self.used.len() < start + count {
+            self.used.resize(start + count, false);
+        }
+        self.used[start..start + count].fill(true);
+        self.set_header(index, (start as u32) << 8 | count as u32, now())
+    }
+
+    /// Removing chunk from region(its sectors become free)
+    pub fn remove_chunk(&mut self, x: i32, z: i32) -> SResult<()> {
+        let index = chunk_index(x, z);
+        let location = self.locations[index];
+        if location == 0 {
+            return Ok(());
+        }
+        let (start, count) = ((location >> 8) as usize, (location & 0xFF) as usize);
+        self.used[start..start + count].fill(false);
+        self.set_header(index, 0, 0)
+    }
+
+    // First free sectors' run for count sectors(or file's end)
+    fn allocate(&self, count: usize) -> usize {
+        let mut run = 0;
+        for (i, used) in self.used.iter().enumerate() {
+            run = if *used { 0 } else { run + 1 };
+            if run == count {
+                return i + 1 - count;
+            }
+        }
+        self.used.len() - run
+    }
+
+    // Updating chunk's location and timestamp in memory and file
+    fn set_header(&mut self, index: usize, location: u32, timestamp: u32) -> SResult<()> {
+        self.locations[index] = location;
+        self.timestamps[index] = timestamp;
+        let offset = (index * 4) as u64;
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| self.file.write_all(&location.to_be_bytes()))
+            .and_then(|_| self.file.seek(SeekFrom::Start(SECTOR_SIZE as u64 + offset)))
+            .and_then(|_| self.file.write_all(&timestamp.to_be_bytes()))
+            .map_err(|err| io_error("Failed to write region's header", err))
+    }
+
+    /// Rewriting the file without free sectors(chunks go one after another)
+    pub fn compact(&mut self) -> SResult<()> {
+        let mut data = vec![0; HEADER_SECTORS * SECTOR_SIZE];
+        let mut locations = [0; 1024];
+        for (index, location) in self.locations.iter().enumerate() {
+            let location = *location;
+            if location == 0 {
+                continue;
+            }
+            let (start, count) = ((location >> 8) as usize, (location & 0xFF) as usize);
+            let mut chunk = vec![0; count * SECTOR_SIZE];
+            self.file
+                .seek(SeekFrom::Start((start * SECTOR_SIZE) as u64))
+                .and_then(|_| self.file.read_exact(&mut chunk))
+                .map_err(|err| io_error("Failed to read chunk", err))?;
+            locations[index] = ((data.len() / SECTOR_SIZE) as u32) << 8 | count as u32;
+            data.extend_from_slice(&chunk);
+        }
+        for (index, location) in locations.iter().enumerate() {
+            data[index * 4..index * 4 + 4].copy_from_slice(&location.to_be_bytes());
+            let offset = SECTOR_SIZE + index * 4;
+            data[offset..offset + 4].copy_from_slice(&self.timestamps[index].to_be_bytes());
+        }
+        // Writing into temporary file and replacing the region
+        let temp = self.path.with_extension("mca.tmp");
+        fs::write(&temp, &data)
+            .and_then(|_| fs::rename(&temp, &self.path))
+            .map_err(|err| io_error("Failed to compact region's file", err))?;
+        *self = Self::open(&self.path.clone())?;
+        Ok(())
+    }
+
+    /// Count of sectors in file(with header)
+    pub fn sectors(&self) -> usize {
+        self.used.len()
+    }
+}
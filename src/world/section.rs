@@ -0,0 +1,173 @@
+use crate::world::bits::{BitArray, NibbleArray};
+
+// Count of blocks in section(16x16x16)
+pub const SECTION_VOLUME: usize = 4096;
+// Minimal bits per block in palette
+pub const MIN_BITS: u8 = 4;
+// Maximal bits per block with section's palette, more - global palette
+pub const MAX_INDIRECT_BITS: u8 = 8;
+// Bits per block of global palette(block's id << 4 | meta) in 1.12
+pub const GLOBAL_BITS: u8 = 13;
+
+// Block's state in global palette(block's id << 4 | meta)
+pub type BlockState = u16;
+pub const AIR: BlockState = 0;
+
+// Block's index in section by local coordinates(0..16)
+pub fn section_index(x: usize, y: usize, z: usize) -> usize {
+    (y << 8) | (z << 4) | x
+}
+
+/// 16x16x16 blocks with palette and light
+#[derive(Debug, Clone)]
+pub struct ChunkSection {
+    // Section's palette(empty with global palette)
+    palette: Vec<BlockState>,
+    // Palette's indexes or global states
+    blocks: BitArray,
+    // Count of not air blocks
+    block_count: u16,
+    pub block_light: NibbleArray,
+    pub sky_light: NibbleArray,
+}
+
+// Sections are equal if they have the same blocks and light(palettes can differ)
+impl PartialEq for ChunkSection {
+    fn eq(&self, other: &Self) -> bool {
+        self.block_light == other.block_light
+            && self.sky_light == other.sky_light
+            && (0..SECTION_VOLUME).all(|i| self.get_index(i) == other.get_index(i))
+    }
+}
+
+impl Eq for ChunkSection {}
+
+impl Default for ChunkSection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkSection {
+    // Section of air with full sky light
+    pub fn new() -> Self {
+        Self {
+            palette: vec![AIR],
+            blocks: BitArray::new(MIN_BITS, SECTION_VOLUME),
+            block_count: 0,
+            block_light: NibbleArray::new(SECTION_VOLUME, 0),
+            sky_light: NibbleArray::new(SECTION_VOLUME, 15),
+        }
+    }
+
+    // Section from all block's states(by section_index)
+    pub fn from_states(states: &[BlockState]) -> Self {
+        let mut section = Self::new();
+        for (i, state) in states.iter().enumerate().take(SECTION_VOLUME) {
+            if *state != AIR {
+                section.set_index(i, *state);
+            }
+        }
+        section
+    }
+
+    // Bits per block(MIN_BITS..=MAX_INDIRECT_BITS with palette, GLOBAL_BITS without)
+    pub fn bits(&self) -> u8 {
+        self.blocks.bits()
+    }
+
+    // Section's palette(empty with global palette)
+    pub fn palette(&self) -> &[BlockState] {
+        &self.palette
+    }
+
+    // Packed palette's indexes or global states
+    pub fn data(&self) -> &[u64] {
+        self.blocks.data()
+    }
+
+    // Count of not air blocks
+    pub fn block_count(&self) -> u16 {
+        self.block_count
+    }
+
+    // Section hasn't blocks
+    pub fn is_empty(&self) -> bool {
+        self.block_count == 0
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> BlockState {
+        self.get_index(section_index(x, y, z))
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, state: BlockState) -> BlockState {
+        self.set_index(section_index(x, y, z), state)
+    }
+
+    pub fn get_index(&self, index: usize) -> BlockState {
+        let value = self.blocks.get(index);
+        if self.palette.is_empty() {
+            value as BlockState
+        } else {
+            self.palette[value as usize]
+        }
+    }
+
+    // Setting block's state, returns previous state
+    pub fn set_index(&mut self, index: usize, state: BlockState) -> BlockState {
+        let previous = self.get_index(index);
+        if previous == state {
+            return previous;
+        }
+        let value = self.palette_index(state);
+        self.blocks.set(index, value);
+        match (previous == AIR, state == AIR) {
+            (true, false) => self.block_count += 1,
+            (false, true) => self.block_count -= 1,
+            _ => {}
+        }
+        previous
+    }
+
+    // State's value in blocks' array(palette grows if state is new)
+    fn palette_index(&mut self, state: BlockState) -> u32 {
+        if self.palette.is_empty() {
+            return state as u32;
+        }
+        if let Some(index) = self.palette.iter().position(|v| *v == state) {
+            return index as u32;
+        }
+        // Palette is full: more bits or global palette
+        if self.palette.len() == 1 << self.bits() {
+            let bits = self.bits() + 1;
+            if bits > MAX_INDIRECT_BITS {
+                let mut blocks = BitArray::new(GLOBAL_BITS, SECTION_VOLUME);
+                for i in 0..SECTION_VOLUME {
+                    blocks.set(i, self.palette[self.blocks.get(i) as usize] as u32);
+                }
+                self.blocks = blocks;
+                self.palette.clear();
+                return state as u32;
+            }
+            self.blocks = self.blocks.resize(bits);
+        }
+        self.palette.push(state);
+        (self.palette.len() - 1) as u32
+    }
+
+    pub fn get_block_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.block_light.get(section_index(x, y, z))
+    }
+
+    pub fn set_block_light(&mut self, x: usize, y: usize, z: usize, value: u8) {
+        self.block_light.set(section_index(x, y, z), value)
+    }
+
+    pub fn get_sky_light(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.sky_light.get(section_index(x, y, z))
+    }
+
+    pub fn set_sky_light(&mut self, x: usize, y: usize, z: usize, value: u8) {
+        self.sky_light.set(section_index(x, y, z), value)
+    }
+}
@@ -0,0 +1,274 @@
+use ule::game::entity::metadata::{MetaValue, Metadata, INDEX_CUSTOM_NAME_VISIBLE};
+use ule::game::entity::{next_entity_id, BoundingBox, Entities, Entity, EntityType, SpawnKind};
+use ule::game::items::move_item;
+use ule::game::movement::{check_move, collides, update_fall, MoveCheck};
+use ule::game::player::Player;
+use ule::network::GameProfile;
+use ule::utils::nbt::{Compound, Tag};
+use ule::world::block::state_of;
+use ule::world::item::ItemStack;
+use ule::world::World;
+
+#[test]
+fn entity_ids_are_unique() {
+    let first = next_entity_id();
+    let entity = Entity::new(EntityType::Zombie, 0.0, 64.0, 0.0);
+    assert!(entity.id > first);
+    assert!(next_entity_id() > entity.id);
+    assert_ne!(
+        entity.uuid,
+        Entity::new(EntityType::Zombie, 0.0, 0.0, 0.0).uuid
+    );
+}
+
+#[test]
+fn entity_types_have_vanilla_data() {
+    assert_eq!(EntityType::by_name("zombie"), Some(EntityType::Zombie));
+    assert_eq!(
+        EntityType::by_name("minecraft:item"),
+        Some(EntityType::Item)
+    );
+    assert_eq!(EntityType::by_name("dragon"), None);
+    assert_eq!(EntityType::Player.spawn_kind(), SpawnKind::Player);
+    assert_eq!(EntityType::Item.spawn_kind(), SpawnKind::Object(2));
+    assert_eq!(EntityType::Creeper.spawn_kind(), SpawnKind::Mob(50));
+    assert_eq!(EntityType::Player.size(), (0.6, 1.8));
+    assert!(EntityType::Pig.is_living());
+    assert!(!EntityType::Item.is_living());
+    assert!(EntityType::Arrow.is_projectile());
+}
+
+#[test]
+fn entities_are_found_by_chunks() {
+    let mut entities = Entities::new();
+    let pig = entities.spawn(Entity::new(EntityType::Pig, 8.0, 64.0, 8.0));
+    let item = entities.spawn(Entity::new(EntityType::Item, 15.9, 64.0, 8.0));
+    let zombie = entities.spawn(Entity::new(EntityType::Zombie, -40.0, 64.0, 100.0));
+    assert_eq!(entities.len(), 3);
+    assert_eq!(entities.in_chunk(0, 0).count(), 2);
+    assert_eq!(entities.in_chunk(-3, 6).next().unwrap().id, zombie);
+    // Entity sticks out of its chunk
+    let area = BoundingBox::new((16.0, 64.0, 7.0), (17.0, 65.0, 9.0));
+    let found: Vec<i32> = entities.in_box(&area).iter().map(|v| v.id).collect();
+    assert_eq!(found, vec![item]);
+    // Moving between chunks updates index
+    assert!(entities.move_entity(pig, 20.0, 64.0, 8.0));
+    assert_eq!(entities.in_chunk(1, 0).next().unwrap().id, pig);
+    assert_eq!(entities.in_chunk(0, 0).count(), 1);
+    let mut near: Vec<i32> = entities
+        .in_radius(18.0, 64.0, 8.0, 3.0)
+        .iter()
+        .map(|v| v.id)
+        .collect();
+    near.sort_unstable();
+    assert_eq!(near, vec![pig, item]);
+    assert!(entities.remove(item).is_some());
+    assert_eq!(entities.in_chunk(0, 0).count(), 0);
+    assert!(!entities.move_entity(item, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn metadata_keeps_values_by_index() {
+    let mut metadata = Metadata::new();
+    metadata.set(2, MetaValue::String(String::from("Bob")));
+    metadata.set(0, MetaValue::Byte(0x20));
+    metadata.set(0, MetaValue::Byte(0x02));
+    let indexes: Vec<u8> = metadata.iter().map(|(k, _)| k).collect();
+    assert_eq!(indexes, vec![0, 2]);
+    assert_eq!(metadata.get(0), Some(&MetaValue::Byte(0x02)));
+    assert!(metadata.remove(2).is_some());
+    assert_eq!(metadata.get(2), None);
+}
+
+#[test]
+fn metadata_round_trips_all_types() {
+    let mut nbt = Compound::new();
+    nbt.insert(String::from("Name"), Tag::String(String::from("test")));
+    let values = vec![
+        MetaValue::Byte(-3),
+        MetaValue::VarInt(300),
+        MetaValue::Float(1.5),
+        MetaValue::String(String::from("name")),
+        MetaValue::Chat(String::from("{\"text\":\"hi\"}")),
+        MetaValue::Slot(Some(ItemStack::new(1, 5, 0))),
+        MetaValue::Boolean(true),
+        MetaValue::Rotation(1.0, 2.0, 3.0),
+        MetaValue::Position((10, 64, -10)),
+        MetaValue::OptPosition(None),
+        MetaValue::Direction(3),
+        MetaValue::OptUuid(Some(42)),
+        MetaValue::OptBlockId(Some(16)),
+        MetaValue::Nbt(nbt),
+    ];
+    let mut metadata = Metadata::new();
+    for (index, value) in values.iter().enumerate() {
+        assert_eq!(value.type_id(), index as i32);
+        metadata.set(index as u8, value.clone());
+    }
+    let mut bytes = metadata.encode();
+    assert_eq!(bytes.last(), Some(&0xFF));
+    // Truncated metadata is an error
+    for len in 0..bytes.len() {
+        assert!(Metadata::read(&mut bytes[..len].to_vec()).is_err());
+    }
+    let read = Metadata::read(&mut bytes).unwrap();
+    let read_values: Vec<MetaValue> = read.iter().map(|(_, v)| v.clone()).collect();
+    assert_eq!(read_values, values);
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn metadata_sends_only_changed_values() {
+    let mut metadata = Metadata::new();
+    metadata.set_sneaking(true);
+    metadata.set_custom_name(Some("Bob"));
+    assert!(metadata.encode_dirty().is_some());
+    assert!(!metadata.is_dirty());
+    assert_eq!(metadata.encode_dirty(), None);
+    // Same value isn't changed
+    metadata.set_sneaking(true);
+    assert_eq!(metadata.encode_dirty(), None);
+    metadata.set_sprinting(true);
+    let mut bytes = metadata.encode_dirty().unwrap();
+    let changed = Metadata::read(&mut bytes).unwrap();
+    assert_eq!(changed.iter().count(), 1);
+    assert!(changed.is_sneaking() && changed.is_sprinting());
+    assert!(!changed.is_on_fire() && !changed.is_glowing());
+}
+
+#[test]
+fn metadata_flags_and_name() {
+    let mut metadata = Metadata::new();
+    metadata.set_invisible(true);
+    metadata.set_on_fire(true);
+    metadata.set_invisible(false);
+    assert!(metadata.is_on_fire() && !metadata.is_invisible());
+    assert_eq!(metadata.custom_name(), None);
+    metadata.set_custom_name(Some("Bob"));
+    assert_eq!(metadata.custom_name(), Some("Bob"));
+    assert_eq!(
+        metadata.get(INDEX_CUSTOM_NAME_VISIBLE),
+        Some(&MetaValue::Boolean(true))
+    );
+    metadata.set_custom_name(None);
+    assert_eq!(metadata.custom_name(), None);
+    assert_eq!(metadata.item(), None);
+    metadata.set_item(Some(ItemStack::new(1, 1, 0)));
+    assert_eq!(metadata.item().map(|v| v.id), Some(1));
+}
+
+// World with stone floor at y = 63 and stone wall at x = 3
+fn movement_world() -> World {
+    let mut world = World::new(true);
+    let stone = state_of("stone", &[]).unwrap();
+    for x in -4..8 {
+        for z in -4..4 {
+            world.set_block(x, 63, z, stone);
+            world.set_block(3, 64 + z.abs(), x, stone);
+        }
+    }
+    world
+}
+
+#[test]
+fn moves_are_validated() {
+    let world = movement_world();
+    let from = (0.5, 64.0, 0.5);
+    assert_eq!(
+        check_move(&world, from, (1.0, 64.0, 0.5), false),
+        MoveCheck::Valid
+    );
+    // Standing on floor and touching wall is allowed
+    assert_eq!(
+        check_move(&world, from, (2.7, 64.0, 0.5), false),
+        MoveCheck::Valid
+    );
+    assert!(!collides(
+        &world,
+        &BoundingBox::of_size(2.7, 64.0, 0.5, 0.6, 1.8)
+    ));
+    assert_eq!(
+        check_move(&world, from, (0.5, 63.5, 0.5), false),
+        MoveCheck::ThroughBlocks
+    );
+    // Long move through the wall is rejected even if it ends in air
+    assert_eq!(
+        check_move(&world, from, (6.5, 64.0, 0.5), false),
+        MoveCheck::ThroughBlocks
+    );
+    assert_eq!(
+        check_move(&world, from, (6.5, 64.0, 0.5), true),
+        MoveCheck::Valid
+    );
+    assert_eq!(
+        check_move(&world, from, (20.5, 64.0, 0.5), true),
+        MoveCheck::TooFast
+    );
+    assert_eq!(
+        check_move(&world, from, (f64::NAN, 64.0, 0.5), true),
+        MoveCheck::TooFast
+    );
+}
+
+#[test]
+fn landing_deals_fall_damage() {
+    let mut world = World::new(true);
+    let profile = GameProfile {
+        name: String::from("Faller"),
+        uuid: 1,
+    };
+    let mut player = Player::new(0, profile, 1, 0);
+    let at = |y| (0.0, y, 0.0);
+    let mut from = 80.0;
+    for y in [78.0, 75.0, 72.5] {
+        assert_eq!(
+            update_fall(&mut player, &world, at(from), at(y), false),
+            0.0
+        );
+        from = y;
+    }
+    assert_eq!(
+        update_fall(&mut player, &world, at(from), at(70.0), true),
+        7.0
+    );
+    assert_eq!(player.fall_distance, 0.0);
+    // Short fall doesn't hurt
+    assert_eq!(
+        update_fall(&mut player, &world, at(73.0), at(70.0), true),
+        0.0
+    );
+    // Creative player isn't hurt
+    player.gamemode = 1;
+    assert_eq!(
+        update_fall(&mut player, &world, at(90.0), at(70.0), true),
+        0.0
+    );
+    // Falling into water at destination doesn't hurt
+    player.gamemode = 0;
+    world.set_block(3, 70, 0, state_of("water", &[]).unwrap());
+    assert_eq!(
+        update_fall(&mut player, &world, at(90.0), (3.5, 70.0, 0.5), true),
+        0.0
+    );
+}
+
+#[test]
+fn items_fall_and_slide_along_blocks() {
+    let world = movement_world();
+    let mut entities = Entities::new();
+    let mut item = Entity::new(EntityType::Item, 0.5, 66.0, 0.5);
+    item.velocity = (0.2, 0.0, 0.0);
+    let id = entities.spawn(item);
+    for _ in 0..40 {
+        let (x, y, z) = move_item(&world, entities.get_mut(id).unwrap());
+        entities.move_entity(id, x, y, z);
+    }
+    let item = entities.get(id).unwrap();
+    let (x, y, _) = item.position();
+    // Item lies on the floor(collision is checked with tolerance) in front of the wall
+    assert!(item.on_ground);
+    assert!(y > 63.9 && y <= 64.0);
+    assert!(x > 2.5 && x < 3.0);
+    assert!(item.velocity.0.abs() < 0.001);
+    assert_eq!(item.velocity.1, 0.0);
+}
@@ -0,0 +1,231 @@
+use ule::network::proto::{PacketReader, PacketWriter};
+use ule::utils::nbt::{
+    from_compound, parse_snbt, read_compressed, read_nbt, to_compound, write_gzip, write_nbt,
+    write_zlib, Compound, IntArray, Tag,
+};
+use ule::world::item::ItemStack;
+
+// Compound with all tag's types
+fn all_tags() -> Compound {
+    let mut nested = Compound::new();
+    nested.insert(
+        String::from("name"),
+        Tag::from("Nested \"quoted\" \u{0}\u{1F600}"),
+    );
+    let mut root = Compound::new();
+    root.insert(String::from("byte"), Tag::Byte(-1));
+    root.insert(String::from("short"), Tag::Short(-300));
+    root.insert(String::from("int"), Tag::Int(123_456));
+    root.insert(String::from("long"), Tag::Long(i64::MIN));
+    root.insert(String::from("float"), Tag::Float(0.5));
+    root.insert(String::from("double"), Tag::Double(-1e-7));
+    root.insert(String::from("bytes"), Tag::ByteArray(vec![1, -2, 3]));
+    root.insert(String::from("string"), Tag::from("hello world"));
+    root.insert(
+        String::from("list"),
+        Tag::List(vec![Tag::Compound(nested.clone()), Tag::Compound(nested)]),
+    );
+    root.insert(String::from("empty"), Tag::List(Vec::new()));
+    root.insert(
+        String::from("ints"),
+        Tag::IntArray(vec![i32::MIN, 0, i32::MAX]),
+    );
+    root.insert(String::from("longs"), Tag::LongArray(vec![1, -1]));
+    root
+}
+
+#[test]
+fn binary_round_trip() {
+    let root = all_tags();
+    let mut bytes = Vec::new();
+    write_nbt(&mut bytes, "root", &root);
+    bytes.push(0x42);
+    let (name, read) = read_nbt(&mut bytes).unwrap().unwrap();
+    assert_eq!(name, "root");
+    assert_eq!(read, root);
+    // Only NBT's bytes are read
+    assert_eq!(bytes, vec![0x42]);
+}
+
+#[test]
+fn binary_matches_vanilla() {
+    // hello_world.nbt from the NBT's specification
+    let mut bytes = vec![
+        0x0A, 0x00, 0x0B, b'h', b'e', b'l', b'l', b'o', b' ', b'w', b'o', b'r', b'l', b'd', 0x08,
+        0x00, 0x04, b'n', b'a', b'm', b'e', 0x00, 0x09, b'B', b'a', b'n', b'a', b'n', b'r', b'a',
+        b'm', b'a', 0x00,
+    ];
+    let expected = bytes.clone();
+    let (name, root) = read_nbt(&mut bytes).unwrap().unwrap();
+    assert_eq!(name, "hello world");
+    assert_eq!(root["name"].as_str(), Some("Bananrama"));
+    let mut written = Vec::new();
+    write_nbt(&mut written, &name, &root);
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn packet_nbt_can_be_empty() {
+    let mut bytes = Vec::new();
+    bytes.write_nbt(None);
+    bytes.write_nbt(Some(&all_tags()));
+    assert_eq!(bytes.get_nbt().unwrap(), None);
+    assert_eq!(bytes.get_nbt().unwrap(), Some(all_tags()));
+    assert!(bytes.is_empty());
+}
+
+#[test]
+fn packet_slot_round_trip() {
+    let mut stack = ItemStack::new(35, 12, 14);
+    stack.set_display_name(Some("Red wool"));
+    let mut bytes = Vec::new();
+    bytes.write_slot(None);
+    bytes.write_slot(Some(&stack));
+    bytes.write_slot(Some(&ItemStack::new(1, 64, 0)));
+    assert_eq!(&bytes[..2], &[0xFF, 0xFF]);
+    assert_eq!(bytes.get_slot().unwrap(), None);
+    assert_eq!(bytes.get_slot().unwrap(), Some(stack));
+    assert_eq!(bytes.get_slot().unwrap(), Some(ItemStack::new(1, 64, 0)));
+    assert!(bytes.is_empty());
+    // Stacks without items are empty slots
+    for count in [0, -1, -128] {
+        bytes.write_i16(1);
+        bytes.write_i8(count);
+        bytes.write_i16(0);
+        bytes.write_nbt(None);
+        assert_eq!(bytes.get_slot().unwrap(), None);
+        assert!(bytes.is_empty());
+        let mut compound = ItemStack::new(1, 1, 0).to_nbt();
+        compound.insert(String::from("Count"), Tag::Byte(count));
+        assert_eq!(ItemStack::from_nbt(&compound), None);
+    }
+}
+
+#[test]
+fn broken_binary_is_error() {
+    let mut bytes = Vec::new();
+    write_nbt(&mut bytes, "", &all_tags());
+    bytes.truncate(bytes.len() / 2);
+    assert!(read_nbt(&mut bytes).is_err());
+    assert!(read_nbt(&mut vec![
+        0x0A, 0x00, 0x00, 0x07, 0x00, 0x00, 0x7F, 0xFF, 0xFF, 0xFF
+    ])
+    .is_err());
+}
+
+#[test]
+fn compression_is_detected() {
+    let root = all_tags();
+    assert_eq!(read_compressed(&write_gzip(&root).unwrap()).unwrap(), root);
+    assert_eq!(read_compressed(&write_zlib(&root).unwrap()).unwrap(), root);
+    let mut raw = Vec::new();
+    write_nbt(&mut raw, "", &root);
+    assert_eq!(read_compressed(&raw).unwrap(), root);
+}
+
+#[test]
+fn snbt_round_trip() {
+    let root = Tag::Compound(all_tags());
+    assert_eq!(parse_snbt(&root.to_string()).unwrap(), root);
+}
+
+#[test]
+fn snbt_keeps_strings_like_numbers_and_special_floats() {
+    for text in [
+        "123", "-5", "1b", "2.5", "3e4", "7L", "true", "false", "NaNf", "abc",
+    ] {
+        let tag = Tag::from(text);
+        assert_eq!(parse_snbt(&tag.to_string()).unwrap(), tag);
+    }
+    assert_eq!(Tag::from("123").to_string(), "\"123\"");
+    assert_eq!(Tag::from("abc").to_string(), "abc");
+    for value in [f32::INFINITY, f32::NEG_INFINITY] {
+        assert_eq!(
+            parse_snbt(&Tag::Float(value).to_string()).unwrap(),
+            Tag::Float(value)
+        );
+    }
+    assert_eq!(
+        parse_snbt("-Infinityd").unwrap(),
+        Tag::Double(f64::NEG_INFINITY)
+    );
+    match parse_snbt(&Tag::Float(f32::NAN).to_string()).unwrap() {
+        Tag::Float(v) => assert!(v.is_nan()),
+        tag => panic!("NaN was read as {:?}", tag),
+    }
+    match parse_snbt(&Tag::Double(f64::NAN).to_string()).unwrap() {
+        Tag::Double(v) => assert!(v.is_nan()),
+        tag => panic!("NaN was read as {:?}", tag),
+    }
+}
+
+#[test]
+fn snbt_infers_types() {
+    let tag = parse_snbt(
+        "{id:\"minecraft:stone\", Count:1b, Damage:0s, big:3L, f:1.5f, d:2.5, i:-7, on:true, word:abc, 'q':'it\\'s', a:[I;1,2], e:[]}",
+    );
+    let tag = tag.unwrap();
+    let compound = tag.as_compound().unwrap();
+    assert_eq!(compound["id"], Tag::from("minecraft:stone"));
+    assert_eq!(compound["Count"], Tag::Byte(1));
+    assert_eq!(compound["Damage"], Tag::Short(0));
+    assert_eq!(compound["big"], Tag::Long(3));
+    assert_eq!(compound["f"], Tag::Float(1.5));
+    assert_eq!(compound["d"], Tag::Double(2.5));
+    assert_eq!(compound["i"], Tag::Int(-7));
+    assert_eq!(compound["on"], Tag::Byte(1));
+    assert_eq!(compound["word"], Tag::from("abc"));
+    assert_eq!(compound["a"], Tag::IntArray(vec![1, 2]));
+    assert_eq!(compound["e"], Tag::List(Vec::new()));
+    assert!(parse_snbt("{a:1,}").is_err());
+    assert!(parse_snbt("[1,2b]").is_err());
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Item {
+    id: String,
+    count: u8,
+    damage: i16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<Tag>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Data {
+    #[serde(rename = "Pos")]
+    pos: Vec<f64>,
+    #[serde(rename = "OnGround")]
+    on_ground: bool,
+    uuid: IntArray,
+    name: Option<String>,
+    items: Vec<Item>,
+}
+
+#[test]
+fn serde_maps_structs_to_compounds() {
+    let data = Data {
+        pos: vec![0.5, 64.0, -0.5],
+        on_ground: true,
+        uuid: IntArray(vec![1, 2, 3, 4]),
+        name: None,
+        items: vec![Item {
+            id: String::from("minecraft:stone"),
+            count: 200,
+            damage: 0,
+            tag: Some(parse_snbt("{display:{Name:\"Rock\"},ench:[{id:16s,lvl:1s}]}").unwrap()),
+        }],
+    };
+    let compound = to_compound(&data).unwrap();
+    assert_eq!(compound["OnGround"], Tag::Byte(1));
+    assert_eq!(compound["uuid"], Tag::IntArray(vec![1, 2, 3, 4]));
+    assert!(!compound.contains_key("name"));
+    assert_eq!(
+        compound["items"].as_list().unwrap()[0]
+            .as_compound()
+            .unwrap()["Count"],
+        Tag::Byte(200u8 as i8)
+    );
+    let read: Data = from_compound(compound).unwrap();
+    assert_eq!(read, data);
+}
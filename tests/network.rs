@@ -0,0 +1,603 @@
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+use ule::client::{Client, ClientEvent};
+use ule::game::chat::format_chat;
+use ule::game::commands::console_command;
+use ule::game::keep_alive::{
+    check_keep_alive, update_latency, KeepAliveCheck, KEEP_ALIVE_INTERVAL, KEEP_ALIVE_TIMEOUT,
+};
+use ule::game::player::Player;
+use ule::game::scheduler::Scheduler;
+use ule::game::tab_list::format_tab_text;
+use ule::game::tick::{TickStats, TPS};
+use ule::network::capture::{
+    finish_capture, record_close, record_frame, record_open, start_capture, CaptureEvent,
+    CaptureReader,
+};
+use ule::network::proto::registry::{decode_frame, Direction};
+use ule::network::proto::{PacketReader, PacketWriter};
+use ule::network::{ConnectionType, GameProfile};
+use ule::start_server;
+use ule::utils::uuid::{offline_uuid, uuid_to_string};
+use ule::world::item::ItemStack;
+
+// Single server on ephemeral port for all tests
+fn server() -> SocketAddr {
+    static SERVER: OnceLock<SocketAddr> = OnceLock::new();
+    *SERVER.get_or_init(|| {
+        // World in temporary directory, so players start from spawn of a new world
+        let dir = std::env::temp_dir().join(format!("ule-test-server-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        start_server(String::from("127.0.0.1:0"), &dir).unwrap()
+    })
+}
+
+#[test]
+fn status_returns_server_info() {
+    let status = Client::status(server()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&status).unwrap();
+    assert_eq!(json["version"]["protocol"], 340);
+    assert_eq!(json["version"]["name"], "ULE");
+}
+
+#[test]
+fn ping_returns_same_payload() {
+    let mut client = Client::connect(server()).unwrap();
+    client.handshake(1).unwrap();
+    client.request_status().unwrap();
+    client.ping(0x0123_4567_89AB_CDEF).unwrap();
+}
+
+#[test]
+fn login_uses_offline_uuid() {
+    let client = Client::login(server(), "Notch").unwrap();
+    assert_eq!(client.name, "Notch");
+    assert_eq!(
+        uuid_to_string(client.uuid),
+        "b50ad385-829d-3141-a216-7e7d7539ba7f"
+    );
+    assert_eq!(client.uuid, offline_uuid("Notch"));
+}
+
+#[test]
+fn player_loads_into_world() {
+    let mut client = Client::login(server(), "Steve").unwrap();
+    let mut joined = false;
+    // Join Game comes first and then the player is teleported to spawn
+    loop {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::JoinGame { entity_id, .. }) => {
+                assert!(entity_id > 0);
+                joined = true;
+            }
+            Some(ClientEvent::Teleport { x, y, z, .. }) => {
+                assert!(joined);
+                // New superflat world's spawn is on grass
+                assert_eq!((x, y, z), (0.5, 4.0, 0.5));
+                break;
+            }
+            Some(_) => {}
+            None => panic!("Player wasn't spawned"),
+        }
+    }
+}
+
+#[test]
+fn login_rejects_other_protocol() {
+    let mut client = Client::connect(server()).unwrap();
+    let mut handshake = Vec::new();
+    handshake.write_varint(47);
+    handshake.write_string(String::from("localhost"));
+    handshake.write_u16(25565);
+    handshake.write_varint(2);
+    client.send(&handshake.create_packet(0x00)).unwrap();
+    let mut login = Vec::new();
+    login.write_string(String::from("OldClient"));
+    client.send(&login.create_packet(0x00)).unwrap();
+    let mut p = client.receive(Duration::from_secs(5)).unwrap().unwrap();
+    let (_, pid) = p.read_base().unwrap();
+    assert_eq!(pid, 0x00);
+    assert!(p.get_string().unwrap().contains("Outdated client"));
+}
+
+#[test]
+fn many_players_play_together() {
+    let bots: Vec<_> = (0..16)
+        .map(|i| {
+            thread::spawn(move || {
+                let mut client = Client::login(server(), &format!("Bot{}", i)).unwrap();
+                client.send_chat("Hello!").unwrap();
+                for step in 0..20 {
+                    client
+                        .move_and_look(step as f64 * 0.1, 64.0, 0.0, 0.0, 0.0, true)
+                        .unwrap();
+                    client.poll(Duration::from_millis(5)).unwrap();
+                }
+                client.disconnect();
+            })
+        })
+        .collect();
+    for bot in bots {
+        bot.join().unwrap();
+    }
+    // Server is still alive after all players left
+    Client::status(server()).unwrap();
+}
+
+#[test]
+fn chunks_are_sent_around_player() {
+    let mut client = Client::login(server(), "Viewer").unwrap();
+    client.send_client_settings(2).unwrap();
+    let mut chunks = Vec::new();
+    // Nearest chunk comes first
+    while chunks.len() < 25 {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::ChunkData {
+                x, z, full_chunk, ..
+            }) => {
+                assert!(full_chunk);
+                chunks.push((x, z));
+            }
+            Some(_) => {}
+            None => panic!("Chunks weren't sent"),
+        }
+    }
+    assert_eq!(chunks[0], (0, 0));
+}
+
+// Waiting for packet with id which content matches
+fn wait_packet(client: &mut Client, pid: i32, matches: impl Fn(&mut Vec<u8>) -> bool) -> Vec<u8> {
+    loop {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Packet(id, p)) if id == pid => {
+                if matches(&mut p.clone()) {
+                    return p;
+                }
+            }
+            Some(_) => {}
+            None => panic!("Packet 0x{:02X} wasn't received", pid),
+        }
+    }
+}
+
+#[test]
+fn players_see_each_other() {
+    let mut watcher = Client::login(server(), "Watcher").unwrap();
+    let mut walker = Client::login(server(), "Walker").unwrap();
+    // Walker's movement is accepted after teleport's confirmation
+    loop {
+        match walker.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Teleport { .. }) => break,
+            Some(_) => {}
+            None => panic!("Walker wasn't spawned"),
+        }
+    }
+    let id = walker.entity_id;
+    let uuid = walker.uuid;
+    // Player List Item comes before Spawn Player
+    wait_packet(&mut watcher, 0x2E, |p| {
+        p.get_varint().unwrap() == 0 && p.get_varint().unwrap() >= 1
+    });
+    let mut spawn = wait_packet(&mut watcher, 0x05, |p| p.get_varint().unwrap() == id);
+    spawn.get_varint().unwrap();
+    assert_eq!(spawn.get_u128().unwrap(), uuid);
+    assert_eq!(
+        (
+            spawn.get_f64().unwrap(),
+            spawn.get_f64().unwrap(),
+            spawn.get_f64().unwrap()
+        ),
+        (0.5, 4.0, 0.5)
+    );
+    // Short move with rotation is relative
+    walker
+        .move_and_look(1.5, 4.0, 0.5, 90.0, 0.0, true)
+        .unwrap();
+    let mut moved = wait_packet(&mut watcher, 0x27, |p| p.get_varint().unwrap() == id);
+    moved.get_varint().unwrap();
+    assert_eq!(
+        (
+            moved.get_i16().unwrap(),
+            moved.get_i16().unwrap(),
+            moved.get_i16().unwrap()
+        ),
+        (4096, 0, 0)
+    );
+    assert_eq!(moved.get_u8().unwrap(), 64);
+    // Long move is teleport
+    walker
+        .move_and_look(10.5, 4.0, 0.5, 90.0, 0.0, true)
+        .unwrap();
+    let mut teleported = wait_packet(&mut watcher, 0x4C, |p| p.get_varint().unwrap() == id);
+    teleported.get_varint().unwrap();
+    assert_eq!(teleported.get_f64().unwrap(), 10.5);
+    // Leaving player is destroyed
+    walker.disconnect();
+    wait_packet(&mut watcher, 0x32, |p| {
+        let count = p.get_varint().unwrap();
+        (0..count).any(|_| p.get_varint().unwrap() == id)
+    });
+}
+
+#[test]
+fn wrong_moves_are_rejected() {
+    let mut client = Client::login(server(), "Cheater").unwrap();
+    loop {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Teleport { .. }) => break,
+            Some(_) => {}
+            None => panic!("Player wasn't spawned"),
+        }
+    }
+    // Too long move and move into the ground return player back
+    for (x, y, z) in [(100.5, 4.0, 0.5), (0.5, 2.0, 0.5)] {
+        client.move_to(x, y, z, true).unwrap();
+        loop {
+            match client.poll(Duration::from_secs(5)).unwrap() {
+                Some(ClientEvent::Teleport { x, y, z, .. }) => {
+                    assert_eq!((x, y, z), (0.5, 4.0, 0.5));
+                    break;
+                }
+                Some(_) => {}
+                None => panic!("Player wasn't returned"),
+            }
+        }
+    }
+    client.disconnect();
+}
+
+// Waiting for chat's message at position which contains text
+fn wait_chat(client: &mut Client, position: i8, text: &str) -> String {
+    loop {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Chat(json, pos)) if pos == position && json.contains(text) => {
+                return json
+            }
+            Some(_) => {}
+            None => panic!("Chat message {:?} wasn't received", text),
+        }
+    }
+}
+
+#[test]
+fn chat_format_keeps_player_formatting_only_with_colors() {
+    let message = format_chat("&7<{player}> {message}!", "Alice", "&chi", false);
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"text":"§7<Alice> ","extra":[{"text":"&chi"},{"text":"!"}]}"#
+    );
+    let message = format_chat("<{player}> {message}", "Alice", "&chi", true);
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"text":"<Alice> ","extra":[{"text":"§chi"}]}"#
+    );
+}
+
+#[test]
+fn chat_is_broadcast_and_commands_are_run() {
+    let mut listener = Client::login(server(), "Listener").unwrap();
+    let mut talker = Client::login(server(), "Talker").unwrap();
+    talker.send_chat("  hello &cworld ").unwrap();
+    let json = wait_chat(&mut listener, 0, "hello");
+    assert!(json.contains("<Talker> "));
+    assert!(json.contains(r#""hello &cworld""#));
+    // Commands aren't broadcast, only their answers are sent to sender
+    talker.send_chat("/ping").unwrap();
+    wait_chat(&mut talker, 1, "Talker's ping");
+    // Console's commands can't be run by players
+    talker.send_chat("/save-all").unwrap();
+    wait_chat(&mut talker, 1, "don't have permission");
+    // Too long message kicks the player
+    talker.send_chat(&"a".repeat(300)).unwrap();
+    loop {
+        match talker.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Disconnect(reason)) => {
+                assert!(reason.contains("too long"));
+                break;
+            }
+            Some(_) => {}
+            None => panic!("Player wasn't kicked"),
+        }
+    }
+    listener.disconnect();
+}
+
+// Waiting for Join Game, other events are skipped
+fn wait_join(client: &mut Client) {
+    loop {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::JoinGame { .. }) => return,
+            Some(_) => {}
+            None => panic!("Player didn't join"),
+        }
+    }
+}
+
+#[test]
+fn second_login_kicks_previous_session() {
+    let mut first = Client::login(server(), "Twice").unwrap();
+    wait_join(&mut first);
+    let mut second = Client::login(server(), "Twice").unwrap();
+    wait_join(&mut second);
+    loop {
+        match first.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Disconnect(reason)) => {
+                assert!(reason.contains("logged in from another location"));
+                break;
+            }
+            Some(_) => {}
+            None => panic!("Previous session wasn't kicked"),
+        }
+    }
+    second.disconnect();
+}
+
+#[test]
+fn tab_text_has_server_state() {
+    assert_eq!(
+        format_tab_text("{online}/{max_players} {tps} {mspt}", 3, 19.96, 4.21),
+        "3/20 20.0 4.2"
+    );
+}
+
+#[test]
+fn tab_list_shows_header_and_gamemode() {
+    let mut client = Client::login(server(), "Tabbed").unwrap();
+    let uuid = client.uuid;
+    let mut header = wait_packet(&mut client, 0x4A, |_| true);
+    assert!(header.get_string().unwrap().contains("ULE"));
+    assert!(header.get_string().unwrap().contains("Online: "));
+    // Only operators and console can change game mode
+    client.send_chat("/gamemode creative").unwrap();
+    wait_chat(&mut client, 1, "don't have permission");
+    // Game mode's change is shown in player list
+    console_command("gamemode adventure Tabbed");
+    let mut state = wait_packet(&mut client, 0x1E, |p| p.get_u8().unwrap() == 3);
+    state.get_u8().unwrap();
+    assert_eq!(state.get_f32().unwrap(), 2.0);
+    let mut update = wait_packet(&mut client, 0x2E, |p| p.get_varint().unwrap() == 1);
+    update.get_varint().unwrap();
+    assert_eq!(update.get_varint().unwrap(), 1);
+    assert_eq!(update.get_u128().unwrap(), uuid);
+    assert_eq!(update.get_varint().unwrap(), 2);
+    client.disconnect();
+}
+
+#[test]
+fn desynced_clicks_are_corrected() {
+    let mut client = Client::login(server(), "Desynced").unwrap();
+    // Player is in game after receiving player list's header
+    wait_packet(&mut client, 0x4A, |_| true);
+    console_command("gamemode creative Desynced");
+    wait_packet(&mut client, 0x1E, |p| p.get_u8().unwrap() == 3);
+    let stone = ItemStack::of("minecraft:stone", 10).unwrap();
+    client.creative_action(36, Some(&stone)).unwrap();
+    // Client thinks that slot is empty, server takes stone to cursor and corrects client
+    client.click_window(0, 36, 0, 1, 0, None).unwrap();
+    let mut confirm = wait_packet(&mut client, 0x11, |p| p.get_i8().unwrap() == 0);
+    confirm.get_i8().unwrap();
+    assert_eq!(
+        (confirm.get_i16().unwrap(), confirm.get_bool().unwrap()),
+        (1, false)
+    );
+    let mut items = wait_packet(&mut client, 0x14, |p| p.get_u8().unwrap() == 0);
+    items.get_u8().unwrap();
+    assert_eq!(items.get_i16().unwrap(), 46);
+    let slots: Vec<_> = (0..46).map(|_| items.get_slot().unwrap()).collect();
+    assert_eq!(slots[36], None);
+    let mut cursor = wait_packet(&mut client, 0x16, |p| p.get_i8().unwrap() == -1);
+    cursor.get_i8().unwrap();
+    cursor.get_i16().unwrap();
+    assert_eq!(cursor.get_slot().unwrap(), Some(stone.clone()));
+    // Clicks are ignored until client accepts the correction of rejected click
+    client.click_window(0, 36, 0, 2, 0, None).unwrap();
+    client.confirm_transaction(0, 0).unwrap();
+    client.click_window(0, 36, 0, 4, 0, None).unwrap();
+    client.confirm_transaction(0, 1).unwrap();
+    client.click_window(0, 37, 0, 3, 0, None).unwrap();
+    let mut confirm = wait_packet(&mut client, 0x11, |p| p.get_i8().unwrap() == 0);
+    confirm.get_i8().unwrap();
+    assert_eq!(
+        (confirm.get_i16().unwrap(), confirm.get_bool().unwrap()),
+        (3, true)
+    );
+    client.disconnect();
+}
+
+#[test]
+fn thrown_items_are_picked_up() {
+    let mut client = Client::login(server(), "Collector").unwrap();
+    let (mut spawned, mut loaded) = (false, false);
+    while !spawned || !loaded {
+        match client.poll(Duration::from_secs(5)).unwrap() {
+            Some(ClientEvent::Teleport { .. }) => spawned = true,
+            Some(ClientEvent::ChunkData { x: -3, z: 0, .. }) => loaded = true,
+            Some(_) => {}
+            None => panic!("Player wasn't spawned"),
+        }
+    }
+    // Going away from other players, so nobody else picks up the item
+    for x in [-7.5, -15.5, -23.5, -31.5, -39.5] {
+        client.move_to(x, 4.0, 0.5, true).unwrap();
+    }
+    console_command("gamemode creative Collector");
+    wait_packet(&mut client, 0x1E, |p| p.get_u8().unwrap() == 3);
+    // Item thrown down falls at player's feet
+    client.look(0.0, 90.0, true).unwrap();
+    let stone = ItemStack::of("minecraft:stone", 5).unwrap();
+    client.creative_action(-1, Some(&stone)).unwrap();
+    let mut collect = wait_packet(&mut client, 0x4B, |_| true);
+    collect.get_varint().unwrap();
+    collect.get_varint().unwrap();
+    assert_eq!(collect.get_varint().unwrap(), 5);
+    let mut items = wait_packet(&mut client, 0x14, |p| p.get_u8().unwrap() == 0);
+    items.get_u8().unwrap();
+    assert_eq!(items.get_i16().unwrap(), 46);
+    let slots: Vec<_> = (0..46).map(|_| items.get_slot().unwrap()).collect();
+    assert_eq!(slots[36], Some(stone));
+    client.disconnect();
+}
+
+#[test]
+fn keep_alive_times_out_once() {
+    let profile = GameProfile {
+        name: String::from("Sleeper"),
+        uuid: 1,
+    };
+    let mut player = Player::new(0, profile, 1, 0);
+    let start = player.last_keep_alive;
+    assert_eq!(
+        check_keep_alive(&mut player, start, 1),
+        KeepAliveCheck::Wait
+    );
+    let sent = start + KEEP_ALIVE_INTERVAL;
+    assert_eq!(
+        check_keep_alive(&mut player, sent, 2),
+        KeepAliveCheck::Send(2)
+    );
+    let late = sent + KEEP_ALIVE_TIMEOUT;
+    assert_eq!(check_keep_alive(&mut player, late, 3), KeepAliveCheck::Wait);
+    let late = late + Duration::from_millis(1);
+    assert_eq!(check_keep_alive(&mut player, late, 4), KeepAliveCheck::Kick);
+    // Kicked player stays until disconnection, but isn't kicked again
+    player.kicked = true;
+    assert_eq!(check_keep_alive(&mut player, late, 5), KeepAliveCheck::Wait);
+}
+
+#[test]
+fn latency_is_smoothed() {
+    let profile = GameProfile {
+        name: String::from("Laggy"),
+        uuid: 2,
+    };
+    let mut player = Player::new(0, profile, 1, 0);
+    let mut now = player.last_keep_alive;
+    // First answer sets latency even if it's zero
+    for (rtt, ping) in [(0, 0), (100, 25), (100, 43)] {
+        now += KEEP_ALIVE_INTERVAL;
+        assert_eq!(
+            check_keep_alive(&mut player, now, rtt),
+            KeepAliveCheck::Send(rtt)
+        );
+        let answer = now + Duration::from_millis(rtt as u64);
+        assert!(!update_latency(&mut player, rtt + 1, answer));
+        assert!(update_latency(&mut player, rtt, answer));
+        assert_eq!(player.ping, Some(ping));
+    }
+    assert!(!update_latency(&mut player, 100, now));
+}
+
+#[test]
+fn tasks_run_after_delay_and_repeat() {
+    let mut scheduler = Scheduler::new();
+    // Once after 2 ticks and every 3 ticks after 1 tick
+    scheduler.schedule(0, 2, 0, Box::new(|_| {}));
+    scheduler.schedule(0, 1, 3, Box::new(|_| {}));
+    let mut due = Vec::new();
+    for tick in 0..8 {
+        let tasks = scheduler.take_due(tick);
+        due.push(tasks.len());
+        for task in tasks {
+            scheduler.reschedule(task, tick);
+        }
+    }
+    assert_eq!(due, vec![0, 1, 1, 0, 1, 0, 0, 1]);
+    assert_eq!(scheduler.len(), 1);
+}
+
+#[test]
+fn tick_stats_are_averaged() {
+    let ms = Duration::from_millis;
+    let factor = 1.0 - (-1.0f64 / 60.0).exp();
+    let mut stats = TickStats::new();
+    // First second sets MSPT as is and keeps full TPS
+    for _ in 0..20 {
+        stats.record(ms(10), ms(50));
+    }
+    assert_eq!(stats.ticks, 20);
+    assert_eq!(stats.tps, [20.0; 3]);
+    assert!(stats.mspt.iter().all(|v| (v - 10.0).abs() < 1e-9));
+    // Lagging second(10 ticks of 100ms) moves averages towards its samples
+    for _ in 0..10 {
+        stats.record(ms(40), ms(100));
+    }
+    assert!((stats.tps[0] - (20.0 - 10.0 * factor)).abs() < 1e-9);
+    assert!((stats.mspt[0] - (10.0 + 30.0 * factor)).abs() < 1e-9);
+    // Longer averages change slower
+    assert!(stats.tps[0] < stats.tps[1] && stats.tps[1] < stats.tps[2]);
+    // Catch-up ticks don't make TPS higher than 20
+    for _ in 0..40 {
+        stats.record(ms(1), ms(25));
+    }
+    assert!(stats.tps[0] <= TPS as f64);
+}
+
+#[test]
+fn truncated_frames_are_errors() {
+    // Ping without its payload
+    assert!(decode_frame(
+        ConnectionType::STATUS,
+        Direction::Serverbound,
+        &[0x02, 0x01, 0x05]
+    )
+    .is_err());
+    let mut p = vec![0x01, 0x02, 0x03];
+    assert!(p.get_i32().is_err());
+    assert!(p.get_u128().is_err());
+    assert_eq!(p.get_u16().unwrap(), 0x0102);
+}
+
+#[test]
+fn capture_is_read_back() {
+    let path = std::env::temp_dir().join(format!("ule-test-capture-{}.cap", std::process::id()));
+    // Test server's connections may be captured too, so records are filtered by connection
+    let conn = usize::MAX >> 2;
+    let mut ping = Vec::new();
+    ping.write_i64(42);
+    let ping = ping.create_packet(0x01);
+    start_capture(path.to_str().unwrap()).unwrap();
+    record_open(conn, "127.0.0.1:25565".parse().unwrap());
+    record_frame(conn, Direction::Serverbound, &ping);
+    record_frame(conn, Direction::Clientbound, &ping);
+    record_close(conn);
+    finish_capture();
+    let mut reader = CaptureReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let mut events = Vec::new();
+    while let Some(record) = reader.next_record().unwrap() {
+        if record.conn == conn {
+            events.push(record.event);
+        }
+    }
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(&events[0], CaptureEvent::Open(address) if address == "127.0.0.1:25565"));
+    assert!(
+        matches!(&events[1], CaptureEvent::Frame(Direction::Serverbound, frame) if *frame == ping)
+    );
+    assert!(
+        matches!(&events[2], CaptureEvent::Frame(Direction::Clientbound, frame) if *frame == ping)
+    );
+    assert!(matches!(events[3], CaptureEvent::Close));
+}
+
+#[test]
+fn truncated_packet_disconnects_only_its_player() {
+    let mut client = Client::login(server(), "Truncated").unwrap();
+    // Player Position without y, z and on ground
+    let mut bytes = Vec::new();
+    bytes.write_f64(0.5);
+    client.send(&bytes.create_packet(0x0D)).unwrap();
+    loop {
+        match client.poll(Duration::from_secs(5)) {
+            Err(_) => break,
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("Player wasn't disconnected"),
+        }
+    }
+    // Server keeps working
+    Client::login(server(), "AfterTruncated")
+        .unwrap()
+        .disconnect();
+}
@@ -0,0 +1,925 @@
+use std::env;
+use std::fs;
+use ule::game::entity::{Entity, EntityType};
+use ule::game::player::Player;
+use ule::game::playerdata::{load_player_data, player_from_nbt, save_player_data};
+use ule::game::window::{click, window_items, Click, Contents, Window, OUTSIDE_SLOT};
+use ule::network::GameProfile;
+use ule::utils::nbt::{read_gzip, write_gzip, Compound, Tag};
+use ule::world::anvil::{chunk_from_nbt, chunk_to_nbt, RegionStorage};
+use ule::world::bits::BitArray;
+use ule::world::block::{
+    block, collision_box, hardness, light_emission, opacity, parse_block_state, state_of,
+    state_properties, state_to_string,
+};
+use ule::world::generator::terrain::SEA_LEVEL;
+use ule::world::generator::{ChunkGenerator, FlatGenerator, TerrainGenerator, VoidGenerator};
+use ule::world::item::{item, item_by_name, ItemStack};
+use ule::world::level::parse_seed;
+use ule::world::light::light_column;
+use ule::world::region::RegionFile;
+use ule::world::section::{ChunkSection, GLOBAL_BITS, MAX_INDIRECT_BITS, MIN_BITS};
+use ule::world::{ChunkColumn, World, WorldInfo};
+
+#[test]
+fn bit_array_values_span_longs() {
+    let mut array = BitArray::new(13, 4096);
+    for i in 0..4096 {
+        array.set(i, (i as u32 * 7) & 0x1FFF);
+    }
+    for i in 0..4096 {
+        assert_eq!(array.get(i), (i as u32 * 7) & 0x1FFF);
+    }
+    assert_eq!(array.data().len(), 832);
+}
+
+#[test]
+fn palette_grows_to_global() {
+    let mut section = ChunkSection::new();
+    assert_eq!(section.bits(), MIN_BITS);
+    // 16 states fit in 4 bits with air
+    for i in 1..16u16 {
+        section.set_index(i as usize, i << 4);
+    }
+    assert_eq!(section.bits(), MIN_BITS);
+    section.set_index(16, 16 << 4);
+    assert_eq!(section.bits(), MIN_BITS + 1);
+    for i in 17..256u16 {
+        section.set_index(i as usize, i << 4);
+    }
+    assert_eq!(section.bits(), MAX_INDIRECT_BITS);
+    section.set_index(256, 256 << 4);
+    assert_eq!(section.bits(), GLOBAL_BITS);
+    assert!(section.palette().is_empty());
+    for i in 1..=256u16 {
+        assert_eq!(section.get_index(i as usize), i << 4);
+    }
+    assert_eq!(section.block_count(), 256);
+}
+
+#[test]
+fn column_stores_blocks_light_and_biomes() {
+    let mut column = ChunkColumn::new(3, -2, true);
+    assert_eq!(column.section_mask(), 0);
+    column.set_block(1, 70, 2, 1 << 4);
+    assert_eq!(column.get_block(1, 70, 2), 1 << 4);
+    assert_eq!(column.section_mask(), 1 << 4);
+    assert_eq!(column.highest_block(1, 2), Some(70));
+    column.set_block_light(1, 71, 2, 14);
+    column.set_sky_light(1, 69, 2, 3);
+    assert_eq!(column.get_block_light(1, 71, 2), 14);
+    assert_eq!(column.get_sky_light(1, 69, 2), 3);
+    assert_eq!(column.get_sky_light(0, 200, 0), 15);
+    column.set_biome(15, 15, 2);
+    assert_eq!(column.get_biome(15, 15), 2);
+    // Removing the last block empties the section
+    column.set_block(1, 70, 2, 0);
+    assert_eq!(column.section_mask(), 0);
+}
+
+// Empty directory for test's files
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = env::temp_dir().join(format!("ule-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Chunk with some blocks, light and a block entity
+fn sample_chunk(x: i32, z: i32) -> ChunkColumn {
+    let mut column = ChunkColumn::new(x, z, true);
+    for i in 0..16 {
+        column.set_block(i, i * 9, 15 - i, ((i as u16 + 1) << 4) | 3);
+    }
+    // Block's id over 255 needs Add array
+    column.set_block(5, 5, 5, 300 << 4);
+    column.set_block_light(1, 2, 3, 12);
+    column.set_sky_light(1, 2, 3, 4);
+    column.set_biome(3, 4, 6);
+    let mut chest = Compound::new();
+    chest.insert(String::from("id"), Tag::from("minecraft:chest"));
+    column.block_entities.push(chest);
+    column
+}
+
+#[test]
+fn anvil_chunk_round_trip() {
+    let column = sample_chunk(-33, 40);
+    let mut read = chunk_from_nbt(&chunk_to_nbt(&column), true).unwrap();
+    read.dirty = column.dirty;
+    // New chunk gets default values of Level
+    assert_eq!(read.other.get("InhabitedTime"), Some(&Tag::Long(0)));
+    read.other.clear();
+    assert_eq!(read, column);
+}
+
+#[test]
+fn anvil_chunk_of_newer_version_is_rejected() {
+    let mut root = chunk_to_nbt(&sample_chunk(1, 2));
+    root.insert(String::from("DataVersion"), Tag::Int(1519));
+    let err = chunk_from_nbt(&root, true).unwrap_err();
+    assert!(err.0.contains("DataVersion 1519"));
+}
+
+#[test]
+fn anvil_chunk_keeps_unknown_values() {
+    let mut root = chunk_to_nbt(&sample_chunk(2, 3));
+    let level = match root.get_mut("Level") {
+        Some(Tag::Compound(v)) => v,
+        _ => panic!("no Level"),
+    };
+    let mut pig = Compound::new();
+    pig.insert(String::from("id"), Tag::from("minecraft:pig"));
+    level.insert(
+        String::from("Entities"),
+        Tag::List(vec![Tag::Compound(pig)]),
+    );
+    level.insert(String::from("InhabitedTime"), Tag::Long(1200));
+    level.insert(String::from("TileTicks"), Tag::List(Vec::new()));
+    level.insert(String::from("Custom"), Tag::Int(5));
+    let mut column = chunk_from_nbt(&root, true).unwrap();
+    assert!(!column.other.contains_key("Sections"));
+    column.set_block(0, 0, 0, 1 << 4);
+    let saved = chunk_to_nbt(&column);
+    let level = saved.get("Level").and_then(|v| v.as_compound()).unwrap();
+    for name in ["Entities", "InhabitedTime", "TileTicks", "Custom"] {
+        assert_eq!(
+            level.get(name),
+            root["Level"].as_compound().unwrap().get(name)
+        );
+    }
+    assert_eq!(
+        chunk_from_nbt(&saved, true).unwrap().get_block(0, 0, 0),
+        1 << 4
+    );
+}
+
+#[test]
+fn region_reuses_and_compacts_sectors() {
+    let path = temp_dir("region").join("r.0.0.mca");
+    let mut region = RegionFile::open(&path).unwrap();
+    assert_eq!(region.sectors(), 2);
+    let mut small = Compound::new();
+    small.insert(String::from("value"), Tag::Int(1));
+    // Random bytes don't compress, so chunk takes 3 sectors
+    let mut seed = 1u32;
+    let noise: Vec<i8> = (0..10000)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as i8
+        })
+        .collect();
+    let mut big = Compound::new();
+    big.insert(String::from("noise"), Tag::ByteArray(noise));
+    region.write_chunk(0, 0, &big).unwrap();
+    region.write_chunk(1, 0, &small).unwrap();
+    assert_eq!(region.sectors(), 6);
+    // Smaller chunk fits into old sectors
+    region.write_chunk(0, 0, &small).unwrap();
+    assert_eq!(region.sectors(), 6);
+    assert_eq!(region.read_chunk(0, 0).unwrap(), Some(small.clone()));
+    region.compact().unwrap();
+    assert_eq!(region.sectors(), 4);
+    assert_eq!(fs::metadata(&path).unwrap().len(), 4 * 4096);
+    // Chunks are the same after reopening
+    let mut region = RegionFile::open(&path).unwrap();
+    assert_eq!(region.read_chunk(0, 0).unwrap(), Some(small.clone()));
+    assert_eq!(region.read_chunk(1, 0).unwrap(), Some(small));
+    assert_eq!(region.read_chunk(2, 0).unwrap(), None);
+}
+
+#[test]
+fn region_ignores_chunks_without_sectors() {
+    let path = temp_dir("region-empty").join("r.0.0.mca");
+    // Chunk's location with first sector 2 and no sectors
+    let mut data = vec![0; 3 * 4096];
+    data[0..4].copy_from_slice(&(2u32 << 8).to_be_bytes());
+    fs::write(&path, data).unwrap();
+    let mut region = RegionFile::open(&path).unwrap();
+    assert!(!region.has_chunk(0, 0));
+    assert_eq!(region.read_chunk(0, 0).unwrap(), None);
+}
+
+#[test]
+fn storage_saves_chunks_into_regions() {
+    let dir = temp_dir("storage");
+    let mut storage = RegionStorage::new(&dir);
+    assert_eq!(storage.load_chunk(40, -1, true).unwrap(), None);
+    // Loading doesn't create files
+    assert!(fs::read_dir(&dir).unwrap().next().is_none());
+    storage.save_chunk(&sample_chunk(40, -1)).unwrap();
+    assert!(dir.join("r.1.-1.mca").exists());
+    let mut storage = RegionStorage::new(&dir);
+    let mut loaded = storage.load_chunk(40, -1, true).unwrap().unwrap();
+    loaded.dirty = true;
+    loaded.other.clear();
+    assert_eq!(loaded, sample_chunk(40, -1));
+}
+
+#[test]
+fn unloaded_chunks_are_saved() {
+    let dir = temp_dir("unload");
+    let mut world = World::open(&dir, true);
+    world.set_block(3, 10, 3, 1 << 4);
+    world.chunk(1, 0);
+    world.chunk(2, 0).dirty = false;
+    assert_eq!(world.unload_chunks(|v| *v == (1, 0)), 2);
+    assert_eq!(world.chunks.len(), 1);
+    assert_eq!(world.get_block(3, 10, 3), 0);
+    assert_eq!(world.chunk(0, 0).get_block(3, 10, 3), 1 << 4);
+    // Changes of world without storage aren't lost
+    let mut world = World::new(true);
+    world.set_block(3, 10, 3, 1 << 4);
+    assert_eq!(world.unload_chunks(|_| false), 0);
+    assert_eq!(world.get_block(3, 10, 3), 1 << 4);
+}
+
+#[test]
+fn chunks_failed_to_load_are_not_overwritten() {
+    let dir = temp_dir("broken");
+    let mut broken = Compound::new();
+    broken.insert(String::from("DataVersion"), Tag::Int(1519));
+    RegionFile::open(&dir.join("r.0.0.mca"))
+        .unwrap()
+        .write_chunk(0, 0, &broken)
+        .unwrap();
+    let mut world = World::open(&dir, true);
+    world.set_block(3, 10, 3, 1 << 4);
+    assert_eq!(world.save(), 0);
+    assert_eq!(world.unload_chunks(|_| false), 1);
+    // Chunk isn't generated in background too
+    let mut world = World::open(&dir, true).with_generator(Box::new(VoidGenerator::new(None)), 0);
+    world.start_workers(1);
+    assert!(world.request_chunk(0, 0));
+    world.set_block(3, 10, 3, 1 << 4);
+    assert_eq!(world.save(), 0);
+    let mut region = RegionFile::open(&dir.join("r.0.0.mca")).unwrap();
+    assert_eq!(region.read_chunk(0, 0).unwrap(), Some(broken));
+}
+
+#[test]
+fn seed_is_parsed_like_vanilla() {
+    assert_eq!(parse_seed("-42"), -42);
+    // Java's "hello".hashCode()
+    assert_eq!(parse_seed("hello"), 99162322);
+    assert_ne!(parse_seed(""), parse_seed("0"));
+}
+
+#[test]
+fn level_dat_saves_atomically_and_keeps_unknown_values() {
+    let dir = temp_dir("level");
+    assert_eq!(WorldInfo::load(&dir).unwrap(), None);
+    // Vanilla's level.dat with value unknown for server and without some rules
+    let mut info = WorldInfo::new("Test", 1234);
+    let mut root = info.to_nbt().unwrap();
+    let data = root.get_mut("Data").unwrap().as_compound_mut().unwrap();
+    data.insert(String::from("BorderSize"), Tag::Double(100.0));
+    data.get_mut("GameRules")
+        .unwrap()
+        .as_compound_mut()
+        .unwrap()
+        .remove("keepInventory");
+    fs::write(dir.join("level.dat"), write_gzip(&root).unwrap()).unwrap();
+    let mut loaded = WorldInfo::load(&dir).unwrap().unwrap();
+    assert_eq!(loaded.seed, 1234);
+    assert_eq!(loaded.name, "Test");
+    assert_eq!(loaded.spawn(), info.spawn());
+    assert_eq!(loaded.game_rule("keepInventory"), Some("false"));
+    // Previous level.dat becomes level.dat_old
+    loaded.day_time = 6000;
+    loaded.raining = true;
+    loaded.save(&dir).unwrap();
+    assert!(dir.join("level.dat_old").exists());
+    assert!(!dir.join("level.dat_new").exists());
+    let saved = read_gzip(&fs::read(dir.join("level.dat")).unwrap()).unwrap();
+    let data = saved["Data"].as_compound().unwrap();
+    assert_eq!(data["BorderSize"], Tag::Double(100.0));
+    assert_eq!(data["raining"], Tag::Byte(1));
+    assert_eq!(data["RandomSeed"], Tag::Long(1234));
+    // Broken level.dat falls back to level.dat_old
+    fs::write(dir.join("level.dat"), b"broken").unwrap();
+    info = WorldInfo::load(&dir).unwrap().unwrap();
+    assert_eq!(info.day_time, 0);
+    assert_eq!(info.seed, 1234);
+}
+
+#[test]
+fn block_states_are_parsed_by_names_and_ids() {
+    assert_eq!(parse_block_state("minecraft:grass"), Some(2 << 4));
+    assert_eq!(parse_block_state("stone:1"), Some(1 << 4 | 1));
+    assert_eq!(parse_block_state("35:14"), Some(35 << 4 | 14));
+    assert_eq!(
+        parse_block_state("minecraft:structure_block"),
+        Some(255 << 4)
+    );
+    assert_eq!(parse_block_state("minecraft:unknown"), None);
+    assert_eq!(parse_block_state("stone:16"), None);
+    assert_eq!(parse_block_state("stone:7"), None);
+    assert_eq!(
+        parse_block_state("minecraft:oak_stairs[facing=north,half=top,shape=straight]"),
+        Some(53 << 4 | 7)
+    );
+    assert_eq!(
+        parse_block_state("minecraft:log[axis=x,variant=birch]"),
+        Some(17 << 4 | 6)
+    );
+    assert_eq!(parse_block_state("minecraft:log[variant=maple]"), None);
+}
+
+#[test]
+fn all_block_states_map_to_properties_and_back() {
+    let mut states = 0;
+    for state in 0..(256 << 4) {
+        let (name, properties) = match state_properties(state) {
+            Some(v) => v,
+            None => continue,
+        };
+        states += 1;
+        let pairs: Vec<(&str, &str)> = properties
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        // Some metadata are duplicates of other(like upper door's halves)
+        let found = state_of(name, &pairs).unwrap();
+        assert_eq!(state_properties(found).unwrap().1, properties);
+        assert_eq!(parse_block_state(&state_to_string(state)), Some(found));
+    }
+    assert!(states > 1500, "{}", states);
+    assert_eq!(state_to_string(35 << 4 | 14), "minecraft:wool[color=red]");
+    assert_eq!(state_to_string(1 << 4), "minecraft:stone[variant=stone]");
+    assert_eq!(state_to_string(4 << 4), "minecraft:cobblestone");
+    assert_eq!(
+        state_of("wooden_door", &[("half", "upper"), ("hinge", "right")]),
+        Some(64 << 4 | 9)
+    );
+}
+
+#[test]
+fn blocks_have_lighting_and_physics_data() {
+    assert_eq!(block(89).unwrap().name, "minecraft:glowstone");
+    assert_eq!(light_emission(89 << 4), 15);
+    assert_eq!(light_emission(50 << 4 | 5), 14);
+    assert_eq!(opacity(0), 0);
+    assert_eq!(opacity(1 << 4), 15);
+    assert_eq!(opacity(18 << 4), 1);
+    assert_eq!(opacity(9 << 4), 3);
+    assert_eq!(collision_box(0), None);
+    assert_eq!(collision_box(1 << 4), Some([0.0, 0.0, 0.0, 1.0, 1.0, 1.0]));
+    assert_eq!(collision_box(44 << 4), Some([0.0, 0.0, 0.0, 1.0, 0.5, 1.0]));
+    assert_eq!(
+        collision_box(44 << 4 | 8),
+        Some([0.0, 0.5, 0.0, 1.0, 1.0, 1.0])
+    );
+    assert_eq!(block(7).unwrap().hardness, -1.0);
+    assert!(block(253).is_none());
+    assert_eq!(hardness(253 << 4), -1.0);
+}
+
+#[test]
+fn doors_gates_and_trapdoors_collide_by_state() {
+    let gate = |facing, open| state_of("fence_gate", &[("facing", facing), ("open", open)]);
+    assert_eq!(
+        collision_box(gate("north", "false").unwrap()),
+        Some([0.0, 0.0, 0.375, 1.0, 1.5, 0.625])
+    );
+    assert_eq!(
+        collision_box(gate("east", "false").unwrap()),
+        Some([0.375, 0.0, 0.0, 0.625, 1.5, 1.0])
+    );
+    assert_eq!(collision_box(gate("east", "true").unwrap()), None);
+    let trapdoor = |half, open| {
+        let state = &[("facing", "south"), ("half", half), ("open", open)];
+        collision_box(state_of("trapdoor", state).unwrap())
+    };
+    assert_eq!(
+        trapdoor("bottom", "false"),
+        Some([0.0, 0.0, 0.0, 1.0, 0.1875, 1.0])
+    );
+    assert_eq!(
+        trapdoor("top", "false"),
+        Some([0.0, 0.8125, 0.0, 1.0, 1.0, 1.0])
+    );
+    assert_eq!(
+        trapdoor("top", "true"),
+        Some([0.0, 0.0, 0.0, 1.0, 1.0, 0.1875])
+    );
+    // Open door's box depends on hinge from upper half
+    let mut world = World::new(true);
+    let lower = |open| {
+        let state = &[("facing", "east"), ("half", "lower"), ("open", open)];
+        state_of("wooden_door", state).unwrap()
+    };
+    let upper = state_of("wooden_door", &[("half", "upper"), ("hinge", "right")]).unwrap();
+    world.set_block(0, 10, 0, lower("false"));
+    world.set_block(0, 11, 0, upper);
+    let closed = Some([0.0, 0.0, 0.0, 0.1875, 1.0, 1.0]);
+    assert_eq!(world.collision_box(0, 10, 0), closed);
+    assert_eq!(world.collision_box(0, 11, 0), closed);
+    world.set_block(0, 10, 0, lower("true"));
+    let open = Some([0.0, 0.0, 0.8125, 1.0, 1.0, 1.0]);
+    assert_eq!(world.collision_box(0, 10, 0), open);
+    assert_eq!(world.collision_box(0, 11, 0), open);
+    let upper = state_of("wooden_door", &[("half", "upper"), ("hinge", "left")]).unwrap();
+    world.set_block(0, 11, 0, upper);
+    assert_eq!(
+        world.collision_box(0, 11, 0),
+        Some([0.0, 0.0, 0.0, 1.0, 1.0, 0.1875])
+    );
+}
+
+#[test]
+fn superflat_preset_is_parsed() {
+    let flat =
+        FlatGenerator::parse("3;minecraft:bedrock,2*minecraft:dirt,minecraft:grass;1;village")
+            .unwrap();
+    assert_eq!(flat.layers, vec![7 << 4, 3 << 4, 3 << 4, 2 << 4]);
+    assert_eq!(flat.biome, 1);
+    assert_eq!(flat.structures, vec![String::from("village")]);
+    assert_eq!(FlatGenerator::parse("").unwrap(), flat);
+    assert_eq!(FlatGenerator::parse(&flat.preset()).unwrap(), flat);
+    let flat = FlatGenerator::parse(
+        "3;minecraft:bedrock,230*minecraft:stone:2,minecraft:air;2;mineshaft(chance=0.01),decoration",
+    )
+    .unwrap();
+    assert_eq!(flat.layers.len(), 232);
+    assert_eq!(flat.layers[1], 1 << 4 | 2);
+    assert_eq!(flat.structures.len(), 2);
+    // Old version with ids
+    let flat = FlatGenerator::parse("2;7,2x3,2;1").unwrap();
+    assert_eq!(flat.layers, vec![7 << 4, 3 << 4, 3 << 4, 2 << 4]);
+    assert_eq!(FlatGenerator::parse("3;minecraft:nothing;1"), None);
+    assert_eq!(FlatGenerator::parse("3;300*minecraft:stone;1"), None);
+    let huge = format!("3;minecraft:stone,{}*minecraft:stone;1", usize::MAX);
+    assert_eq!(FlatGenerator::parse(&huge), None);
+
+    let column = FlatGenerator::default().generate(-3, 5, 0);
+    assert_eq!((column.x, column.z), (-3, 5));
+    assert_eq!(column.get_block(7, 0, 9), 7 << 4);
+    assert_eq!(column.get_block(15, 3, 0), 2 << 4);
+    assert_eq!(column.highest_block(0, 0), Some(3));
+}
+
+#[test]
+fn void_has_only_spawn_platform() {
+    let void = VoidGenerator::new(Some((1 << 4, (15, 63, 0), 1)));
+    let column = void.generate(0, 0, 0);
+    assert_eq!(column.get_block(14, 63, 0), 1 << 4);
+    assert_eq!(column.get_block(15, 63, 1), 1 << 4);
+    assert_eq!(column.get_block(13, 63, 0), 0);
+    // Platform continues in neighbour chunks
+    assert_eq!(void.generate(1, -1, 0).get_block(0, 63, 15), 1 << 4);
+    assert_eq!(void.generate(5, 5, 0).section_mask(), 0);
+    assert_eq!(VoidGenerator::new(None).generate(0, 0, 0).section_mask(), 0);
+}
+
+#[test]
+fn terrain_is_deterministic() {
+    let terrain = TerrainGenerator::default();
+    let column = terrain.generate(3, -2, 42);
+    assert_eq!(column, terrain.generate(3, -2, 42));
+    assert_ne!(column, terrain.generate(3, -2, 43));
+    for z in 0..16 {
+        for x in 0..16 {
+            assert_eq!(column.get_block(x, 0, z), 7 << 4);
+            let info = terrain.column_info(42, 48 + x as i32, -32 + z as i32);
+            assert_eq!(column.get_biome(x, z), info.biome.id);
+            // Oceans are filled with water(or ice) up to sea level
+            if info.height < SEA_LEVEL {
+                let top = column.get_block(x, SEA_LEVEL as usize, z) >> 4;
+                assert!(top == 9 || top == 79);
+            }
+        }
+    }
+}
+
+#[test]
+fn workers_generate_chunks_in_background() {
+    let mut world = World::new(true).with_generator(Box::new(TerrainGenerator::default()), 7);
+    world.start_workers(2);
+    assert!(!world.request_chunk(10, 10));
+    assert!(!world.request_chunk(10, 10));
+    for _ in 0..500 {
+        world.receive_generated();
+        if world.request_chunk(10, 10) {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert!(world.chunks.contains_key(&(10, 10)));
+    // Workers light generated chunks
+    let mut expected = TerrainGenerator::default().generate(10, 10, 7);
+    light_column(&mut expected);
+    assert_eq!(world.chunk(10, 10).clone(), expected);
+}
+
+// Generator which is slow and panics at chunk 0, 0
+struct BrokenGenerator;
+
+impl ChunkGenerator for BrokenGenerator {
+    fn generate(&self, x: i32, z: i32, _seed: i64) -> ChunkColumn {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        if (x, z) == (0, 0) {
+            panic!("Broken chunk");
+        }
+        ChunkColumn::new(x, z, true)
+    }
+}
+
+// Receiving workers' results until chunk isn't pending
+fn wait_generated(world: &mut World, x: i32, z: i32) {
+    for _ in 0..500 {
+        world.receive_generated();
+        if !world.is_pending(x, z) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    panic!("Chunk {}, {} is still pending", x, z);
+}
+
+#[test]
+fn failed_and_cancelled_chunks_are_not_pending() {
+    let mut world = World::new(true).with_generator(Box::new(BrokenGenerator), 0);
+    world.start_workers(1);
+    for x in 0..4 {
+        assert!(!world.request_chunk(x, 0));
+    }
+    // Panic gives an empty chunk instead of waiting forever
+    wait_generated(&mut world, 0, 0);
+    assert!(world.chunks[&(0, 0)].sections.iter().all(|v| v.is_none()));
+    assert!(!world.chunks[&(0, 0)].dirty);
+    // Queued chunks out of view are skipped
+    world.cancel_requests(|v| *v == (3, 0));
+    wait_generated(&mut world, 3, 0);
+    assert!(world.chunks.contains_key(&(3, 0)));
+    assert!(!world.is_pending(2, 0));
+    assert!(!world.chunks.contains_key(&(2, 0)));
+    // Worker survived the panic
+    assert!(!world.request_chunk(2, 0));
+    wait_generated(&mut world, 2, 0);
+    assert!(world.chunks.contains_key(&(2, 0)));
+}
+
+#[test]
+fn item_registry_has_vanilla_items() {
+    let sword = item_by_name("diamond_sword").unwrap();
+    assert_eq!(sword.id, 276);
+    assert_eq!((sword.max_stack, sword.durability), (1, 1561));
+    assert!(!sword.is_block());
+    assert_eq!(item(1).unwrap().name, "minecraft:stone");
+    assert!(item(1).unwrap().is_block());
+    assert!(item_by_name("minecraft:dye").unwrap().subtypes);
+    assert_eq!(item_by_name("ender_pearl").unwrap().max_stack, 16);
+    assert_eq!(item_by_name("record_wait").unwrap().id, 2267);
+    assert!(item_by_name("unknown_item").is_none());
+}
+
+#[test]
+fn item_stack_tag_round_trip() {
+    let mut stack = ItemStack::of("diamond_sword", 1).unwrap();
+    assert_eq!(stack.max_stack(), 1);
+    stack.set_display_name(Some("\u{a7}bExcalibur"));
+    stack.set_lore(&["Line 1", "Line 2"]);
+    stack.set_enchantment(16, 5);
+    stack.set_enchantment(34, 3);
+    stack.set_enchantment(16, 4);
+    stack.set_unbreakable(true);
+    assert_eq!(stack.display_name(), Some("\u{a7}bExcalibur"));
+    assert_eq!(stack.lore(), vec!["Line 1", "Line 2"]);
+    assert_eq!(stack.enchantments(), vec![(34, 3), (16, 4)]);
+    assert!(stack.is_unbreakable());
+    let nbt = stack.to_nbt();
+    assert_eq!(nbt.get("id"), Some(&Tag::from("minecraft:diamond_sword")));
+    assert_eq!(ItemStack::from_nbt(&nbt), Some(stack.clone()));
+    // Removing everything leaves item without tag
+    stack.set_display_name(None);
+    stack.set_lore(&[]);
+    stack.set_enchantment(16, 0);
+    stack.set_enchantment(34, 0);
+    stack.set_unbreakable(false);
+    assert_eq!(stack.nbt, None);
+    // Enchanted book stores enchantments
+    let mut book = ItemStack::of("enchanted_book", 1).unwrap();
+    book.set_enchantment(0, 4);
+    assert!(book
+        .nbt
+        .as_ref()
+        .unwrap()
+        .contains_key("StoredEnchantments"));
+}
+
+#[test]
+fn column_is_lit_by_sky_and_blocks() {
+    let glowstone = state_of("glowstone", &[]).unwrap();
+    let mut column = FlatGenerator::default().generate(0, 0, 0);
+    column.set_block(8, 4, 8, glowstone);
+    light_column(&mut column);
+    // Sky's light is full over grass and doesn't go through it
+    assert_eq!(column.get_sky_light(0, 4, 0), 15);
+    assert_eq!(column.get_sky_light(0, 200, 0), 15);
+    assert_eq!(column.get_sky_light(0, 3, 0), 0);
+    assert_eq!(column.get_sky_light(8, 4, 8), 0);
+    assert_eq!(column.get_block_light(8, 4, 8), 15);
+    assert_eq!(column.get_block_light(9, 4, 8), 14);
+    assert_eq!(column.get_block_light(8, 4, 12), 11);
+    assert_eq!(column.get_block_light(10, 6, 9), 10);
+    assert_eq!(column.get_block_light(8, 3, 8), 0);
+}
+
+#[test]
+fn light_is_updated_across_chunks() {
+    let glowstone = state_of("glowstone", &[]).unwrap();
+    let stone = state_of("stone", &[]).unwrap();
+    let mut world = World::new(true).with_generator(Box::new(FlatGenerator::default()), 0);
+    for x in -1..=1 {
+        for z in -1..=1 {
+            world.chunk(x, z);
+        }
+    }
+    // Block's light goes to the next chunk and disappears with its source
+    world.set_block(15, 4, 0, glowstone);
+    assert_eq!(world.get_block_light(16, 4, 0), 14);
+    assert_eq!(world.get_block_light(20, 4, 0), 10);
+    world.set_block(15, 4, 0, 0);
+    assert_eq!(world.get_block_light(16, 4, 0), 0);
+    assert_eq!(world.get_block_light(15, 4, 0), 0);
+    // Roof over chunks' border darkens blocks under it
+    for x in -3..=3 {
+        for z in -3..=3 {
+            world.set_block(x, 10, z, stone);
+        }
+    }
+    assert_eq!(world.get_sky_light(0, 11, 0), 15);
+    assert_eq!(world.get_sky_light(0, 9, 0), 11);
+    assert_eq!(world.get_sky_light(0, 5, 0), 11);
+    assert_eq!(world.get_sky_light(-3, 9, 0), 14);
+    assert_eq!(world.get_sky_light(4, 5, 0), 15);
+    // Hole in roof lets the sky in
+    world.set_block(0, 10, 0, 0);
+    assert_eq!(world.get_sky_light(0, 9, 0), 15);
+    assert_eq!(world.get_sky_light(0, 5, 0), 15);
+    assert_eq!(world.get_sky_light(1, 5, 0), 14);
+    world.set_block(0, 10, 0, stone);
+    assert_eq!(world.get_sky_light(0, 5, 0), 11);
+}
+
+#[test]
+fn light_spreads_into_new_chunks() {
+    let stone = state_of("stone", &[]).unwrap();
+    let mut world = World::new(true);
+    for x in 0..16 {
+        for z in 0..16 {
+            world.set_block(x, 10, z, stone);
+        }
+    }
+    assert_eq!(world.get_sky_light(15, 9, 5), 0);
+    world.chunk(1, 0);
+    assert_eq!(world.get_sky_light(15, 9, 5), 14);
+    assert_eq!(world.get_sky_light(12, 9, 5), 11);
+    assert_eq!(world.get_sky_light(12, 2, 5), 11);
+}
+
+fn test_player(name: &str) -> Player {
+    let profile = GameProfile {
+        name: String::from(name),
+        uuid: 0x069a79f444e94726a5befca90e38aaf5,
+    };
+    Player::new(0, profile, 1, 0)
+}
+
+#[test]
+fn player_data_is_saved_and_loaded() {
+    let dir = temp_dir("playerdata");
+    let mut player = test_player("Saver");
+    let uuid = player.profile.uuid;
+    let mut entity = Entity::with_id(1, uuid, EntityType::Player, 10.5, 70.0, -3.25);
+    (entity.yaw, entity.pitch) = (90.0, 15.0);
+    player.health = 12.5;
+    player.food = 17;
+    player.xp_level = 5;
+    player.xp_progress = 0.5;
+    player.xp_total = 60;
+    player.gamemode = 2;
+    player.inventory.selected = 4;
+    player.inventory.main[4] = ItemStack::of("minecraft:stone", 32);
+    player.inventory.armor[3] = ItemStack::of("minecraft:diamond_helmet", 1);
+    player.inventory.offhand = ItemStack::of("minecraft:torch", 16);
+    player.ender_chest[26] = ItemStack::of("minecraft:apple", 3);
+    // Values which server doesn't use are kept
+    player
+        .saved_data
+        .insert(String::from("Score"), Tag::Int(42));
+    save_player_data(&dir, &player, &entity).unwrap();
+    assert!(dir
+        .join("069a79f4-44e9-4726-a5be-fca90e38aaf5.dat")
+        .exists());
+    let nbt = load_player_data(&dir, player.profile.uuid)
+        .unwrap()
+        .unwrap();
+    assert_eq!(nbt.get("Score"), Some(&Tag::Int(42)));
+    let mut loaded = test_player("Saver");
+    let loaded_entity = player_from_nbt(&mut loaded, &nbt).unwrap();
+    assert_eq!(loaded_entity.position(), (10.5, 70.0, -3.25));
+    assert_eq!((loaded_entity.yaw, loaded_entity.pitch), (90.0, 15.0));
+    assert_eq!((loaded_entity.id, loaded_entity.uuid), (1, uuid));
+    assert_eq!((loaded.health, loaded.food), (12.5, 17));
+    assert_eq!(
+        (loaded.xp_level, loaded.xp_progress, loaded.xp_total),
+        (5, 0.5, 60)
+    );
+    assert_eq!(loaded.gamemode, 2);
+    assert_eq!(loaded.inventory, player.inventory);
+    assert_eq!(loaded.ender_chest, player.ender_chest);
+    assert_eq!(load_player_data(&dir, 1).unwrap(), None);
+}
+
+#[test]
+fn player_data_from_other_dimension_keeps_spawn() {
+    let mut nbt = Compound::new();
+    nbt.insert(String::from("Dimension"), Tag::Int(-1));
+    nbt.insert(
+        String::from("Pos"),
+        Tag::List(vec![Tag::Double(1.0), Tag::Double(2.0), Tag::Double(3.0)]),
+    );
+    nbt.insert(String::from("Health"), Tag::Float(5.0));
+    let mut player = test_player("Traveler");
+    assert_eq!(player_from_nbt(&mut player, &nbt), None);
+    assert_eq!(player.health, 5.0);
+}
+
+#[test]
+fn items_are_added_to_similar_stacks_first() {
+    let mut player = test_player("Collector");
+    let inventory = &mut player.inventory;
+    inventory.main[5] = ItemStack::of("minecraft:stone", 60);
+    assert_eq!(
+        inventory.add_item(ItemStack::of("minecraft:stone", 10).unwrap()),
+        None
+    );
+    assert_eq!(
+        (count(&inventory.main[5]), count(&inventory.main[0])),
+        (64, 6)
+    );
+    // Empty stack doesn't take a slot
+    let mut empty = ItemStack::of("minecraft:dirt", 1).unwrap();
+    empty.count = 0;
+    assert_eq!(inventory.add_item(empty), None);
+    assert_eq!(inventory.main[1], None);
+    // What doesn't fit is returned
+    for slot in inventory.main.iter_mut() {
+        slot.get_or_insert(ItemStack::of("minecraft:stone", 64).unwrap());
+    }
+    let rest = inventory.add_item(ItemStack::of("minecraft:dirt", 3).unwrap());
+    assert_eq!(rest, ItemStack::of("minecraft:dirt", 3));
+}
+
+// Clicking slot of player's window
+fn click_slot(player: &mut Player, slot: i16, button: i8, mode: i32) -> Option<Vec<ItemStack>> {
+    click(player, Click { slot, button, mode }).map(|v| v.dropped)
+}
+
+fn count(item: &Option<ItemStack>) -> u8 {
+    item.as_ref().map(|v| v.count).unwrap_or(0)
+}
+
+#[test]
+fn clicks_move_items_in_inventory() {
+    let mut player = test_player("Clicker");
+    player.inventory.main[0] = ItemStack::of("minecraft:stone", 32);
+    // Hotbar's first slot is window's slot 36
+    let result = click(
+        &mut player,
+        Click {
+            slot: 36,
+            button: 0,
+            mode: 0,
+        },
+    )
+    .unwrap();
+    assert_eq!(result.clicked, ItemStack::of("minecraft:stone", 32));
+    assert_eq!(
+        (
+            count(&player.windows.cursor),
+            count(&player.inventory.main[0])
+        ),
+        (32, 0)
+    );
+    // Right click puts one item, right click outside throws one item
+    click_slot(&mut player, 9, 1, 0).unwrap();
+    let dropped = click_slot(&mut player, OUTSIDE_SLOT, 1, 0).unwrap();
+    assert_eq!(dropped, vec![ItemStack::of("minecraft:stone", 1).unwrap()]);
+    click_slot(&mut player, 36, 0, 0).unwrap();
+    assert_eq!(
+        (
+            count(&player.inventory.main[9]),
+            count(&player.inventory.main[0])
+        ),
+        (1, 30)
+    );
+    // Shift-click from hotbar fills similar stack in main inventory
+    click_slot(&mut player, 36, 0, 1).unwrap();
+    assert_eq!(
+        (
+            count(&player.inventory.main[9]),
+            count(&player.inventory.main[0])
+        ),
+        (31, 0)
+    );
+    // Number key swaps slot with hotbar
+    click_slot(&mut player, 9, 2, 2).unwrap();
+    assert_eq!(
+        (
+            count(&player.inventory.main[2]),
+            count(&player.inventory.main[9])
+        ),
+        (31, 0)
+    );
+    // Only helmet can be put into helmet's slot
+    click_slot(&mut player, 38, 0, 0).unwrap();
+    click_slot(&mut player, 5, 0, 0).unwrap();
+    assert_eq!(
+        (
+            count(&player.windows.cursor),
+            count(&player.inventory.armor[3])
+        ),
+        (31, 0)
+    );
+    click_slot(&mut player, 38, 0, 0).unwrap();
+    player.inventory.main[4] = ItemStack::of("minecraft:iron_helmet", 1);
+    click_slot(&mut player, 40, 0, 1).unwrap();
+    assert_eq!(
+        player.inventory.armor[3],
+        ItemStack::of("minecraft:iron_helmet", 1)
+    );
+    // Dropping with Q from slot
+    let dropped = click_slot(&mut player, 38, 0, 4).unwrap();
+    assert_eq!(
+        (dropped[0].count, count(&player.inventory.main[2])),
+        (1, 30)
+    );
+    // Slots outside of window and unknown modes aren't valid
+    assert!(click_slot(&mut player, 46, 0, 0).is_none());
+    assert!(click_slot(&mut player, 9, 0, 7).is_none());
+    assert_eq!(window_items(&player).len(), 46);
+    assert_eq!(window_items(&player)[5], player.inventory.armor[3]);
+}
+
+#[test]
+fn dragging_and_double_click_spread_and_collect_items() {
+    let mut player = test_player("Painter");
+    player.windows.cursor = ItemStack::of("minecraft:dirt", 64);
+    // Left drag splits cursor evenly between slots
+    click_slot(&mut player, OUTSIDE_SLOT, 0, 5).unwrap();
+    for slot in [9, 10, 11] {
+        click_slot(&mut player, slot, 1, 5).unwrap();
+    }
+    click_slot(&mut player, OUTSIDE_SLOT, 2, 5).unwrap();
+    assert_eq!(count(&player.windows.cursor), 1);
+    assert!(player.inventory.main[9..12].iter().all(|v| count(v) == 21));
+    // Right drag puts one item into slot
+    player.windows.cursor = ItemStack::of("minecraft:dirt", 2);
+    click_slot(&mut player, OUTSIDE_SLOT, 4, 5).unwrap();
+    click_slot(&mut player, 12, 5, 5).unwrap();
+    click_slot(&mut player, OUTSIDE_SLOT, 6, 5).unwrap();
+    assert_eq!(
+        (
+            count(&player.windows.cursor),
+            count(&player.inventory.main[12])
+        ),
+        (1, 1)
+    );
+    // Broken drag's sequence isn't valid and middle drag needs creative
+    assert!(click_slot(&mut player, OUTSIDE_SLOT, 2, 5).is_none());
+    assert!(click_slot(&mut player, OUTSIDE_SLOT, 8, 5).is_none());
+    // Double click collects similar items into cursor
+    click_slot(&mut player, 13, 0, 6).unwrap();
+    assert_eq!(count(&player.windows.cursor), 64);
+    assert_eq!(count(&player.inventory.main[11]), 0);
+}
+
+#[test]
+fn shift_click_moves_items_between_container_and_inventory() {
+    let mut player = test_player("Looter");
+    player.ender_chest[0] = ItemStack::of("minecraft:apple", 5);
+    player.windows.open = Some(Window {
+        id: 1,
+        kind: String::from("minecraft:chest"),
+        title: String::from("Ender Chest"),
+        contents: Contents::EnderChest,
+    });
+    assert_eq!(window_items(&player).len(), 63);
+    // Container's items go to the last hotbar's slot first
+    click_slot(&mut player, 0, 0, 1).unwrap();
+    assert_eq!(
+        player.inventory.main[8],
+        ItemStack::of("minecraft:apple", 5)
+    );
+    // Last hotbar's slot is window's slot 62
+    click_slot(&mut player, 62, 0, 1).unwrap();
+    assert_eq!(player.ender_chest[0], ItemStack::of("minecraft:apple", 5));
+    // Number key swaps container's slot with hotbar
+    player.inventory.main[3] = ItemStack::of("minecraft:torch", 4);
+    click_slot(&mut player, 2, 3, 2).unwrap();
+    assert_eq!(player.ender_chest[2], ItemStack::of("minecraft:torch", 4));
+    assert_eq!(player.inventory.main[3], None);
+}